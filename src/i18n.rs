@@ -30,30 +30,48 @@ pub enum Msg {
     HelpAttachs,
     HelpAudio,
     HelpAutoDefaults,
+    HelpAutoDelays,
+    HelpAutoDispositions,
     HelpAutoEncs,
     HelpAutoFlags,
     HelpAutoForceds,
     HelpAutoLangs,
     HelpAutoNames,
+    HelpBitrates,
     HelpChapters,
+    HelpCodecs,
+    HelpComments,
+    HelpCut,
     HelpDefaults,
+    HelpDelays,
     HelpDepth,
+    HelpDryRun,
+    HelpDubs,
+    HelpEdition,
+    HelpExactAudio,
     HelpExitOnErr,
     HelpFonts,
     HelpForceds,
     HelpGlobalOptions,
+    HelpHearingImpaireds,
     HelpHelp,
     HelpIOOptions,
     HelpInput,
     HelpJobs,
+    HelpKeywords,
     HelpLangs,
     HelpListContainers,
     HelpListLangs,
     HelpListTargets,
     HelpLoad,
     HelpLocale,
+    HelpMaxComments,
     HelpMaxDefaults,
+    HelpMaxDubs,
     HelpMaxForceds,
+    HelpMaxHearingImpaireds,
+    HelpMaxOriginals,
+    HelpMaxVisualImpaireds,
     HelpNames,
     HelpNoAttachs,
     HelpNoAudio,
@@ -63,27 +81,42 @@ pub enum Msg {
     HelpNoStreams,
     HelpNoSubs,
     HelpNoVideo,
+    HelpOriginals,
     HelpOtherOptions,
     HelpOutput,
     HelpParts,
+    HelpPlanIn,
+    HelpPlanOut,
     HelpPro,
     HelpQuiet,
     HelpRange,
     HelpReencode,
+    HelpReencodeUnsupported,
     HelpRetimingOptions,
     HelpSaveConfig,
     HelpSaveStreams,
+    HelpSegmentsCache,
+    HelpSegmentsDir,
     HelpSkip,
+    HelpSmartCut,
     HelpSolo,
     HelpSortFonts,
+    HelpSplitForcedPgs,
     HelpStreams,
+    HelpSubFormat,
+    HelpSubStretch,
+    HelpSubStyle,
     HelpSubs,
+    HelpSyncAudio,
+    HelpSyncSubs,
     HelpTargetHelp,
     HelpTargetOptions,
     HelpVerbosity,
     HelpVersion,
     HelpVideo,
+    HelpVisualImpaireds,
     InstallIt,
+    LCodec,
     LMedia,
     LMultipleTracksOrTypeTrack,
     LangNotSupLog,
@@ -98,6 +131,7 @@ pub enum Msg {
     NotMuxedAny,
     NotOutSaveAny,
     NotRecognizedMedia,
+    Reencoding,
     RunCommand,
     RunningCommand,
     Skipping,
@@ -9,30 +9,48 @@ impl_msg_as_str!(
     HelpAttachs => "[!]Save other attachments",
     HelpAudio => "[!]Save audio streams",
     HelpAutoDefaults => "Auto set default flags",
+    HelpAutoDelays => "Auto set delays from file names",
+    HelpAutoDispositions => "Auto set other flags from sources",
     HelpAutoEncs => "Auto set subs encodings",
     HelpAutoFlags => "Auto flags",
     HelpAutoForceds => "Auto set forced flags",
     HelpAutoLangs => "Auto set stream langs",
     HelpAutoNames => "Auto set stream names",
+    HelpBitrates => "Set encoder bitrates",
     HelpChapters => "Save chapters from file",
+    HelpCodecs => "Set encoder codecs",
+    HelpComments => "Set commentary flags",
+    HelpCut => "[!]Save time ranges, w/o chapters",
     HelpDefaults => "Set default flags",
+    HelpDelays => "Set stream delays in ms",
     HelpDepth => "Scan subdirectories up to this depth",
+    HelpDryRun => "Print track layout without muxing",
+    HelpDubs => "Set dub flags",
+    HelpEdition => "Retime by matroska chapter edition",
+    HelpExactAudio => "Trim audio exactly on parts by discard padding",
     HelpExitOnErr => "Skip muxing next files if error occurs",
     HelpFonts => "[!]Save font attachments",
     HelpForceds => "Set forced flags",
     HelpGlobalOptions => "Global options",
+    HelpHearingImpaireds => "Set hearing impaired flags",
     HelpHelp => "Show help",
     HelpIOOptions => "I/O options",
     HelpInput => "Top-level media directory",
     HelpJobs => "Max parallel muxing",
+    HelpKeywords => "Load keyword dictionaries from JSON",
     HelpLangs => "Set stream languages",
     HelpListContainers => "Show supported output containers",
     HelpListLangs => "Show supported language codes",
     HelpListTargets => "Show supported targets",
     HelpLoad => "Load config from JSON",
     HelpLocale => "Locale language (for logging and sort)",
-    HelpMaxDefaults => "Max auto-enabled default",
-    HelpMaxForceds => "Max auto-enabled forced",
+    HelpMaxComments => "Max auto-enabled commentary (default: unlimited)",
+    HelpMaxDefaults => "Max auto-enabled default (default: 1)",
+    HelpMaxDubs => "Max auto-enabled dub (default: unlimited)",
+    HelpMaxForceds => "Max auto-enabled forced (default: 0)",
+    HelpMaxHearingImpaireds => "Max auto-enabled hearing impaired (default: unlimited)",
+    HelpMaxOriginals => "Max auto-enabled original (default: unlimited)",
+    HelpMaxVisualImpaireds => "Max auto-enabled visual impaired (default: unlimited)",
     HelpNames => "Set stream names",
     HelpNoAttachs => "Don't save any other attachment",
     HelpNoAudio => "Don't save any audio stream",
@@ -42,27 +60,42 @@ impl_msg_as_str!(
     HelpNoStreams => "Don't save any stream",
     HelpNoSubs => "Don't save any subtitle stream",
     HelpNoVideo => "Don't save any video stream",
+    HelpOriginals => "Set original flags",
     HelpOtherOptions => "Other options",
     HelpOutput => "Output paths pattern: out{num}[put]",
     HelpParts => "[!]Save parts for chapter names",
+    HelpPlanIn => "Mux by plan from JSON without auto",
+    HelpPlanOut => "Save mux plan to JSON",
     HelpPro => "Disable all auto below",
     HelpQuiet => "Suppress logging",
     HelpRange => "Number range of media-files",
-    HelpReencode => "Force reencode",
+    HelpReencode => "Reencode all audio and video streams",
+    HelpReencodeUnsupported => "Reencode streams the output container can't copy",
     HelpRetimingOptions => "Retiming options",
     HelpSaveConfig => "Save config to JSON in the input directory",
     HelpSaveStreams => "Save streams",
+    HelpSegmentsCache => "Index of linked segments to reuse between runs",
+    HelpSegmentsDir => "Search linked segments also in directory recursively",
     HelpSkip => "Patterns of files to skip",
+    HelpSmartCut => "Cut exactly on chapters, re-encode boundary GOPs (H.264, HEVC, or if an encoder matches the source headers)",
     HelpSolo => "Process media without external tracks",
     HelpSortFonts => "On/Off sort in-files fonts",
+    HelpSplitForcedPgs => "[!]Split forced captions of PGS subtitles into a track",
     HelpStreams => "[!]Save streams",
+    HelpSubFormat => "Convert text subtitles to format",
+    HelpSubStretch => "Stretch text subtitle timings",
+    HelpSubStyle => "Take ASS styles from file",
     HelpSubs => "[!]Save subtitle streams",
+    HelpSyncAudio => "Detect delays of external audio by the audio of video",
+    HelpSyncSubs => "Align external text subtitles with speech of the audio of video",
     HelpTargetHelp => "Set next options for target",
     HelpTargetOptions => "Target options",
     HelpVerbosity => "Increase logging",
     HelpVersion => "Show version",
     HelpVideo => "[!]Save video streams",
+    HelpVisualImpaireds => "Set visual impaired flags",
     InstallIt => "Please install it, add to system PATH and re-run",
+    LCodec => "codec",
     LMedia => "media",
     LMultipleTracksOrTypeTrack => "multiple tracks or type track",
     LangNotSupLog => "Language is not supported for logging",
@@ -77,6 +110,7 @@ impl_msg_as_str!(
     NotMuxedAny => "Not muxed any media",
     NotOutSaveAny => "Not found any save stream for output",
     NotRecognizedMedia => "Not recognized media file",
+    Reencoding => "Reencoding",
     RunCommand => "Run command",
     RunningCommand => "Running command",
     Skipping => "Skipping",
@@ -91,7 +125,6 @@ impl_msg_as_str!(
  4. Global (default)"#,
     ListContainers => r#"Supported containers:
  1. Matroska (.mkv). Universal default container
- 2. AVI (.avi). Legacy
- 3. MP4 (.mp4). MPEG-4 Part 10
- 4. Webm (.webm). YouTube format"#,
+ 2. MP4 (.mp4). MPEG-4 Part 14. Without fonts, attachments and non-MP4 codecs
+ 3. WebM (.webm, .weba). Browser playable. VP8/VP9/AV1, Opus/Vorbis and WebVTT only"#,
 );
@@ -9,30 +9,48 @@ impl_msg_as_str!(
     HelpAttachs => "[!]Сохранить другие вложения",
     HelpAudio => "[!]Сохранить аудио потоки",
     HelpAutoDefaults => "Автонастройка default флагов",
+    HelpAutoDelays => "Автонастройка задержек из имен файлов",
+    HelpAutoDispositions => "Автонастройка остальных флагов из источников",
     HelpAutoEncs => "Автонастройка кодировки субтитров",
     HelpAutoFlags => "Автофлаги",
     HelpAutoForceds => "Автонастройка forced флагов",
     HelpAutoLangs => "Автонастройка языков потоков",
     HelpAutoNames => "Автонастройка имен потоков",
+    HelpBitrates => "Установить битрейты кодировщика",
     HelpChapters => "Сохранить главы из файла",
+    HelpCodecs => "Установить кодеки кодировщика",
+    HelpComments => "Установить commentary флаги",
+    HelpCut => "[!]Сохранить временные диапазоны, без глав",
     HelpDefaults => "Установить default флаги",
+    HelpDelays => "Установить задержки потоков в мс",
     HelpDepth => "Сканировать подкаталоги до этой глубины",
+    HelpDryRun => "Вывести раскладку дорожек без муксинга",
+    HelpDubs => "Установить dub флаги",
+    HelpEdition => "Ретайминг по редакции глав matroska",
+    HelpExactAudio => "Обрезать аудио точно по частям через discard padding",
     HelpExitOnErr => "Пропустить мукс следующих файлов при ошибке",
     HelpFonts => "[!]Сохранить вложенные шрифты",
     HelpForceds => "Установить forced флаги",
     HelpGlobalOptions => "Глобальные опции",
+    HelpHearingImpaireds => "Установить hearing impaired флаги",
     HelpHelp => "Показать справку",
     HelpIOOptions => "I/O опции",
     HelpInput => "Директория медиа верхнего уровня",
     HelpJobs => "Максимум параллельных муксирований",
+    HelpKeywords => "Загрузить словари ключевых слов из JSON",
     HelpLangs => "Установить языки потоков",
     HelpListContainers => "Показать поддерживаемые выходные контейнеры",
     HelpListLangs => "Показать поддерживаемые языковые коды",
     HelpListTargets => "Показать поддерживаемые цели",
     HelpLoad => "Загрузить конфиг из JSON",
     HelpLocale => "Язык локали (для логирования и сортировки)",
-    HelpMaxDefaults => "Максимум включаемых в авто default",
-    HelpMaxForceds => "Максимум включаемых в авто forced",
+    HelpMaxComments => "Максимум включаемых в авто commentary (по умолчанию без ограничения)",
+    HelpMaxDefaults => "Максимум включаемых в авто default (по умолчанию 1)",
+    HelpMaxDubs => "Максимум включаемых в авто dub (по умолчанию без ограничения)",
+    HelpMaxForceds => "Максимум включаемых в авто forced (по умолчанию 0)",
+    HelpMaxHearingImpaireds => "Максимум включаемых в авто hearing impaired (по умолчанию без ограничения)",
+    HelpMaxOriginals => "Максимум включаемых в авто original (по умолчанию без ограничения)",
+    HelpMaxVisualImpaireds => "Максимум включаемых в авто visual impaired (по умолчанию без ограничения)",
     HelpNames => "Установить имена потоков",
     HelpNoAttachs => "Не сохранять другие вложения",
     HelpNoAudio => "Не сохранять аудио потоки",
@@ -42,27 +60,42 @@ impl_msg_as_str!(
     HelpNoStreams => "Не сохранять потоки",
     HelpNoSubs => "Не сохранять потоки субтитров",
     HelpNoVideo => "Не сохранять видео потоки",
+    HelpOriginals => "Установить original флаги",
     HelpOtherOptions => "Другие опции",
     HelpOutput => "Паттерн выходных файлов: out{num}[put]",
     HelpParts => "[!]Сохранить части для имен глав",
+    HelpPlanIn => "Муксить по плану из JSON без автонастроек",
+    HelpPlanOut => "Сохранить план муксинга в JSON",
     HelpPro => "Отключить все автофлаги ниже",
     HelpQuiet => "Подавить логирование",
     HelpRange => "Диапазон номеров медиа-файлов",
-    HelpReencode => "Форсировать перекодирование",
+    HelpReencode => "Перекодировать все аудио и видео потоки",
+    HelpReencodeUnsupported => "Перекодировать потоки, которые выходной контейнер не может скопировать",
     HelpRetimingOptions => "Ретайминг опции",
     HelpSaveConfig => "Сохранить конфиг в JSON в директории медиа",
     HelpSaveStreams => "Сохранить потоки",
+    HelpSegmentsCache => "Индекс линкованных сегментов для повторных запусков",
+    HelpSegmentsDir => "Искать линкованные сегменты также в директории рекурсивно",
     HelpSkip => "Паттерны пропуска файлов",
+    HelpSmartCut => "Резать точно по главам, перекодируя граничные GOP (H.264, HEVC, или если кодировщик совпадает с заголовками источника)",
     HelpSolo => "Обработать медиа без внешних дорожек",
     HelpSortFonts => "Вкл/Выкл сортировку вложенных шрифтов",
+    HelpSplitForcedPgs => "[!]Выделить принудительные субтитры PGS в дорожку",
     HelpStreams => "[!]Сохранить потоки",
+    HelpSubFormat => "Конвертировать текстовые субтитры в формат",
+    HelpSubStretch => "Растянуть тайминги текстовых субтитров",
+    HelpSubStyle => "Взять стили ASS из файла",
     HelpSubs => "[!]Сохранить потоки субтитров",
+    HelpSyncAudio => "Определить задержки внешнего аудио по аудио видео",
+    HelpSyncSubs => "Выровнять внешние текстовые субтитры по речи в аудио видео",
     HelpTargetHelp => "Установить следующие опции для цели",
     HelpTargetOptions => "Целевые опции",
     HelpVerbosity => "Увеличить логирование",
     HelpVersion => "Показать версию",
     HelpVideo => "[!]Сохранить видео потоки",
+    HelpVisualImpaireds => "Установить visual impaired флаги",
     InstallIt => "Пожалуйста, установите его, добавьте в системный PATH и перезапустите",
+    LCodec => "кодек",
     LMedia => "медиа",
     LMultipleTracksOrTypeTrack => "несколько дорожек или тип дорожки",
     LangNotSupLog => "Язык не поддерживается для ведения журнала",
@@ -77,6 +110,7 @@ impl_msg_as_str!(
     NotMuxedAny => "Медиа не муксированы",
     NotOutSaveAny => "Не найдено сохраняемого потока для выходного файла",
     NotRecognizedMedia => "Не распознан медиа файл",
+    Reencoding => "Перекодирование",
     RunCommand => "Выполнить команду",
     RunningCommand => "Выполнение команды",
     Skipping => "Пропуск",
@@ -91,7 +125,6 @@ impl_msg_as_str!(
  4. Global (по умолчанию)"#,
     ListContainers => r#"Поддерживаемые контейнеры:
  1. Matroska (.mkv). Универсальный контейнер по умолчанию
- 2. AVI (.avi). Устаревший
- 3. MP4 (.mp4). MPEG-4 Part 10
- 4. Webm (.webm). YouTube формат"#,
+ 2. MP4 (.mp4). MPEG-4 Part 14. Без шрифтов, вложений и не-MP4 кодеков
+ 3. WebM (.webm, .weba). Воспроизводится в браузере. Только VP8/VP9/AV1, Opus/Vorbis и WebVTT"#,
 );
@@ -1,5 +1,5 @@
-use crate::{Container, Msg, MuxError};
-use log::{debug, warn};
+use crate::{CodecId, Container, Msg, MuxError};
+use log::{debug, info, warn};
 use std::{ffi::OsStr, path::Path};
 
 pub(crate) fn warn_container_does_not_support(cont: Container, src: &Path, i_stream: usize) {
@@ -14,6 +14,45 @@ pub(crate) fn warn_container_does_not_support(cont: Container, src: &Path, i_str
     );
 }
 
+pub(crate) fn warn_container_does_not_support_codec(
+    cont: Container,
+    codec: CodecId,
+    src: &Path,
+    i_stream: usize,
+) {
+    warn!(
+        "{} {} {} '{}'. {} '{}' stream {}",
+        cont,
+        Msg::ContainerDoesNotSupport,
+        Msg::LCodec,
+        codec.name(),
+        Msg::Skipping,
+        src.display(),
+        i_stream
+    );
+}
+
+pub(crate) fn info_reencode_unsupported_codec(
+    cont: Container,
+    codec: CodecId,
+    to: CodecId,
+    src: &Path,
+    i_stream: usize,
+) {
+    info!(
+        "{} {} {} '{}'. {} '{}' stream {}: '{}' -> '{}'",
+        cont,
+        Msg::ContainerDoesNotSupport,
+        Msg::LCodec,
+        codec.name(),
+        Msg::Reencoding,
+        src.display(),
+        i_stream,
+        codec.name(),
+        to.name()
+    );
+}
+
 #[inline(always)]
 pub(crate) fn warn_file_is_already_exists(path: &Path) {
     warn!(
@@ -35,18 +35,27 @@ pub use traits::{
 
 pub use types::{
     arc_path_buf::ArcPathBuf,
+    audio_sync::AudioSync,
     auto_flags::AutoFlags,
+    bitrate::Bitrate,
     chapters::Chapters,
     char_encoding::CharEncoding,
     cli_arg::CliArg,
     codec_id::CodecId,
     config::{Config, ConfigTarget},
     container::Container,
-    dispositions::{DefaultDispositions, Dispositions, ForcedDispositions, ty::DispositionType},
+    delay::Delay,
+    dispositions::{
+        CommentDispositions, DefaultDispositions, Dispositions, DubDispositions,
+        ForcedDispositions, HearingImpairedDispositions, OriginalDispositions,
+        VisualImpairedDispositions, ty::DispositionType,
+    },
     duration::Duration,
     extension::Extension,
     globset_pattern::GlobSetPattern,
     input::{Input, InputFileType, iters::MediaGroupedByStem},
+    keyword::Keyword,
+    keywords::Keywords,
     lang::{Lang, LangCode},
     log_level::LogLevel,
     media_info::{
@@ -54,18 +63,25 @@ pub use types::{
         cache::{CacheMI, CacheMIOfFile, CacheMIOfGroup, CacheState},
     },
     media_number::MediaNumber,
-    metadata::{LangMetadata, Metadata, NameMetadata},
+    metadata::{
+        BitrateMetadata, CodecMetadata, DelayMetadata, LangMetadata, Metadata, NameMetadata,
+    },
     mux_error::{MuxError, kind::MuxErrorKind},
     mux_logger::MuxLogger,
+    mux_plan::{MuxPlan, MuxPlanGroup, MuxPlanTrack},
     output::Output,
     range::RangeUsize,
-    retiming::options::{RetimingOptions, RetimingOptionsParts},
+    retiming::options::{
+        RetimingEdition, RetimingOptions, RetimingOptionsCut, RetimingOptionsParts,
+        RetimingOptionsSegments,
+    },
     stream::{
         Stream,
-        order::{StreamsOrder, StreamsOrderItem},
+        order::{StreamsOrder, StreamsOrderItem, TrackEncoding},
         streams::Streams,
         ty::StreamType,
     },
+    subtitles::{Pgs, SubDensity, SubFormat, SubStretch, SubSync, SubType, Subs},
     target::Target,
     value::Value,
 };
@@ -78,6 +94,7 @@ use is_default::IsDefault;
 use functions::add_copy_stream;
 use types::{
     helpers,
-    retiming::{RetimedStream, Retiming, RetimingChapter},
+    retiming::{RetimedStream, Retiming, RetimingChapter, editions::Edition, subs::shift_subs},
     stream::supported::StreamsSupported,
+    subtitles::try_extract_sub,
 };
@@ -1,10 +1,13 @@
 // Get Field Config markers
 pub use crate::types::config::fields::{
-    CfgChapters, CfgDefaults, CfgForceds, CfgLangs, CfgNames, CfgStreams,
+    CfgBitrates, CfgChapters, CfgCodecs, CfgComments, CfgDefaults, CfgDelays, CfgDubs, CfgForceds,
+    CfgHearingImpaireds, CfgLangs, CfgNames, CfgOriginals, CfgStreams, CfgSubFormat,
+    CfgVisualImpaireds,
 };
 
 // Get Field MediaInfo markers
 pub use crate::types::media_info::lazy_fields::{
-    MIAudioDuration, MICache, MICmnStem, MICmnStreamsOrder, MIPathTail, MIPlayableDuration,
-    MIRelativeUpmost, MIStreams, MISubCharEncoding, MITargetPaths, MIVideoDuration,
+    MIAudioDuration, MICache, MICmnStem, MICmnStreamsOrder, MIDelay, MIPathTail,
+    MIPlayableDuration, MIRelativeUpmost, MIStreams, MISubCharEncoding, MISubDensity,
+    MITargetPaths, MIVideoDuration,
 };
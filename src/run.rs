@@ -1,11 +1,13 @@
 mod buf_packets;
 mod current;
+mod dry_run;
 mod encoder;
 mod header;
 mod init_external_fonts;
+mod plan;
 
 use crate::{
-    Config, MediaInfo, Msg, MuxError, MuxLogger, Result, StreamsOrder, TryFinalizeInit,
+    Config, MediaInfo, Msg, MuxError, MuxLogger, MuxPlan, Result, StreamsOrder, TryFinalizeInit,
     ffmpeg::{self, format},
     markers::*,
 };
@@ -65,6 +67,10 @@ pub fn run() -> Result<()> {
     let result = cfg.mux();
     cfg.output.remove_created_dirs();
 
+    if cfg.dry_run {
+        return result.map(|_| ());
+    }
+
     result.map(|cnt| match cnt {
         0 => warn!("{}", Msg::NotMuxedAny),
         _ => {
@@ -82,25 +88,58 @@ impl Config {
     /// - **Only if** [`Config::exit_on_err`] is true.
     ///
     /// - Returns an error if one occurs during processing.
+    ///
+    /// - Fails read [`Config::plan_in`] or write [`Config::plan_out`].
     #[inline]
     pub fn mux(&self) -> Result<usize> {
         let fonts = init_external_fonts::init_external_fonts(self);
         let cnt = Mutex::new(0usize);
-        let it = Mutex::new(self.input.iter_media_grouped_by_stem());
+
+        if let Some(json) = &self.plan_in {
+            let plan = MuxPlan::try_read(json)?;
+            self.try_for_each_group(plan.iter(), |mi, g| {
+                current::mux_plan_group(self, fonts.as_ref(), &cnt, mi, g)
+            })?;
+            return Ok(cnt.into_inner().unwrap_or(0));
+        }
+
+        let plan = self
+            .plan_out
+            .as_ref()
+            .map(|_| Mutex::new(MuxPlan::default()));
+
+        self.try_for_each_group(self.input.iter_media_grouped_by_stem(), |mi, g| {
+            current::mux_current_files(self, fonts.as_ref(), &cnt, plan.as_ref(), mi, g)
+        })?;
+
+        if let (Some(json), Some(plan)) = (&self.plan_out, plan) {
+            let mut plan = plan.into_inner().unwrap_or_default();
+            plan.0.sort_by(|a, b| a.output.cmp(&b.output));
+            plan.try_write(json)
+                .map_err(|e| err!("Fail write mux plan '{}': {}", json.display(), e))?;
+        }
+
+        Ok(cnt.into_inner().unwrap_or(0))
+    }
+
+    fn try_for_each_group<T, F>(&self, it: impl Iterator<Item = T> + Send, f: F) -> Result<()>
+    where
+        T: Send,
+        F: Fn(&mut MediaInfo, T) -> Result<()> + Sync,
+    {
+        let it = Mutex::new(it);
 
         (0..self.jobs).into_par_iter().try_for_each(|j| {
             let mut mi = MediaInfo::new(self, j);
             loop {
                 let g = { it.lock().map_or(None, |mut it| it.next()) };
                 match g {
-                    Some(g) => current::mux_current_files(self, fonts.as_ref(), &cnt, &mut mi, g)?,
+                    Some(g) => f(&mut mi, g)?,
                     None => return Ok::<(), MuxError>(()),
                 }
                 mi.clear();
             }
-        })?;
-
-        Ok(cnt.into_inner().unwrap_or(0))
+        })
     }
 }
 
@@ -108,7 +147,7 @@ impl MediaInfo<'_> {
     /// Tries muxing all files from [`MediaInfo::cache`] to `dest`.
     pub fn mux_files(&mut self, dest: &Path) -> Result<()> {
         let order = self.try_take_cmn(MICmnStreamsOrder)?;
-        let mut octx = format::output(dest)?;
+        let mut octx = format::output_as(dest, self.cfg.container.as_format())?;
         let (mut icontexts, mut encoders, idx_map) = header::write_header(self, &order, &mut octx)?;
 
         let mut iters: Vec<_> = icontexts
@@ -164,7 +203,6 @@ impl MediaInfo<'_> {
             enc.finalize(&mut octx)?;
         }
 
-        copy_chapters(self, &order, &icontexts, &mut octx);
         self.set_cmn(MICmnStreamsOrder, order);
 
         octx.write_trailer()?;
@@ -1,6 +1,6 @@
 use crate::{
-    ArcPathBuf, CacheMIOfFile, Config, MediaInfo, MuxError, Result, i18n::logs, markers::MICmnStem,
-    types::input::iters::MediaGroupedByStem,
+    ArcPathBuf, CacheMIOfFile, Config, MediaInfo, MuxError, MuxPlan, MuxPlanGroup, Result,
+    i18n::logs, markers::MICmnStem, types::input::iters::MediaGroupedByStem,
 };
 use log::error;
 use std::{
@@ -13,6 +13,7 @@ pub fn mux_current_files(
     cfg: &Config,
     fonts: Option<&(ArcPathBuf, CacheMIOfFile)>,
     cnt: &Mutex<usize>,
+    plan: Option<&Mutex<MuxPlan>>,
     mi: &mut MediaInfo<'_>,
     m: MediaGroupedByStem,
 ) -> Result<()> {
@@ -24,7 +25,60 @@ pub fn mux_current_files(
         MuxCurrent::Err(e) => return Err(e),
     }
 
-    match mi.mux_files(&out) {
+    if let (Some(plan), Some(json)) = (plan, cfg.plan_out.as_deref()) {
+        match mi.try_plan_group(json, &out) {
+            Ok(g) => {
+                if let Ok(mut plan) = plan.lock() {
+                    plan.0.push(g);
+                }
+            }
+            Err(e) if cfg.exit_on_err => return Err(e),
+            Err(e) => {
+                error!("{}", e);
+                return Ok(());
+            }
+        }
+    }
+
+    mux_or_print(cfg, cnt, mi, &out)
+}
+
+/// Muxes a group from the [`MuxPlan`], taking tracks as is.
+pub fn mux_plan_group(
+    cfg: &Config,
+    fonts: Option<&(ArcPathBuf, CacheMIOfFile)>,
+    cnt: &Mutex<usize>,
+    mi: &mut MediaInfo<'_>,
+    g: &MuxPlanGroup,
+) -> Result<()> {
+    let out = &g.output;
+    if !cfg.dry_run && out.exists() {
+        logs::warn_file_is_already_exists(out);
+        return Ok(());
+    }
+
+    if let Some((f, cache)) = fonts {
+        mi.cache.of_files.insert(f.clone(), cache.clone());
+    }
+
+    match mi.try_init_plan_group(g) {
+        Ok(()) => mux_or_print(cfg, cnt, mi, out),
+        Err(e) if cfg.exit_on_err => Err(e),
+        Err(e) => {
+            error!("{}", e);
+            Ok(())
+        }
+    }
+}
+
+fn mux_or_print(cfg: &Config, cnt: &Mutex<usize>, mi: &mut MediaInfo, out: &Path) -> Result<()> {
+    let result = if cfg.dry_run {
+        mi.print_dry_run(out)
+    } else {
+        mi.mux_files(out)
+    };
+
+    match result {
         Ok(()) => {
             if let Ok(mut cnt) = cnt.lock() {
                 *cnt += 1;
@@ -44,7 +98,8 @@ fn init_current_files(
     files: Vec<PathBuf>,
     out: &Path,
 ) -> MuxCurrent<()> {
-    if out.exists() {
+    // A dry run writes nothing, so an existing output is listed too.
+    if !mi.cfg.dry_run && out.exists() {
         logs::warn_file_is_already_exists(out);
         return MuxCurrent::Continue;
     }
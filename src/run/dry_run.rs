@@ -0,0 +1,157 @@
+use super::{encoder, header};
+use crate::{DispositionType, Edition, IsDefault, MediaInfo, Result, StreamsOrder, markers::*};
+use enum_map::EnumMap;
+use std::{
+    io::{self, Write},
+    path::Path,
+};
+
+const HEADER: [&str; 11] = [
+    "#", "Source", "Stream", "Type", "Codec", "Delay", "Lang", "Name", "Default", "Forced", "Other",
+];
+
+impl MediaInfo<'_> {
+    /// Prints a track layout of muxing all files from [`MediaInfo::cache`] to `dest`
+    /// without writing it.
+    pub fn print_dry_run(&mut self, dest: &Path) -> Result<()> {
+        let layout = self.dry_run_layout(dest)?;
+        io::stdout().lock().write_all(layout.as_bytes())?;
+        Ok(())
+    }
+
+    /// Returns a track layout as [`MediaInfo::print_dry_run`] prints it, with codecs and
+    /// delays resolved as muxing applies them.
+    pub fn dry_run_layout(&mut self, dest: &Path) -> Result<String> {
+        let order = self.try_take_cmn(MICmnStreamsOrder)?;
+        let rows = self.dry_run_rows(&order);
+        let editions = self.dry_run_editions(&order);
+        self.set_cmn(MICmnStreamsOrder, order);
+
+        let mut table = format_table(dest, rows);
+        table.push_str(&editions);
+        Ok(table)
+    }
+
+    /// Lists chapter editions of the matroska video if there's a choice of them.
+    fn dry_run_editions(&self, order: &StreamsOrder) -> String {
+        let src = some_or!(
+            order.iter().find(|ord| ord.ty.is_video()),
+            return String::new()
+        );
+        let mat = some_or!(matroska::open(&src.key).ok(), return String::new());
+        let editions = Edition::list(&src.key, &mat);
+        if editions.len() < 2 {
+            return String::new();
+        }
+
+        let selected = self
+            .cfg
+            .retiming_options
+            .edition
+            .as_ref()
+            .and_then(|ed| ed.find(&src.key, &mat));
+
+        let mut s = String::from("  Editions:\n");
+        for (i, ed) in editions.iter().enumerate() {
+            let flags: Vec<&str> = [
+                (ed.is_default, "default"),
+                (ed.is_hidden, "hidden"),
+                (ed.is_ordered, "ordered"),
+                (ed.is_linked, "linked"),
+            ]
+            .into_iter()
+            .filter_map(|(is, flag)| is.then_some(flag))
+            .collect();
+
+            let mark = if selected == Some(i) { "*" } else { " " };
+            let name = ed.name.as_deref().unwrap_or("");
+            let line = format!("    {}{}  {}  {}", mark, i, name, flags.join(","));
+            s.push_str(line.trim_end());
+            s.push('\n');
+        }
+
+        s
+    }
+
+    fn dry_run_rows(&self, order: &StreamsOrder) -> Vec<[String; HEADER.len()]> {
+        let auto = self.cfg.auto_flags.map_dispositions();
+        let mut counts = EnumMap::default();
+
+        order
+            .iter()
+            .enumerate()
+            .filter_map(|(i, ord)| {
+                let stream = self.immut(MIStreams, &ord.key)?.get(ord.key_i_stream)?;
+                let ds = header::new_dispositions(self, &auto, &mut counts, ord, stream);
+
+                let src = ord
+                    .key
+                    .strip_prefix(&self.cfg.input.dir)
+                    .unwrap_or(&ord.key);
+                let enc = encoder::track_encoding(self, ord, stream);
+                let to = enc
+                    .codec
+                    .or_else(|| ord.reencode.map(|to| to.name().to_owned()));
+                let codec = match to {
+                    Some(to) => format!("{} -> {}", stream.codec.name(), to),
+                    None => stream.codec.name().to_owned(),
+                };
+                let delay = match enc.delay.is_default() {
+                    true => String::new(),
+                    false => format!("{}ms", enc.delay),
+                };
+                let name = stream.name.as_ref().map_or("", |n| n.deref().as_str());
+                let flag = |ty: DispositionType| if ds[ty] { "+" } else { "-" };
+                let other: Vec<&str> = ds
+                    .iter()
+                    .filter(|(ty, v)| {
+                        **v && !matches!(ty, DispositionType::Default | DispositionType::Forced)
+                    })
+                    .map(|(ty, _)| ty.as_ref())
+                    .collect();
+
+                Some([
+                    i.to_string(),
+                    src.display().to_string(),
+                    ord.key_i_stream.to_string(),
+                    stream.ty.as_ref().to_owned(),
+                    codec,
+                    delay,
+                    stream.lang.deref().to_string(),
+                    name.to_owned(),
+                    flag(DispositionType::Default).to_owned(),
+                    flag(DispositionType::Forced).to_owned(),
+                    other.join(","),
+                ])
+            })
+            .collect()
+    }
+}
+
+fn format_table(dest: &Path, rows: Vec<[String; HEADER.len()]>) -> String {
+    let mut widths = HEADER.map(|s| s.chars().count());
+    for row in rows.iter() {
+        for (w, s) in widths.iter_mut().zip(row) {
+            *w = (*w).max(s.chars().count());
+        }
+    }
+
+    let mut table = format!("'{}':\n", dest.display());
+    let mut push_row = |row: &[&str]| {
+        let line: Vec<String> = row
+            .iter()
+            .zip(widths)
+            .map(|(s, w)| format!("{:<w$}", s, w = w))
+            .collect();
+        table.push_str("  ");
+        table.push_str(line.join("  ").trim_end());
+        table.push('\n');
+    };
+
+    push_row(&HEADER);
+    for row in rows.iter() {
+        push_row(&row.each_ref().map(|s| s.as_str()));
+    }
+
+    table
+}
@@ -1,16 +1,25 @@
 mod copy;
+mod transcode;
+mod transcode_sub;
 
 pub use copy::EncoderCopy;
+pub use transcode::EncoderTranscode;
+pub use transcode_sub::EncoderTranscodeSub;
 
-use crate::Result;
 use crate::ffmpeg::{
     self, Packet, Rational,
     format::{self, context},
 };
+use crate::{
+    CodecId, Delay, IsDefault, MediaInfo, Result, Stream, StreamsOrderItem, TrackEncoding,
+    markers::*,
+};
 
 #[non_exhaustive]
 pub enum Encoder {
     Copy(EncoderCopy),
+    Transcode(EncoderTranscode),
+    TranscodeSub(EncoderTranscodeSub),
 }
 
 pub trait Encode {
@@ -25,10 +34,86 @@ pub trait Encode {
 
 impl Encoder {
     pub fn new<'a>(
+        mi: &MediaInfo,
+        ord: &StreamsOrderItem,
+        stream: &Stream,
         ist: &format::stream::Stream,
         octx: &'a mut format::context::Output,
     ) -> Result<(ffmpeg::StreamMut<'a>, Self)> {
-        EncoderCopy::new_encoder(ist, octx)
+        let is_cover = ist
+            .disposition()
+            .contains(format::stream::Disposition::ATTACHED_PIC);
+
+        if is_cover || !stream.ty.is_track() {
+            return EncoderCopy::new_encoder(ist, octx, 0);
+        }
+
+        let encoding = track_encoding(mi, ord, stream);
+        let delay = encoding.delay.to_ts(ist.time_base());
+
+        if stream.ty.is_sub() {
+            return match ord.reencode {
+                Some(to) => EncoderTranscodeSub::new_encoder(ist, octx, find_encoder(to)?, delay),
+                None => EncoderCopy::new_encoder(ist, octx, delay),
+            };
+        }
+
+        let TrackEncoding { codec, bitrate, .. } = encoding;
+
+        if !mi.cfg.reencode && ord.reencode.is_none() && codec.is_none() && bitrate.is_none() {
+            return EncoderCopy::new_encoder(ist, octx, delay);
+        }
+
+        let codec = match codec {
+            // The named encoder, or else the default one of the named codec.
+            Some(name) => match ffmpeg::encoder::find_by_name(&name) {
+                Some(codec) => codec,
+                None => find_encoder(CodecId::try_from_encoder_name(&name)?)?,
+            },
+            None => find_encoder(ord.reencode.unwrap_or(CodecId(ist.parameters().id())))?,
+        };
+        EncoderTranscode::new_encoder(ist, octx, codec, bitrate, delay)
+    }
+}
+
+/// Returns the fixed encoding of the track or resolves it by the configuration.
+pub(crate) fn track_encoding(
+    mi: &MediaInfo,
+    ord: &StreamsOrderItem,
+    stream: &Stream,
+) -> TrackEncoding {
+    if let Some(enc) = &ord.encoding {
+        return enc.clone();
+    }
+
+    let cfg = mi.cfg;
+    let target_paths = mi
+        .immut(MITargetPaths, &ord.key)
+        .map_or(&[][..], |xs| xs.as_slice());
+
+    let (i, delays) = cfg.stream_val(CfgDelays, target_paths, stream);
+    let delay = delays
+        .get(&i, &stream.lang)
+        .or_else(|| auto_delay(mi, ord, stream))
+        .copied()
+        .unwrap_or_default();
+
+    if stream.ty.is_sub() {
+        return TrackEncoding {
+            delay,
+            ..Default::default()
+        };
+    }
+
+    let (i, codecs) = cfg.stream_val(CfgCodecs, target_paths, stream);
+    let codec = codecs.get(&i, &stream.lang).cloned();
+    let (i, bitrates) = cfg.stream_val(CfgBitrates, target_paths, stream);
+    let bitrate = bitrates.get(&i, &stream.lang).copied();
+
+    TrackEncoding {
+        delay,
+        codec,
+        bitrate,
     }
 }
 
@@ -36,24 +121,77 @@ impl Encode for Encoder {
     fn set_ist_time_base(&mut self, tb: Rational) {
         match self {
             Self::Copy(enc) => enc.set_ist_time_base(tb),
+            Self::Transcode(enc) => enc.set_ist_time_base(tb),
+            Self::TranscodeSub(enc) => enc.set_ist_time_base(tb),
         }
     }
 
     fn set_ost_time_base(&mut self, tb: Rational) {
         match self {
             Self::Copy(enc) => enc.set_ost_time_base(tb),
+            Self::Transcode(enc) => enc.set_ost_time_base(tb),
+            Self::TranscodeSub(enc) => enc.set_ost_time_base(tb),
         }
     }
 
     fn processing_packet(&mut self, octx: &mut context::Output, packet: &mut Packet) -> Result<()> {
         match self {
             Self::Copy(enc) => enc.processing_packet(octx, packet),
+            Self::Transcode(enc) => enc.processing_packet(octx, packet),
+            Self::TranscodeSub(enc) => enc.processing_packet(octx, packet),
         }
     }
 
     fn finalize(&mut self, octx: &mut context::Output) -> Result<()> {
         match self {
             Self::Copy(enc) => enc.finalize(octx),
+            Self::Transcode(enc) => enc.finalize(octx),
+            Self::TranscodeSub(enc) => enc.finalize(octx),
         }
     }
 }
+
+// Delay hints from file names are for external audio and subs. Delays detected by
+// --sync-audio are applied without auto delays too.
+fn auto_delay<'a>(mi: &'a MediaInfo, ord: &StreamsOrderItem, stream: &Stream) -> Option<&'a Delay> {
+    if !(*mi.cfg.auto_flags.delays || mi.cfg.sync_audio) || stream.ty.is_video() {
+        return None;
+    }
+    mi.immut(MIDelay, &ord.key).filter(|d| !d.is_default())
+}
+
+/// Shifts packet timestamps by the delay in the packet time base.
+///
+/// A sub cue shifted to start before zero is clamped to zero, keeping its end.
+///
+/// Returns `false` if the shifted packet starts before zero (or the sub cue ends by
+/// zero) and should be dropped.
+fn delay_packet(packet: &mut Packet, delay: i64, is_sub: bool) -> bool {
+    if delay == 0 {
+        return true;
+    }
+
+    packet.set_pts(packet.pts().map(|ts| ts + delay));
+    packet.set_dts(packet.dts().map(|ts| ts + delay));
+
+    let ts = some_or!(packet.pts().or(packet.dts()), return true);
+    if ts >= 0 {
+        return true;
+    }
+    let duration = packet.duration();
+    if !is_sub || (duration > 0 && ts + duration <= 0) {
+        return false;
+    }
+
+    packet.set_pts(packet.pts().map(|_| 0));
+    packet.set_dts(packet.dts().map(|_| 0));
+    if duration > 0 {
+        packet.set_duration(ts + duration);
+    }
+    true
+}
+
+fn find_encoder(codec: CodecId) -> Result<ffmpeg::Codec> {
+    ffmpeg::encoder::find(*codec)
+        .ok_or_else(|| err!("Not found encoder for codec '{}'", codec.name()))
+}
@@ -1,7 +1,8 @@
-use super::{Encode, Encoder};
+use super::{Encode, Encoder, delay_packet};
 use crate::ffmpeg::{
     self, Packet, Rational,
     format::{self, context},
+    media,
 };
 use crate::{Result, add_copy_stream};
 
@@ -9,6 +10,8 @@ pub struct EncoderCopy {
     ist_time_base: Rational,
     ost_time_base: Rational,
     ost_index: usize,
+    delay: i64,
+    is_sub: bool,
 }
 
 impl Encode for EncoderCopy {
@@ -21,6 +24,9 @@ impl Encode for EncoderCopy {
     }
 
     fn processing_packet(&mut self, octx: &mut context::Output, packet: &mut Packet) -> Result<()> {
+        if !delay_packet(packet, self.delay, self.is_sub) {
+            return Ok(());
+        }
         packet.rescale_ts(self.ist_time_base, self.ost_time_base);
         packet.set_position(-1);
         packet.set_stream(self.ost_index);
@@ -37,12 +43,15 @@ impl EncoderCopy {
     pub fn new_encoder<'a>(
         ist: &format::stream::Stream,
         octx: &'a mut context::Output,
+        delay: i64,
     ) -> Result<(ffmpeg::StreamMut<'a>, Encoder)> {
         let ost = add_copy_stream(ist, octx)?;
         let enc = Self {
             ist_time_base: Rational(1, 1),
             ost_time_base: Rational(1, 1),
             ost_index: ost.index(),
+            delay,
+            is_sub: ist.parameters().medium() == media::Type::Subtitle,
         };
 
         Ok((ost, Encoder::Copy(enc)))
@@ -0,0 +1,297 @@
+use super::{Encode, Encoder, delay_packet};
+use crate::ffmpeg::{
+    self, ChannelLayout, Codec, Frame, Packet, Rational, Rescale, codec, decoder, encoder, ffi,
+    filter,
+    format::{self, context},
+    media,
+};
+use crate::{Bitrate, Result};
+
+pub struct EncoderTranscode {
+    decoder: decoder::Opened,
+    encoder: encoder::Encoder,
+    filter: filter::Graph,
+    filter_time_base: Rational,
+    enc_time_base: Rational,
+    ost_time_base: Rational,
+    ost_index: usize,
+    is_video: bool,
+    delay: i64,
+}
+
+impl Encode for EncoderTranscode {
+    // The decoder receives the input time base on creation.
+    fn set_ist_time_base(&mut self, _: Rational) {}
+
+    fn set_ost_time_base(&mut self, tb: Rational) {
+        self.ost_time_base = tb;
+    }
+
+    fn processing_packet(&mut self, octx: &mut context::Output, packet: &mut Packet) -> Result<()> {
+        if !delay_packet(packet, self.delay, false) {
+            return Ok(());
+        }
+        self.decoder.send_packet(packet)?;
+        self.receive_decoded(octx)
+    }
+
+    fn finalize(&mut self, octx: &mut context::Output) -> Result<()> {
+        self.decoder.send_eof()?;
+        self.receive_decoded(octx)?;
+
+        filter_ctx(&mut self.filter, "in")?.source().flush()?;
+        self.receive_filtered(octx)?;
+
+        self.encoder.send_eof()?;
+        self.receive_encoded(octx)
+    }
+}
+
+impl EncoderTranscode {
+    pub fn new_encoder<'a>(
+        ist: &format::stream::Stream,
+        octx: &'a mut context::Output,
+        codec: Codec,
+        bitrate: Option<Bitrate>,
+        delay: i64,
+    ) -> Result<(ffmpeg::StreamMut<'a>, Encoder)> {
+        let global_header = octx.format().flags().contains(format::Flags::GLOBAL_HEADER);
+
+        let mut dec = codec::Context::from_parameters(ist.parameters())?.decoder();
+        dec.set_packet_time_base(ist.time_base());
+
+        let is_video = match ist.parameters().medium() {
+            media::Type::Audio => false,
+            media::Type::Video => true,
+            _ => return Err(err!("Unsupported stream type for transcoding")),
+        };
+
+        let (decoder, mut encoder, mut filter) = if is_video {
+            new_video(ist, dec, codec)?
+        } else {
+            new_audio(ist, dec, codec)?
+        };
+
+        if global_header {
+            encoder.set_flags(codec::Flags::GLOBAL_HEADER);
+        }
+        if let Some(b) = bitrate {
+            encoder.set_bit_rate(*b);
+        }
+        encoder.compliance(codec::Compliance::Experimental);
+        let enc_time_base = encoder.time_base();
+
+        let encoder = open_encoder(encoder, codec, is_video)?;
+        if !is_video
+            && !codec
+                .capabilities()
+                .contains(codec::Capabilities::VARIABLE_FRAME_SIZE)
+        {
+            let frame_size = unsafe { (*encoder.as_ptr()).frame_size } as u32;
+            filter_ctx(&mut filter, "out")?
+                .sink()
+                .set_frame_size(frame_size);
+        }
+        let filter_time_base = filter_ctx(&mut filter, "out")?.sink().time_base();
+
+        let mut ost = octx.add_stream(codec)?;
+        ost.set_parameters(&encoder);
+        ost.set_time_base(enc_time_base);
+        unsafe {
+            (*ost.as_mut_ptr()).sample_aspect_ratio = (*ist.as_ptr()).sample_aspect_ratio;
+            (*ost.parameters().as_mut_ptr()).codec_tag = 0;
+        }
+
+        let enc = Self {
+            decoder,
+            encoder,
+            filter,
+            filter_time_base,
+            enc_time_base,
+            ost_time_base: Rational(1, 1),
+            ost_index: ost.index(),
+            is_video,
+            delay,
+        };
+
+        Ok((ost, Encoder::Transcode(enc)))
+    }
+
+    fn receive_decoded(&mut self, octx: &mut context::Output) -> Result<()> {
+        let mut frame = unsafe { Frame::empty() };
+
+        while self.decoder.receive_frame(&mut frame).is_ok() {
+            let ts = frame.timestamp();
+            frame.set_pts(ts);
+            filter_ctx(&mut self.filter, "in")?.source().add(&frame)?;
+            self.receive_filtered(octx)?;
+        }
+
+        Ok(())
+    }
+
+    fn receive_filtered(&mut self, octx: &mut context::Output) -> Result<()> {
+        let mut frame = unsafe { Frame::empty() };
+
+        while filter_ctx(&mut self.filter, "out")?
+            .sink()
+            .frame(&mut frame)
+            .is_ok()
+        {
+            let pts = frame
+                .pts()
+                .map(|pts| pts.rescale(self.filter_time_base, self.enc_time_base));
+            frame.set_pts(pts);
+
+            if self.is_video {
+                // Let the encoder choose frame types itself.
+                unsafe {
+                    (*frame.as_mut_ptr()).pict_type = ffi::AVPictureType::AV_PICTURE_TYPE_NONE;
+                }
+            }
+
+            self.encoder.send_frame(&frame)?;
+            self.receive_encoded(octx)?;
+        }
+
+        Ok(())
+    }
+
+    fn receive_encoded(&mut self, octx: &mut context::Output) -> Result<()> {
+        let mut packet = Packet::empty();
+
+        while self.encoder.receive_packet(&mut packet).is_ok() {
+            packet.rescale_ts(self.enc_time_base, self.ost_time_base);
+            packet.set_position(-1);
+            packet.set_stream(self.ost_index);
+            packet.write_interleaved(octx)?;
+        }
+
+        Ok(())
+    }
+}
+
+fn new_audio(
+    ist: &format::stream::Stream,
+    dec: decoder::Decoder,
+    codec: Codec,
+) -> Result<(decoder::Opened, encoder::Encoder, filter::Graph)> {
+    let dec = dec.audio()?;
+    let codec = codec.audio()?;
+
+    let src_layout = match dec.channel_layout() {
+        l if l.is_empty() => ChannelLayout::default(dec.channels() as i32),
+        l => l,
+    };
+    let is_supported_layout = codec
+        .channel_layouts()
+        .is_none_or(|mut ls| ls.any(|l| l == src_layout));
+    let layout = match codec.channel_layouts() {
+        Some(ls) if !is_supported_layout => ls.best(src_layout.channels()),
+        _ => src_layout,
+    };
+    let rate = supported_or_first(codec.rates(), dec.rate() as i32);
+    let sample_fmt = supported_or_first(codec.formats(), dec.format());
+
+    let mut enc = codec::Context::new_with_codec(*codec).encoder().audio()?;
+    enc.set_rate(rate);
+    enc.set_channel_layout(layout);
+    enc.set_format(sample_fmt);
+    enc.set_time_base((1, rate));
+
+    let args = format!(
+        "time_base={}:sample_rate={}:sample_fmt={}:channel_layout=0x{:x}",
+        ist.time_base(),
+        dec.rate(),
+        dec.format().name(),
+        src_layout.bits()
+    );
+
+    let mut graph = filter::Graph::new();
+    graph.add(&find_filter("abuffer")?, "in", &args)?;
+    graph.add(&find_filter("abuffersink")?, "out", "")?;
+    {
+        let mut out = filter_ctx(&mut graph, "out")?;
+        out.set_sample_format(sample_fmt);
+        out.set_channel_layout(layout);
+        out.set_sample_rate(rate as u32);
+    }
+    graph.output("in", 0)?.input("out", 0)?.parse("anull")?;
+    graph.validate()?;
+
+    Ok((dec.0, enc.0, graph))
+}
+
+fn new_video(
+    ist: &format::stream::Stream,
+    dec: decoder::Decoder,
+    codec: Codec,
+) -> Result<(decoder::Opened, encoder::Encoder, filter::Graph)> {
+    let dec = dec.video()?;
+    let codec = codec.video()?;
+    let pix_fmt = supported_or_first(codec.formats(), dec.format());
+
+    let aspect = match dec.aspect_ratio() {
+        r if r.numerator() > 0 => r,
+        _ => Rational(1, 1),
+    };
+    let frame_rate = Some(ist.avg_frame_rate()).filter(|r| r.numerator() > 0);
+
+    let mut enc = codec::Context::new_with_codec(*codec).encoder().video()?;
+    enc.set_width(dec.width());
+    enc.set_height(dec.height());
+    enc.set_format(pix_fmt);
+    enc.set_aspect_ratio(aspect);
+    enc.set_frame_rate(frame_rate);
+    enc.set_time_base(ist.time_base());
+
+    let args = format!(
+        "video_size={}x{}:pix_fmt={}:time_base={}:pixel_aspect={}",
+        dec.width(),
+        dec.height(),
+        dec.format().name(),
+        ist.time_base(),
+        aspect
+    );
+
+    let mut graph = filter::Graph::new();
+    graph.add(&find_filter("buffer")?, "in", &args)?;
+    graph.add(&find_filter("buffersink")?, "out", "")?;
+    filter_ctx(&mut graph, "out")?.set_pixel_format(pix_fmt);
+    graph.output("in", 0)?.input("out", 0)?.parse("null")?;
+    graph.validate()?;
+
+    Ok((dec.0, enc.0, graph))
+}
+
+fn find_filter(name: &str) -> Result<filter::Filter> {
+    filter::find(name).ok_or_else(|| err!("Not found ffmpeg filter '{}'", name))
+}
+
+fn filter_ctx<'a>(graph: &'a mut filter::Graph, name: &str) -> Result<filter::Context<'a>> {
+    graph
+        .get(name)
+        .ok_or_else(|| err!("Not found filter '{}' in graph", name))
+}
+
+fn open_encoder(enc: encoder::Encoder, codec: Codec, is_video: bool) -> Result<encoder::Encoder> {
+    let enc = if is_video {
+        enc.video()?.open_as(codec)?.0.0
+    } else {
+        enc.audio()?.open_as(codec)?.0.0
+    };
+    Ok(enc)
+}
+
+fn supported_or_first<T: Copy + PartialEq>(xs: Option<impl Iterator<Item = T>>, val: T) -> T {
+    match xs {
+        Some(xs) => {
+            let xs: Vec<T> = xs.collect();
+            match xs.first() {
+                Some(first) if !xs.contains(&val) => *first,
+                _ => val,
+            }
+        }
+        None => val,
+    }
+}
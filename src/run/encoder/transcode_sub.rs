@@ -0,0 +1,132 @@
+use super::{Encode, Encoder, delay_packet};
+use crate::Result;
+use crate::ffmpeg::{
+    self, Codec, Packet, Rational, Rescale, Subtitle, codec, decoder, encoder, ffi,
+    format::{self, context},
+};
+use std::ptr;
+
+const AV_TIME_BASE_Q: Rational = Rational(1, 1_000_000);
+const MS_TIME_BASE: Rational = Rational(1, 1000);
+
+/// Reencodes text subtitles between text formats (e.g. SRT to WebVTT).
+pub struct EncoderTranscodeSub {
+    decoder: decoder::Subtitle,
+    encoder: encoder::Encoder,
+    buf: Vec<u8>,
+    ost_time_base: Rational,
+    ost_index: usize,
+    delay: i64,
+}
+
+impl Encode for EncoderTranscodeSub {
+    fn set_ist_time_base(&mut self, _: Rational) {}
+
+    fn set_ost_time_base(&mut self, tb: Rational) {
+        self.ost_time_base = tb;
+    }
+
+    fn processing_packet(&mut self, octx: &mut context::Output, packet: &mut Packet) -> Result<()> {
+        if !delay_packet(packet, self.delay, true) {
+            return Ok(());
+        }
+        let mut sub = Subtitle::new();
+        if !self.decoder.decode(packet, &mut sub)? {
+            return Ok(());
+        }
+
+        let result = self.encode(octx, &mut sub);
+        unsafe { ffi::avsubtitle_free(sub.as_mut_ptr()) };
+        result
+    }
+
+    fn finalize(&mut self, _: &mut context::Output) -> Result<()> {
+        Ok(())
+    }
+}
+
+impl EncoderTranscodeSub {
+    pub fn new_encoder<'a>(
+        ist: &format::stream::Stream,
+        octx: &'a mut context::Output,
+        codec: Codec,
+        delay: i64,
+    ) -> Result<(ffmpeg::StreamMut<'a>, Encoder)> {
+        let mut dec = codec::Context::from_parameters(ist.parameters())?.decoder();
+        dec.set_packet_time_base(ist.time_base());
+        let decoder = dec.subtitle()?;
+
+        let mut enc = codec::Context::new_with_codec(codec).encoder().subtitle()?;
+        enc.set_time_base(AV_TIME_BASE_Q);
+        enc.compliance(codec::Compliance::Experimental);
+
+        // Text encoders need the ASS header the decoder builds.
+        unsafe {
+            let d = decoder.as_ptr();
+            let e = enc.as_mut_ptr();
+            if !(*d).subtitle_header.is_null() {
+                let size = (*d).subtitle_header_size as usize;
+                let header = ffi::av_mallocz(size + 1) as *mut u8;
+                ptr::copy_nonoverlapping((*d).subtitle_header, header, size);
+                (*e).subtitle_header = header;
+                (*e).subtitle_header_size = size as i32;
+            }
+        }
+
+        let encoder = enc.open_as(codec)?.0.0;
+
+        let mut ost = octx.add_stream(codec)?;
+        ost.set_parameters(&encoder);
+        unsafe {
+            (*ost.parameters().as_mut_ptr()).codec_tag = 0;
+        }
+
+        let enc = Self {
+            decoder,
+            encoder,
+            buf: vec![0u8; 1 << 20],
+            ost_time_base: Rational(1, 1),
+            ost_index: ost.index(),
+            delay,
+        };
+
+        Ok((ost, Encoder::TranscodeSub(enc)))
+    }
+
+    fn encode(&mut self, octx: &mut context::Output, sub: &mut Subtitle) -> Result<()> {
+        let pts = some_or!(sub.pts(), return Ok(()));
+        let start = sub.start() as i64;
+        let duration = sub.end().saturating_sub(sub.start());
+
+        // Encoders expect the display start in pts.
+        let pts = pts + start.rescale(MS_TIME_BASE, AV_TIME_BASE_Q);
+        sub.set_pts(Some(pts));
+        sub.set_start(0);
+        sub.set_end(duration);
+
+        let size = unsafe {
+            ffi::avcodec_encode_subtitle(
+                self.encoder.as_mut_ptr(),
+                self.buf.as_mut_ptr(),
+                self.buf.len() as i32,
+                sub.as_ptr(),
+            )
+        };
+        if size < 0 {
+            return Err(ffmpeg::Error::from(size).into());
+        } else if size == 0 {
+            return Ok(());
+        }
+
+        let tb = self.ost_time_base;
+        let mut packet = Packet::copy(&self.buf[..size as usize]);
+        let pts = pts.rescale(AV_TIME_BASE_Q, tb);
+        packet.set_pts(Some(pts));
+        packet.set_dts(Some(pts));
+        packet.set_duration((duration as i64).rescale(MS_TIME_BASE, tb));
+        packet.set_stream(self.ost_index);
+        packet.write_interleaved(octx)?;
+
+        Ok(())
+    }
+}
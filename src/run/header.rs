@@ -3,8 +3,8 @@ mod input_stream;
 use super::{Encode, Encoder};
 use crate::ffmpeg::{self, Dictionary, format::context};
 use crate::{
-    DispositionType, MediaInfo, Result, Stream, StreamType, StreamsOrder, StreamsOrderItem,
-    VERSION, immut, markers::*,
+    Container, DispositionType, Keyword, MediaInfo, Result, Stream, StreamType, StreamsOrder,
+    StreamsOrderItem, VERSION, immut, markers::*,
 };
 use enum_map::EnumMap;
 
@@ -25,8 +25,8 @@ pub(super) fn write_header(
         let ist = input_stream::new(mi, &mut icontexts, ord)?;
         let st = &immut!(@try, mi, MIStreams, &ord.key)?[ord.key_i_stream];
 
-        let (mut ost, enc) = Encoder::new(&ist, octx)?;
-        ost.set_metadata(new_ost_metadata(st, &ist));
+        let (mut ost, enc) = Encoder::new(mi, ord, st, &ist, octx)?;
+        ost.set_metadata(new_ost_metadata(mi.cfg.container, st, &ist));
         set_ost_dispositions(mi, &auto, &mut counts, ord, st, &mut ost);
 
         push_idx(&mut idx_map[ord.src_num], ord.i_stream, ost.index());
@@ -37,6 +37,9 @@ pub(super) fn write_header(
     meta.set("application", VERSION);
     octx.set_metadata(meta);
 
+    // MP4 writes a chapter track only for chapters known before the header.
+    super::copy_chapters(mi, order, &icontexts, octx);
+
     octx.write_header()?;
 
     for (i, ord) in order.0.iter().enumerate() {
@@ -55,9 +58,17 @@ pub(super) fn write_header(
     Ok((icontexts, encoders, idx_map))
 }
 
-fn new_ost_metadata<'a>(stream: &Stream, ist: &'a ffmpeg::Stream<'a>) -> Dictionary<'a> {
+fn new_ost_metadata<'a>(
+    container: Container,
+    stream: &Stream,
+    ist: &'a ffmpeg::Stream<'a>,
+) -> Dictionary<'a> {
     let mut meta = ist.metadata().to_owned();
-    meta.set("language", stream.lang.as_str());
+    let lang = match container {
+        Container::Mp4 => stream.lang.as_iso639_2t(),
+        _ => stream.lang.as_str(),
+    };
+    meta.set("language", lang);
     if let Some(s) = stream.name.as_ref() {
         meta.set("title", &*s);
     }
@@ -75,17 +86,50 @@ fn set_ost_dispositions(
     stream: &Stream,
     ost: &mut ffmpeg::StreamMut,
 ) {
-    let target_paths = some_or!(mi.immut(MITargetPaths, &ord.key), return);
-
     let st = unsafe { &mut *ost.as_mut_ptr() };
 
+    for (ty, v) in new_dispositions(mi, auto, counts, ord, stream) {
+        if v {
+            st.disposition |= ty.bits();
+        }
+    }
+}
+
+/// Returns dispositions of the output stream, counting enabled ones.
+pub(super) fn new_dispositions(
+    mi: &MediaInfo,
+    auto: &EnumMap<DispositionType, bool>,
+    counts: &mut EnumMap<StreamType, EnumMap<DispositionType, usize>>,
+    ord: &StreamsOrderItem,
+    stream: &Stream,
+) -> EnumMap<DispositionType, bool> {
+    if let Some(ds) = ord.dispositions {
+        ds.iter()
+            .filter(|(_, v)| **v)
+            .for_each(|(ty, _)| counts[stream.ty][ty] += 1);
+        return ds;
+    }
+
+    let mut dispositions = EnumMap::default();
+    let target_paths = some_or!(mi.immut(MITargetPaths, &ord.key), return dispositions);
+
     for ty in DispositionType::iter() {
         let (i_key, values) = mi.cfg.stream_val_dispositions(ty, target_paths, stream);
 
         let v = values.get(&i_key, &stream.lang).unwrap_or_else(|| {
-            if auto[ty] {
+            if auto[ty] && is_auto_candidate(ty, stream) {
                 let cnt = counts[stream.ty][ty];
-                cnt < values.max(ty)
+                let max = values.max(ty);
+                // A likely forced sub is flagged even if no forceds are allowed by default,
+                // but never over a user max.
+                match ty {
+                    DispositionType::Forced
+                        if stream.is_forced_hint() && values.max_in_auto.is_none() =>
+                    {
+                        cnt < max.max(1)
+                    }
+                    _ => cnt < max,
+                }
             } else {
                 false
             }
@@ -93,8 +137,23 @@ fn set_ost_dispositions(
 
         if v {
             counts[stream.ty][ty] += 1;
-            st.disposition |= ty.bits();
+            dispositions[ty] = true;
+        }
+    }
+
+    dispositions
+}
+
+// Defaults and forceds are set by the order; others are carried over from sources
+// or detected by keywords.
+fn is_auto_candidate(ty: DispositionType, stream: &Stream) -> bool {
+    match ty {
+        DispositionType::Default | DispositionType::Forced => true,
+        DispositionType::HearingImpaired => {
+            stream.dispositions[ty] || stream.keywords[Keyword::Sdh]
         }
+        DispositionType::Comment => stream.dispositions[ty] || stream.keywords[Keyword::Commentary],
+        _ => stream.dispositions[ty],
     }
 }
 
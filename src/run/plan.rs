@@ -0,0 +1,157 @@
+use super::{encoder, header};
+use crate::{
+    ArcPathBuf, MediaInfo, MuxPlanGroup, MuxPlanTrack, Result, StreamsOrder, StreamsOrderItem,
+    TrackEncoding, Value, markers::*,
+};
+use enum_map::EnumMap;
+use std::{
+    collections::{HashMap, hash_map::Entry},
+    fs,
+    path::{Path, PathBuf},
+};
+
+impl MediaInfo<'_> {
+    /// Returns a resolved plan of muxing all files from [`MediaInfo::cache`] to `dest`.
+    ///
+    /// Processed tracks (retimed, converted, synced, split) are copied from the temp dir
+    /// next to the `plan` JSON, so the plan can be replayed after the run.
+    pub(crate) fn try_plan_group(&mut self, plan: &Path, dest: &Path) -> Result<MuxPlanGroup> {
+        let order = self.try_take_cmn(MICmnStreamsOrder)?;
+        let tracks = self.try_plan_tracks(plan, dest, &order);
+        self.set_cmn(MICmnStreamsOrder, order);
+
+        Ok(MuxPlanGroup {
+            output: dest.to_path_buf(),
+            tracks: tracks?,
+        })
+    }
+
+    fn try_plan_tracks(
+        &self,
+        plan: &Path,
+        dest: &Path,
+        order: &StreamsOrder,
+    ) -> Result<Vec<MuxPlanTrack>> {
+        let auto = self.cfg.auto_flags.map_dispositions();
+        let mut counts = EnumMap::default();
+        let mut kept: HashMap<PathBuf, PathBuf> = HashMap::new();
+        let mut tracks: Vec<MuxPlanTrack> = Vec::with_capacity(order.len());
+
+        for ord in order.iter() {
+            let stream = some_or!(
+                self.immut(MIStreams, &ord.key)
+                    .and_then(|xs| xs.get(ord.key_i_stream)),
+                continue
+            );
+            let ds = header::new_dispositions(self, &auto, &mut counts, ord, stream);
+            let enc = encoder::track_encoding(self, ord, stream);
+
+            let (src, i_stream) = match &ord.src {
+                Some(src) => (
+                    try_keep_processed(plan, dest, &mut kept, src)?,
+                    ord.i_stream,
+                ),
+                None => (ord.key.to_path_buf(), ord.key_i_stream),
+            };
+
+            tracks.push(MuxPlanTrack {
+                src,
+                i_stream,
+                reencode: ord.reencode,
+                name: stream.name.as_ref().map(|n| n.deref().clone()),
+                lang: stream.lang.deref().clone(),
+                dispositions: ds,
+                delay: enc.delay,
+                codec: enc.codec,
+                bitrate: enc.bitrate,
+            });
+        }
+
+        Ok(tracks)
+    }
+
+    /// Tries init [`MediaInfo`] by the plan group, taking all tracks as is.
+    pub(crate) fn try_init_plan_group(&mut self, g: &MuxPlanGroup) -> Result<()> {
+        if let Some(stem) = g.output.file_stem() {
+            self.set_cmn(MICmnStem, stem.to_owned());
+        }
+
+        let mut sources: Vec<PathBuf> = Vec::new();
+        g.tracks.iter().for_each(|t| {
+            if !sources.contains(&t.src) {
+                sources.push(t.src.clone());
+            }
+        });
+        self.try_insert_many(sources)?;
+
+        let mut numbers: HashMap<&Path, usize> = HashMap::new();
+        let mut items: Vec<StreamsOrderItem> = Vec::with_capacity(g.tracks.len());
+
+        for t in g.tracks.iter() {
+            let key = ArcPathBuf::from(t.src.as_path());
+            let stream = self
+                .try_mut(MIStreams, &key)?
+                .get_mut(t.i_stream)
+                .ok_or_else(|| err!("Not found stream {} of '{}'", t.i_stream, t.src.display()))?;
+
+            stream.name = t.name.clone().map(Value::User);
+            stream.lang = Value::User(t.lang.clone());
+            let ty = stream.ty;
+
+            let len = numbers.len();
+            let (src_num, is_first_entry) = match numbers.entry(t.src.as_path()) {
+                Entry::Occupied(e) => (*e.get(), false),
+                Entry::Vacant(e) => (*e.insert(len), true),
+            };
+
+            items.push(StreamsOrderItem {
+                ty,
+                key,
+                key_i_stream: t.i_stream,
+                src: None,
+                i_stream: t.i_stream,
+                src_num,
+                is_first_entry,
+                reencode: t.reencode,
+                dispositions: Some(t.dispositions),
+                encoding: Some(TrackEncoding {
+                    delay: t.delay,
+                    codec: t.codec.clone(),
+                    bitrate: t.bitrate,
+                }),
+            });
+        }
+
+        if items.is_empty() {
+            return Err(err!("Not save any track"));
+        }
+
+        self.set_cmn(MICmnStreamsOrder, StreamsOrder(items));
+        Ok(())
+    }
+}
+
+/// Copies a processed track to the `<plan stem>.tracks` dir once per source.
+fn try_keep_processed(
+    plan: &Path,
+    dest: &Path,
+    kept: &mut HashMap<PathBuf, PathBuf>,
+    src: &Path,
+) -> Result<PathBuf> {
+    if let Some(p) = kept.get(src) {
+        return Ok(p.clone());
+    }
+
+    let dir = plan.with_extension("tracks");
+    fs::create_dir_all(&dir)?;
+
+    let stem = dest.file_stem().unwrap_or_default().to_string_lossy();
+    let ext = src.extension().unwrap_or_default().to_string_lossy();
+    let path = dir.join(format!("{}-{}.{}", stem, kept.len(), ext));
+
+    fs::copy(src, &path)
+        .map_err(|e| err!("Fail keep processed track '{}': {}", src.display(), e))?;
+    kept.insert(src.to_path_buf(), path.clone());
+
+    Ok(path)
+}
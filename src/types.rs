@@ -65,19 +65,24 @@ macro_rules! to_json_args {
 }
 
 pub(crate) mod arc_path_buf;
+pub(crate) mod audio_sync;
 pub(crate) mod auto_flags;
+pub(crate) mod bitrate;
 pub(crate) mod chapters;
 pub(crate) mod char_encoding;
 pub(crate) mod cli_arg;
 pub(crate) mod codec_id;
 pub mod config;
 pub(crate) mod container;
+pub(crate) mod delay;
 pub(crate) mod dispositions;
 pub(crate) mod duration;
 pub(crate) mod extension;
 pub(crate) mod globset_pattern;
 pub(crate) mod helpers;
 pub(crate) mod input;
+pub(crate) mod keyword;
+pub(crate) mod keywords;
 pub(crate) mod lang;
 pub(crate) mod log_level;
 pub(crate) mod media_info;
@@ -85,9 +90,11 @@ pub(crate) mod media_number;
 pub(crate) mod metadata;
 pub(crate) mod mux_error;
 pub(crate) mod mux_logger;
+pub(crate) mod mux_plan;
 pub(crate) mod output;
 pub(crate) mod range;
 pub(crate) mod retiming;
 pub(crate) mod stream;
+pub(crate) mod subtitles;
 pub(crate) mod target;
 pub(crate) mod value;
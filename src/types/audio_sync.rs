@@ -0,0 +1,210 @@
+mod fft;
+
+use crate::{
+    Delay, Result, StreamType,
+    ffmpeg::{self, ChannelLayout, frame, software::resampling},
+    types::helpers,
+};
+use fft::{Complex, fft};
+use std::{path::Path, time::Duration};
+
+/// A detected sync offset of an audio track against a base audio track.
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+pub struct AudioSync {
+    /// A delay to apply to the track.
+    pub delay: Delay,
+    /// How much the best lag outweighs the next best one, from 0 to 1.
+    pub confidence: f64,
+}
+
+impl AudioSync {
+    /// Sample rate of analyzed signals. Raw waveforms are correlated, and 2 kHz of
+    /// bandwidth keeps most of speech energy while making the FFT cheap.
+    pub const SAMPLE_RATE: u32 = 4000;
+
+    /// A length of analyzed signals from the start of tracks.
+    pub const WINDOW: Duration = Duration::from_secs(120);
+
+    /// A max detected offset in either direction.
+    pub const MAX_LAG: Duration = Duration::from_secs(20);
+
+    /// A confidence below which a detected offset isn't applied.
+    pub const MIN_CONFIDENCE: f64 = 0.2;
+
+    /// Lags closer than this to the best one are the same peak.
+    const PEAK_WIDTH: Duration = Duration::from_millis(50);
+
+    /// Finds the offset of `other` against `base` by FFT cross-correlation.
+    ///
+    /// Both signals are mono samples at `rate`. Returns [`None`] if `rate` is zero or
+    /// any signal is empty or silent.
+    /// ```
+    /// use mux_media::{AudioSync, Delay};
+    ///
+    /// let mut x = 1u32;
+    /// let base: Vec<f32> = (0..4000)
+    ///     .map(|_| {
+    ///         x = x.wrapping_mul(1103515245).wrapping_add(12345);
+    ///         (x >> 16) as f32
+    ///     })
+    ///     .collect();
+    /// let other = base[400..].to_vec();
+    ///
+    /// let sync = AudioSync::new(&base, &other, 4000).unwrap();
+    /// assert_eq!(Delay(100), sync.delay);
+    /// ```
+    pub fn new(base: &[f32], other: &[f32], rate: u32) -> Option<AudioSync> {
+        if rate == 0 {
+            return None;
+        }
+
+        let to_len = |d: Duration| (d.as_secs_f64() * rate as f64) as usize;
+        let peak = cross_correlate(base, other, to_len(Self::MAX_LAG), to_len(Self::PEAK_WIDTH))?;
+
+        Some(AudioSync {
+            delay: Delay((peak.lag as f64 * 1000.0 / rate as f64).round() as i64),
+            confidence: peak.confidence,
+        })
+    }
+}
+
+/// The best lag of a cross-correlation.
+pub(crate) struct Peak {
+    /// Positive if the other signal is ahead of the base one.
+    pub lag: isize,
+    pub value: f64,
+    /// How much the peak outweighs the next one, from 0 to 1.
+    pub confidence: f64,
+}
+
+/// Finds the best lag of `other` against `base` within `max_lag` samples in either
+/// direction by FFT cross-correlation of the mean-removed signals.
+///
+/// Lags closer than `peak_width` to the best one are the same peak.
+/// Returns [`None`] if any signal is empty or flat.
+pub(crate) fn cross_correlate(
+    base: &[f32],
+    other: &[f32],
+    max_lag: usize,
+    peak_width: usize,
+) -> Option<Peak> {
+    if base.is_empty() || other.is_empty() {
+        return None;
+    }
+
+    let n = (base.len() + other.len()).next_power_of_two();
+    let mut a = to_complex(base, n);
+    let mut b = to_complex(other, n);
+    fft(&mut a, false);
+    fft(&mut b, false);
+
+    let mut xs: Vec<Complex> = a.iter().zip(&b).map(|(a, b)| *a * b.conj()).collect();
+    fft(&mut xs, true);
+
+    // xs[k] correlates base[i + k] with other[i]; negative lags wrap to the end.
+    let max_lag = max_lag as isize;
+    let lags = -max_lag.min(other.len() as isize - 1)..=max_lag.min(base.len() as isize - 1);
+    let corr = |lag: isize| xs[lag.rem_euclid(n as isize) as usize].re;
+
+    let (lag, value) = lags
+        .clone()
+        .map(|lag| (lag, corr(lag)))
+        .max_by(|a, b| a.1.total_cmp(&b.1))?;
+
+    if value <= 0.0 {
+        return None;
+    }
+
+    let second = lags
+        .filter(|l| (l - lag).abs() > peak_width as isize)
+        .map(corr)
+        .fold(0f64, f64::max);
+
+    Some(Peak {
+        lag,
+        value,
+        confidence: (1.0 - second / value).clamp(0.0, 1.0),
+    })
+}
+
+fn to_complex(xs: &[f32], n: usize) -> Vec<Complex> {
+    let mean = xs.iter().map(|x| *x as f64).sum::<f64>() / xs.len() as f64;
+    let mut ys: Vec<Complex> = xs
+        .iter()
+        .map(|x| Complex::new(*x as f64 - mean, 0.0))
+        .collect();
+    ys.resize(n, Complex::default());
+    ys
+}
+
+/// Tries decode the audio stream from the start up to `window` as mono samples at
+/// [`AudioSync::SAMPLE_RATE`].
+///
+/// A late start of the stream is filled with silence.
+pub(crate) fn try_decode_mono(src: &Path, i_stream: usize, window: Duration) -> Result<Vec<f32>> {
+    let mut ictx = ffmpeg::format::input(src)?;
+    let stream = ictx.stream(i_stream).ok_or(ffmpeg::Error::StreamNotFound)?;
+    let (i, tb) = helpers::ffmpeg_stream_i_tb(&stream);
+    let mut opened = helpers::try_ffmpeg_opened(StreamType::Audio, &stream)?;
+
+    let max_len = (window.as_secs_f64() * AudioSync::SAMPLE_RATE as f64) as usize;
+    let mut samples: Vec<f32> = Vec::with_capacity(max_len);
+    let mut resampler: Option<resampling::Context> = None;
+
+    for (s, packet) in ictx.packets() {
+        if s.index() != i {
+            continue;
+        }
+
+        opened.send_packet(&packet)?;
+
+        loop {
+            let mut frame = frame::Audio::empty();
+            match opened.receive_frame(&mut frame) {
+                Ok(_) => {}
+                Err(ffmpeg::Error::Other { errno: 11 }) => break,
+                Err(ffmpeg::Error::Eof) => break,
+                Err(e) => return Err(err!("Ffmpeg decoder error: {}", e)),
+            }
+
+            if resampler.is_none() {
+                let start = frame.pts().map_or(0.0, |pts| pts as f64 * tb).max(0.0);
+                let silence = (start * AudioSync::SAMPLE_RATE as f64) as usize;
+                samples.resize(silence.min(max_len), 0.0);
+                resampler = Some(new_resampler(&frame)?);
+            }
+
+            let mut mono = frame::Audio::empty();
+            if let Some(rs) = resampler.as_mut() {
+                rs.run(&frame, &mut mono)?;
+            }
+            if mono.samples() > 0 {
+                samples.extend_from_slice(mono.plane::<f32>(0));
+            }
+
+            if samples.len() >= max_len {
+                samples.truncate(max_len);
+                return Ok(samples);
+            }
+        }
+    }
+
+    Ok(samples)
+}
+
+fn new_resampler(frame: &frame::Audio) -> Result<resampling::Context> {
+    let layout = match frame.channel_layout() {
+        l if l.is_empty() => ChannelLayout::default(frame.channels() as i32),
+        l => l,
+    };
+
+    resampling::Context::get(
+        frame.format(),
+        layout,
+        frame.rate(),
+        ffmpeg::format::Sample::F32(ffmpeg::format::sample::Type::Packed),
+        ChannelLayout::MONO,
+        AudioSync::SAMPLE_RATE,
+    )
+    .map_err(Into::into)
+}
@@ -0,0 +1,97 @@
+use std::{
+    f64::consts::PI,
+    ops::{Add, Mul, Sub},
+};
+
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+pub(super) struct Complex {
+    pub re: f64,
+    pub im: f64,
+}
+
+impl Complex {
+    pub const fn new(re: f64, im: f64) -> Complex {
+        Complex { re, im }
+    }
+
+    pub fn conj(self) -> Complex {
+        Complex::new(self.re, -self.im)
+    }
+}
+
+impl Add for Complex {
+    type Output = Complex;
+
+    fn add(self, o: Complex) -> Complex {
+        Complex::new(self.re + o.re, self.im + o.im)
+    }
+}
+
+impl Sub for Complex {
+    type Output = Complex;
+
+    fn sub(self, o: Complex) -> Complex {
+        Complex::new(self.re - o.re, self.im - o.im)
+    }
+}
+
+impl Mul for Complex {
+    type Output = Complex;
+
+    fn mul(self, o: Complex) -> Complex {
+        Complex::new(
+            self.re * o.re - self.im * o.im,
+            self.re * o.im + self.im * o.re,
+        )
+    }
+}
+
+/// In-place iterative radix-2 FFT. The length must be a power of two.
+///
+/// The inverse transform is scaled by `1 / len`.
+pub(super) fn fft(xs: &mut [Complex], is_inverse: bool) {
+    let n = xs.len();
+    debug_assert!(n.is_power_of_two());
+
+    // bit-reversal permutation
+    let mut j = 0;
+    for i in 1..n {
+        let mut bit = n >> 1;
+        while j & bit != 0 {
+            j ^= bit;
+            bit >>= 1;
+        }
+        j |= bit;
+        if i < j {
+            xs.swap(i, j);
+        }
+    }
+
+    let sign = if is_inverse { 1.0 } else { -1.0 };
+    let mut len = 2;
+
+    while len <= n {
+        let angle = sign * 2.0 * PI / len as f64;
+        let w_len = Complex::new(angle.cos(), angle.sin());
+
+        for start in (0..n).step_by(len) {
+            let mut w = Complex::new(1.0, 0.0);
+            for k in 0..len / 2 {
+                let u = xs[start + k];
+                let v = xs[start + k + len / 2] * w;
+                xs[start + k] = u + v;
+                xs[start + k + len / 2] = u - v;
+                w = w * w_len;
+            }
+        }
+        len <<= 1;
+    }
+
+    if is_inverse {
+        let scale = 1.0 / n as f64;
+        xs.iter_mut().for_each(|x| {
+            x.re *= scale;
+            x.im *= scale;
+        });
+    }
+}
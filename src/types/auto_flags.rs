@@ -8,14 +8,20 @@ pub struct AutoFlags {
     pub pro: bool,
     pub defaults: Value<bool>,
     pub forceds: Value<bool>,
+    pub dispositions: Value<bool>,
     pub names: Value<bool>,
     pub langs: Value<bool>,
     pub encs: Value<bool>,
+    pub delays: Value<bool>,
 }
 
 impl AutoFlags {
     pub(crate) fn map_dispositions(&self) -> EnumMap<DispositionType, bool> {
-        enum_map!(DispositionType::Default => *self.defaults, DispositionType::Forced => *self.forceds )
+        enum_map!(
+            DispositionType::Default => *self.defaults,
+            DispositionType::Forced => *self.forceds,
+            _ => *self.dispositions,
+        )
     }
 }
 
@@ -25,9 +31,11 @@ impl Default for AutoFlags {
             pro: false,
             defaults: Value::Auto(true),
             forceds: Value::Auto(true),
+            dispositions: Value::Auto(true),
             names: Value::Auto(true),
             langs: Value::Auto(true),
             encs: Value::Auto(true),
+            delays: Value::Auto(true),
         }
     }
 }
@@ -36,9 +44,11 @@ impl IsDefault for AutoFlags {
         matches!(self.pro, false)
             && matches!(self.defaults, Value::Auto(true))
             && matches!(self.forceds, Value::Auto(true))
+            && matches!(self.dispositions, Value::Auto(true))
             && matches!(self.names, Value::Auto(true))
             && matches!(self.langs, Value::Auto(true))
             && matches!(self.encs, Value::Auto(true))
+            && matches!(self.delays, Value::Auto(true))
     }
 }
 
@@ -64,9 +74,11 @@ impl ToJsonArgs for AutoFlags {
             args;
             self.defaults, AutoDefaults, NoAutoDefaults,
             self.forceds, AutoForceds, NoAutoForceds,
+            self.dispositions, AutoDispositions, NoAutoDispositions,
             self.names, AutoNames, NoAutoNames,
             self.langs, AutoLangs, NoAutoLangs,
-            self.encs, AutoEncs, NoAutoEncs
+            self.encs, AutoEncs, NoAutoEncs,
+            self.delays, AutoDelays, NoAutoDelays
         );
     }
 }
@@ -0,0 +1,54 @@
+use crate::{IsDefault, MuxError, Result};
+use std::{fmt, str::FromStr};
+
+/// A bit rate in bits per second.
+///
+/// Parses a plain number or a number with a `k` or `M` suffix.
+/// ```
+/// use mux_media::Bitrate;
+///
+/// assert_eq!(Bitrate(192_000), "192k".parse().unwrap());
+/// assert_eq!(Bitrate(1_500_000), "1.5M".parse().unwrap());
+/// assert_eq!(Bitrate(640), "640".parse().unwrap());
+/// ```
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+pub struct Bitrate(pub usize);
+
+deref_singleton_tuple_struct!(Bitrate, usize);
+
+impl IsDefault for Bitrate {
+    fn is_default(&self) -> bool {
+        self.0 == 0
+    }
+}
+
+impl FromStr for Bitrate {
+    type Err = MuxError;
+
+    fn from_str(s: &str) -> Result<Bitrate> {
+        let s = s.trim();
+        let (num, mul) = match s.char_indices().last() {
+            Some((i, 'k' | 'K')) => (&s[..i], 1_000f64),
+            Some((i, 'm' | 'M')) => (&s[..i], 1_000_000f64),
+            _ => (s, 1f64),
+        };
+
+        let num = num.trim().parse::<f64>()?;
+        if !num.is_finite() || num <= 0.0 {
+            return Err(err!("Bitrate must be a positive number"));
+        }
+
+        Ok(Bitrate((num * mul).round() as usize))
+    }
+}
+
+impl fmt::Display for Bitrate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.0 {
+            0 => write!(f, "0"),
+            x if x % 1_000_000 == 0 => write!(f, "{}M", x / 1_000_000),
+            x if x % 1_000 == 0 => write!(f, "{}k", x / 1_000),
+            x => write!(f, "{}", x),
+        }
+    }
+}
@@ -40,6 +40,13 @@ enum_cli_arg! {
     Load => "load",
     SaveConfig => "save-config",
     Reencode => "reencode",
+    ReencodeUnsupported => "reencode-unsupported",
+    DryRun => "dry-run",
+    SyncAudio => "sync-audio",
+    SyncSubs => "sync-subs",
+    PlanOut => "plan-out",
+    PlanIn => "plan-in",
+    Keywords => "keywords",
     Pro => "pro",
     HelpAutoDefaults => "auto-defaults / --no-auto-defaults",
     AutoDefaults => "auto-defaults",
@@ -56,6 +63,12 @@ enum_cli_arg! {
     HelpAutoEncs => "auto-encs / --no-auto-encs",
     AutoEncs => "auto-encs",
     NoAutoEncs => "no-auto-encs",
+    HelpAutoDelays => "auto-delays / --no-auto-delays",
+    AutoDelays => "auto-delays",
+    NoAutoDelays => "no-auto-delays",
+    HelpAutoDispositions => "auto-dispositions / --no-auto-dispositions",
+    AutoDispositions => "auto-dispositions",
+    NoAutoDispositions => "no-auto-dispositions",
     Target => "target",
     ListTargets => "list-targets",
     Streams => "streams",
@@ -76,10 +89,33 @@ enum_cli_arg! {
     MaxDefaults => "max-defaults",
     Forceds => "forceds",
     MaxForceds => "max-forceds",
+    HearingImpaireds => "hearing-impaireds",
+    MaxHearingImpaireds => "max-hearing-impaireds",
+    Comments => "comments",
+    MaxComments => "max-comments",
+    Originals => "originals",
+    MaxOriginals => "max-originals",
+    VisualImpaireds => "visual-impaireds",
+    MaxVisualImpaireds => "max-visual-impaireds",
+    Dubs => "dubs",
+    MaxDubs => "max-dubs",
     Names => "names",
     Langs => "langs",
+    Codecs => "codecs",
+    Bitrates => "bitrates",
+    Delays => "delays",
+    SubFormat => "sub-format",
+    SubStyle => "sub-style",
+    SubStretch => "sub-stretch",
+    SplitForcedPgs => "split-forced-pgs",
     Parts => "parts",
+    Cut => "cut",
+    Edition => "edition",
+    SegmentsDir => "segments-dir",
+    SegmentsCache => "segments-cache",
     NoLinked => "no-linked",
+    SmartCut => "smart-cut",
+    ExactAudio => "exact-audio",
     ListContainers => "list-containers",
     ListLangs => "list-langs",
     Version => "version",
@@ -1,7 +1,11 @@
-use crate::ffmpeg::codec::id::Id;
+use crate::{
+    Result,
+    ffmpeg::{self, codec::id::Id, ffi},
+};
+use std::ffi::CString;
 
 /// A wrapper around [`ffmpeg::codec::id::Id`](Id).
-#[derive(Copy, Clone, Debug)]
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
 pub struct CodecId(pub Id);
 
 deref_singleton_tuple_struct!(CodecId, Id);
@@ -13,6 +17,44 @@ impl Default for CodecId {
 }
 
 impl CodecId {
+    /// Returns a codec by its name, as [`Id::name`] returns.
+    ///
+    /// Unlike decoder and encoder names, codec names are stable across ffmpeg builds.
+    /// ```
+    /// use mux_media::CodecId;
+    /// use ffmpeg_next::codec::Id;
+    ///
+    /// assert_eq!(Some(CodecId(Id::WEBVTT)), CodecId::from_name("webvtt"));
+    /// assert_eq!(None, CodecId::from_name("libopus"));
+    /// ```
+    pub fn from_name(name: &str) -> Option<CodecId> {
+        let name = CString::new(name).ok()?;
+        let desc = unsafe { ffi::avcodec_descriptor_get_by_name(name.as_ptr()) };
+        if desc.is_null() {
+            return None;
+        }
+        Some(CodecId(unsafe { (*desc).id }.into()))
+    }
+
+    /// Returns a codec by an encoder name (e.g. "libopus") or a codec name (e.g. "h264").
+    /// ```
+    /// use mux_media::CodecId;
+    /// use ffmpeg_next::codec::Id;
+    ///
+    /// assert_eq!(CodecId(Id::FLAC), CodecId::try_from_encoder_name("flac").unwrap());
+    /// assert!(CodecId::try_from_encoder_name("not-a-codec").is_err());
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if there's no such encoder or no encoder of the codec.
+    pub fn try_from_encoder_name(name: &str) -> Result<CodecId> {
+        ffmpeg::encoder::find_by_name(name)
+            .map(|c| CodecId(c.id()))
+            .or_else(|| Self::from_name(name).filter(|id| ffmpeg::encoder::find(id.0).is_some()))
+            .ok_or_else(|| err!("Not found encoder '{}'", name))
+    }
+
     pub(crate) fn is_attach(self) -> bool {
         match self.0 {
             Id::PNG => true,
@@ -28,4 +70,22 @@ impl CodecId {
             _ => false,
         }
     }
+
+    pub(crate) fn is_text_sub(self) -> bool {
+        match self.0 {
+            Id::ASS | Id::SSA => true,
+            Id::SRT | Id::SUBRIP => true,
+            Id::WEBVTT | Id::MOV_TEXT | Id::TEXT => true,
+            Id::MICRODVD | Id::MPL2 | Id::REALTEXT | Id::SAMI => true,
+            Id::SUBVIEWER | Id::SUBVIEWER1 => true,
+            _ => false,
+        }
+    }
+
+    pub(crate) fn is_bitmap_sub(self) -> bool {
+        match self.0 {
+            Id::HDMV_PGS_SUBTITLE | Id::DVD_SUBTITLE => true,
+            _ => false,
+        }
+    }
 }
@@ -5,10 +5,13 @@ mod to_json_args;
 #[allow(unused_imports)]
 use crate::TryFinalizeInit;
 use crate::{
-    AutoFlags, Chapters, Container, DefaultDispositions, ForcedDispositions, Input, IsDefault,
-    LangCode, LangMetadata, LogLevel, NameMetadata, Output, RetimingOptions, Streams, Target,
+    AutoFlags, BitrateMetadata, Chapters, CodecMetadata, CommentDispositions, Container,
+    DefaultDispositions, DelayMetadata, DubDispositions, ForcedDispositions,
+    HearingImpairedDispositions, Input, IsDefault, Keywords, LangCode, LangMetadata, LogLevel,
+    NameMetadata, OriginalDispositions, Output, RetimingOptions, Streams, SubFormat, Target,
+    VisualImpairedDispositions,
 };
-use std::collections::HashMap;
+use std::{collections::HashMap, path::PathBuf};
 
 /// A configuration.
 ///
@@ -27,13 +30,29 @@ pub struct Config {
     pub exit_on_err: bool,
     pub save_config: bool,
     pub reencode: bool,
+    pub reencode_unsupported: bool,
+    pub dry_run: bool,
+    pub sync_audio: bool,
+    pub sync_subs: bool,
+    pub plan_in: Option<PathBuf>,
+    pub plan_out: Option<PathBuf>,
+    pub keywords: Keywords,
     pub auto_flags: AutoFlags,
     pub streams: Streams,
     pub chapters: Chapters,
     pub defaults: DefaultDispositions,
     pub forceds: ForcedDispositions,
+    pub hearing_impaireds: HearingImpairedDispositions,
+    pub comments: CommentDispositions,
+    pub originals: OriginalDispositions,
+    pub visual_impaireds: VisualImpairedDispositions,
+    pub dubs: DubDispositions,
     pub names: NameMetadata,
     pub langs: LangMetadata,
+    pub codecs: CodecMetadata,
+    pub bitrates: BitrateMetadata,
+    pub delays: DelayMetadata,
+    pub sub_format: SubFormat,
     pub retiming_options: RetimingOptions,
     pub targets: Option<HashMap<Target, ConfigTarget>>,
     pub container: Container,
@@ -48,8 +67,17 @@ pub struct ConfigTarget {
     pub chapters: Option<Chapters>,
     pub defaults: Option<DefaultDispositions>,
     pub forceds: Option<ForcedDispositions>,
+    pub hearing_impaireds: Option<HearingImpairedDispositions>,
+    pub comments: Option<CommentDispositions>,
+    pub originals: Option<OriginalDispositions>,
+    pub visual_impaireds: Option<VisualImpairedDispositions>,
+    pub dubs: Option<DubDispositions>,
     pub names: Option<NameMetadata>,
     pub langs: Option<LangMetadata>,
+    pub codecs: Option<CodecMetadata>,
+    pub bitrates: Option<BitrateMetadata>,
+    pub delays: Option<DelayMetadata>,
+    pub sub_format: Option<SubFormat>,
 }
 
 impl Config {
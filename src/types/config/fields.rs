@@ -1,7 +1,9 @@
 use super::{Config, ConfigTarget};
 use crate::{
-    Chapters, DefaultDispositions, DispositionType, Dispositions, Field, ForcedDispositions,
-    LangMetadata, NameMetadata, Stream, Streams, Target,
+    BitrateMetadata, Chapters, CodecMetadata, CommentDispositions, DefaultDispositions,
+    DelayMetadata, DispositionType, Dispositions, DubDispositions, Field, ForcedDispositions,
+    HearingImpairedDispositions, LangMetadata, NameMetadata, OriginalDispositions, Stream, Streams,
+    SubFormat, Target, VisualImpairedDispositions,
 };
 use std::path::Path;
 
@@ -60,15 +62,21 @@ impl Config {
         I: IntoIterator<Item = T>,
         T: AsRef<Path>,
     {
-        match ty {
-            DispositionType::Default => {
-                let (i, xs) = self.stream_val(CfgDefaults, target_paths, stream);
-                (i, &xs.0)
-            }
-            DispositionType::Forced => {
-                let (i, xs) = self.stream_val(CfgForceds, target_paths, stream);
+        macro_rules! val {
+            ($marker:ident) => {{
+                let (i, xs) = self.stream_val($marker, target_paths, stream);
                 (i, &xs.0)
-            }
+            }};
+        }
+
+        match ty {
+            DispositionType::Default => val!(CfgDefaults),
+            DispositionType::Forced => val!(CfgForceds),
+            DispositionType::HearingImpaired => val!(CfgHearingImpaireds),
+            DispositionType::Comment => val!(CfgComments),
+            DispositionType::Original => val!(CfgOriginals),
+            DispositionType::VisualImpaired => val!(CfgVisualImpaireds),
+            DispositionType::Dub => val!(CfgDubs),
         }
     }
 
@@ -144,6 +152,15 @@ fields! {
     chapters, Chapters => CfgChapters,
     defaults, DefaultDispositions => CfgDefaults,
     forceds, ForcedDispositions => CfgForceds,
+    hearing_impaireds, HearingImpairedDispositions => CfgHearingImpaireds,
+    comments, CommentDispositions => CfgComments,
+    originals, OriginalDispositions => CfgOriginals,
+    visual_impaireds, VisualImpairedDispositions => CfgVisualImpaireds,
+    dubs, DubDispositions => CfgDubs,
     names, NameMetadata => CfgNames,
     langs, LangMetadata => CfgLangs,
+    codecs, CodecMetadata => CfgCodecs,
+    bitrates, BitrateMetadata => CfgBitrates,
+    delays, DelayMetadata => CfgDelays,
+    sub_format, SubFormat => CfgSubFormat,
 }
@@ -53,9 +53,11 @@ impl Blocks {
 enum AutoFlag {
     AutoDefaults,
     AutoForceds,
+    AutoDispositions,
     AutoNames,
     AutoLangs,
     AutoEncs,
+    AutoDelays,
 }
 
 impl AutoFlag {
@@ -63,9 +65,11 @@ impl AutoFlag {
         match self {
             Self::AutoDefaults => CliArg::HelpAutoDefaults,
             Self::AutoForceds => CliArg::HelpAutoForceds,
+            Self::AutoDispositions => CliArg::HelpAutoDispositions,
             Self::AutoNames => CliArg::HelpAutoNames,
             Self::AutoLangs => CliArg::HelpAutoLangs,
             Self::AutoEncs => CliArg::HelpAutoEncs,
+            Self::AutoDelays => CliArg::HelpAutoDelays,
         }
     }
 
@@ -73,9 +77,11 @@ impl AutoFlag {
         match self {
             Self::AutoDefaults => CliArg::AutoDefaults,
             Self::AutoForceds => CliArg::AutoForceds,
+            Self::AutoDispositions => CliArg::AutoDispositions,
             Self::AutoNames => CliArg::AutoNames,
             Self::AutoLangs => CliArg::AutoLangs,
             Self::AutoEncs => CliArg::AutoEncs,
+            Self::AutoDelays => CliArg::AutoDelays,
         }
     }
 
@@ -83,9 +89,11 @@ impl AutoFlag {
         match self {
             Self::AutoDefaults => CliArg::NoAutoDefaults,
             Self::AutoForceds => CliArg::NoAutoForceds,
+            Self::AutoDispositions => CliArg::NoAutoDispositions,
             Self::AutoNames => CliArg::NoAutoNames,
             Self::AutoLangs => CliArg::NoAutoLangs,
             Self::AutoEncs => CliArg::NoAutoEncs,
+            Self::AutoDelays => CliArg::NoAutoDelays,
         }
     }
 
@@ -93,9 +101,11 @@ impl AutoFlag {
         match self {
             Self::AutoDefaults => Msg::HelpAutoDefaults,
             Self::AutoForceds => Msg::HelpAutoForceds,
+            Self::AutoDispositions => Msg::HelpAutoDispositions,
             Self::AutoNames => Msg::HelpAutoNames,
             Self::AutoLangs => Msg::HelpAutoLangs,
             Self::AutoEncs => Msg::HelpAutoEncs,
+            Self::AutoDelays => Msg::HelpAutoDelays,
         }
         .as_str_localized()
     }
@@ -1,7 +1,7 @@
 use super::{Blocks, val_parsers::ConfigParser};
-use crate::{LangCode, Msg, undashed};
+use crate::{Keywords, LangCode, Msg, undashed};
 use clap::{Arg, ArgAction, builder::ValueParser};
-use std::str::FromStr;
+use std::{path::PathBuf, str::FromStr};
 
 impl Blocks {
     pub fn global(mut self) -> Self {
@@ -66,8 +66,52 @@ impl Blocks {
                     .long(undashed!(Reencode))
                     .alias("re-encode")
                     .help(Msg::HelpReencode.as_str_localized())
-                    .hide(true)
                     .action(ArgAction::SetTrue),
+            )
+            .arg(
+                Arg::new(undashed!(ReencodeUnsupported))
+                    .long(undashed!(ReencodeUnsupported))
+                    .help(Msg::HelpReencodeUnsupported.as_str_localized())
+                    .action(ArgAction::SetTrue),
+            )
+            .arg(
+                Arg::new(undashed!(DryRun))
+                    .long(undashed!(DryRun))
+                    .help(Msg::HelpDryRun.as_str_localized())
+                    .action(ArgAction::SetTrue),
+            )
+            .arg(
+                Arg::new(undashed!(SyncAudio))
+                    .long(undashed!(SyncAudio))
+                    .help(Msg::HelpSyncAudio.as_str_localized())
+                    .action(ArgAction::SetTrue),
+            )
+            .arg(
+                Arg::new(undashed!(SyncSubs))
+                    .long(undashed!(SyncSubs))
+                    .help(Msg::HelpSyncSubs.as_str_localized())
+                    .action(ArgAction::SetTrue),
+            )
+            .arg(
+                Arg::new(undashed!(PlanOut))
+                    .long(undashed!(PlanOut))
+                    .value_name("json")
+                    .help(Msg::HelpPlanOut.as_str_localized())
+                    .value_parser(clap::value_parser!(PathBuf)),
+            )
+            .arg(
+                Arg::new(undashed!(PlanIn))
+                    .long(undashed!(PlanIn))
+                    .value_name("json")
+                    .help(Msg::HelpPlanIn.as_str_localized())
+                    .value_parser(clap::value_parser!(PathBuf)),
+            )
+            .arg(
+                Arg::new(undashed!(Keywords))
+                    .long(undashed!(Keywords))
+                    .value_name("json")
+                    .help(Msg::HelpKeywords.as_str_localized())
+                    .value_parser(ValueParser::new(Keywords::from_str)),
             );
 
         self
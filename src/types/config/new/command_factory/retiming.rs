@@ -1,6 +1,7 @@
-use super::Blocks;
-use crate::{Msg, RetimingOptions, undashed};
+use super::{Blocks, val_parsers::InputDirParser};
+use crate::{Msg, RetimingEdition, RetimingOptions, RetimingOptionsCut, undashed};
 use clap::{Arg, ArgAction, builder::ValueParser};
+use std::{path::PathBuf, str::FromStr};
 
 impl Blocks {
     pub fn retiming(mut self) -> Self {
@@ -14,11 +15,52 @@ impl Blocks {
                     .help(Msg::HelpParts.as_str_localized())
                     .value_parser(ValueParser::new(RetimingOptions::from_str_parts)),
             )
+            .arg(
+                Arg::new(undashed!(Cut))
+                    .long(undashed!(Cut))
+                    .value_name("[!]start-end[,start-end]...")
+                    .help(Msg::HelpCut.as_str_localized())
+                    .value_parser(ValueParser::new(RetimingOptionsCut::from_str)),
+            )
+            .arg(
+                Arg::new(undashed!(Edition))
+                    .long(undashed!(Edition))
+                    .value_name("n|name")
+                    .help(Msg::HelpEdition.as_str_localized())
+                    .value_parser(ValueParser::new(RetimingEdition::from_str)),
+            )
+            .arg(
+                Arg::new(undashed!(SegmentsDir))
+                    .long(undashed!(SegmentsDir))
+                    .value_name("dir")
+                    .help(Msg::HelpSegmentsDir.as_str_localized())
+                    .value_parser(ValueParser::new(InputDirParser))
+                    .action(ArgAction::Append),
+            )
+            .arg(
+                Arg::new(undashed!(SegmentsCache))
+                    .long(undashed!(SegmentsCache))
+                    .value_name("json")
+                    .help(Msg::HelpSegmentsCache.as_str_localized())
+                    .value_parser(clap::value_parser!(PathBuf)),
+            )
             .arg(
                 Arg::new(undashed!(NoLinked))
                     .long(undashed!(NoLinked))
                     .help(Msg::HelpNoLinked.as_str_localized())
                     .action(ArgAction::SetTrue),
+            )
+            .arg(
+                Arg::new(undashed!(SmartCut))
+                    .long(undashed!(SmartCut))
+                    .help(Msg::HelpSmartCut.as_str_localized())
+                    .action(ArgAction::SetTrue),
+            )
+            .arg(
+                Arg::new(undashed!(ExactAudio))
+                    .long(undashed!(ExactAudio))
+                    .help(Msg::HelpExactAudio.as_str_localized())
+                    .action(ArgAction::SetTrue),
             );
 
         self
@@ -1,9 +1,12 @@
 use super::Blocks;
 use crate::{
-    DefaultDispositions, ForcedDispositions, LangMetadata, Msg, NameMetadata, Streams, undashed,
+    BitrateMetadata, CodecMetadata, CommentDispositions, DefaultDispositions, DelayMetadata,
+    DubDispositions, ForcedDispositions, HearingImpairedDispositions, LangMetadata, Msg,
+    NameMetadata, OriginalDispositions, Streams, SubStretch, SubType, VisualImpairedDispositions,
+    undashed,
 };
 use clap::{Arg, ArgAction, builder::ValueParser};
-use std::str::FromStr;
+use std::{path::PathBuf, str::FromStr};
 
 impl Blocks {
     pub fn target(mut self) -> Self {
@@ -85,6 +88,76 @@ impl Blocks {
                     .help(Msg::HelpMaxForceds.as_str_localized())
                     .value_parser(clap::value_parser!(usize)),
             )
+            .arg(
+                Arg::new(undashed!(HearingImpaireds))
+                    .long(undashed!(HearingImpaireds))
+                    .value_name("[n:]B[,m:B]...")
+                    .help(Msg::HelpHearingImpaireds.as_str_localized())
+                    .value_parser(ValueParser::new(HearingImpairedDispositions::from_str)),
+            )
+            .arg(
+                Arg::new(undashed!(MaxHearingImpaireds))
+                    .long(undashed!(MaxHearingImpaireds))
+                    .value_name("n")
+                    .help(Msg::HelpMaxHearingImpaireds.as_str_localized())
+                    .value_parser(clap::value_parser!(usize)),
+            )
+            .arg(
+                Arg::new(undashed!(Comments))
+                    .long(undashed!(Comments))
+                    .value_name("[n:]B[,m:B]...")
+                    .help(Msg::HelpComments.as_str_localized())
+                    .value_parser(ValueParser::new(CommentDispositions::from_str)),
+            )
+            .arg(
+                Arg::new(undashed!(MaxComments))
+                    .long(undashed!(MaxComments))
+                    .value_name("n")
+                    .help(Msg::HelpMaxComments.as_str_localized())
+                    .value_parser(clap::value_parser!(usize)),
+            )
+            .arg(
+                Arg::new(undashed!(Originals))
+                    .long(undashed!(Originals))
+                    .value_name("[n:]B[,m:B]...")
+                    .help(Msg::HelpOriginals.as_str_localized())
+                    .value_parser(ValueParser::new(OriginalDispositions::from_str)),
+            )
+            .arg(
+                Arg::new(undashed!(MaxOriginals))
+                    .long(undashed!(MaxOriginals))
+                    .value_name("n")
+                    .help(Msg::HelpMaxOriginals.as_str_localized())
+                    .value_parser(clap::value_parser!(usize)),
+            )
+            .arg(
+                Arg::new(undashed!(VisualImpaireds))
+                    .long(undashed!(VisualImpaireds))
+                    .value_name("[n:]B[,m:B]...")
+                    .help(Msg::HelpVisualImpaireds.as_str_localized())
+                    .value_parser(ValueParser::new(VisualImpairedDispositions::from_str)),
+            )
+            .arg(
+                Arg::new(undashed!(MaxVisualImpaireds))
+                    .long(undashed!(MaxVisualImpaireds))
+                    .value_name("n")
+                    .help(Msg::HelpMaxVisualImpaireds.as_str_localized())
+                    .value_parser(clap::value_parser!(usize)),
+            )
+            .arg(
+                Arg::new(undashed!(Dubs))
+                    .long(undashed!(Dubs))
+                    .value_name("[n:]B[,m:B]...")
+                    .help(Msg::HelpDubs.as_str_localized())
+                    .value_parser(ValueParser::new(DubDispositions::from_str)),
+            )
+            .arg(
+                Arg::new(undashed!(MaxDubs))
+                    .long(undashed!(MaxDubs))
+                    .value_name("n")
+                    .help(Msg::HelpMaxDubs.as_str_localized())
+                    .value_parser(clap::value_parser!(usize)),
+            )
             .arg(
                 Arg::new(undashed!(Names))
                     .long(undashed!(Names))
@@ -98,6 +171,56 @@ impl Blocks {
                     .value_name("[n:]L[,m:L]...")
                     .help(Msg::HelpLangs.as_str_localized())
                     .value_parser(ValueParser::new(LangMetadata::from_str)),
+            )
+            .arg(
+                Arg::new(undashed!(Codecs))
+                    .long(undashed!(Codecs))
+                    .value_name("[n:]C[,m:C]...")
+                    .help(Msg::HelpCodecs.as_str_localized())
+                    .value_parser(ValueParser::new(CodecMetadata::from_str)),
+            )
+            .arg(
+                Arg::new(undashed!(Bitrates))
+                    .long(undashed!(Bitrates))
+                    .value_name("[n:]R[,m:R]...")
+                    .help(Msg::HelpBitrates.as_str_localized())
+                    .value_parser(ValueParser::new(BitrateMetadata::from_str)),
+            )
+            .arg(
+                Arg::new(undashed!(Delays))
+                    .long(undashed!(Delays))
+                    .value_name("[n:]ms[,m:ms]...")
+                    .help(Msg::HelpDelays.as_str_localized())
+                    .allow_hyphen_values(true)
+                    .value_parser(ValueParser::new(DelayMetadata::from_str)),
+            )
+            .arg(
+                Arg::new(undashed!(SubFormat))
+                    .long(undashed!(SubFormat))
+                    .value_name("srt|ass|vtt")
+                    .help(Msg::HelpSubFormat.as_str_localized())
+                    .value_parser(ValueParser::new(SubType::from_str)),
+            )
+            .arg(
+                Arg::new(undashed!(SubStyle))
+                    .long(undashed!(SubStyle))
+                    .value_name("ass")
+                    .help(Msg::HelpSubStyle.as_str_localized())
+                    .value_parser(clap::value_parser!(PathBuf)),
+            )
+            .arg(
+                Arg::new(undashed!(SubStretch))
+                    .long(undashed!(SubStretch))
+                    .value_name("auto|R|fps:fps")
+                    .help(Msg::HelpSubStretch.as_str_localized())
+                    .value_parser(ValueParser::new(SubStretch::from_str)),
+            )
+            .arg(
+                Arg::new(undashed!(SplitForcedPgs))
+                    .long(undashed!(SplitForcedPgs))
+                    .value_name("[!]n[,m]...")
+                    .help(Msg::HelpSplitForcedPgs.as_str_localized())
+                    .value_parser(ValueParser::new(Streams::from_str)),
             );
 
         self
@@ -1,10 +1,11 @@
-use crate::{Config, Container, IsDefault, Msg, MuxLogger, Output, Result, TryFinalizeInit};
+use crate::{Config, Container, IsDefault, Msg, MuxLogger, Output, Result, TryFinalizeInit, Value};
 
 impl TryFinalizeInit for Config {
     fn try_finalize_init(&mut self) -> Result<()> {
         input(self)?;
         output(self)?;
         container(self);
+        plan_in(self);
 
         return Ok(());
 
@@ -36,12 +37,35 @@ impl TryFinalizeInit for Config {
                 c = Container::Matroska;
             }
 
-            let ext = c.as_ext();
-            if ext != &cfg.output.ext {
-                cfg.output.ext = ext.into();
+            if Container::from_output(&cfg.output) != Some(c) {
+                cfg.output.ext = c.as_ext().into();
             }
 
             cfg.container = c;
         }
+
+        // A plan keeps tracks as resolved, so nothing is detected again.
+        // Charset detection stays: it only decodes text subs of the sources.
+        fn plan_in(cfg: &mut Config) {
+            if cfg.plan_in.is_none() {
+                return;
+            }
+
+            let auto = &mut cfg.auto_flags;
+            auto.pro = true;
+            [
+                &mut auto.defaults,
+                &mut auto.forceds,
+                &mut auto.dispositions,
+                &mut auto.names,
+                &mut auto.langs,
+                &mut auto.delays,
+            ]
+            .into_iter()
+            .for_each(|v| *v = Value::User(false));
+
+            cfg.sync_audio = false;
+            cfg.sync_subs = false;
+        }
     }
 }
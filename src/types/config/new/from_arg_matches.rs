@@ -1,8 +1,11 @@
 use super::super::{Config, ConfigTarget};
 use crate::{
-    AutoFlags, Chapters, CliArg, DefaultDispositions, Dispositions, ForcedDispositions,
-    GlobSetPattern, Input, LangCode, LangMetadata, LogLevel, Msg, MuxError, NameMetadata, Output,
-    RangeUsize, RetimingOptions, StreamType, Streams, Target, VERSION, Value, undashed,
+    AutoFlags, BitrateMetadata, Chapters, CliArg, CodecMetadata, CommentDispositions,
+    DefaultDispositions, DelayMetadata, Dispositions, DubDispositions, ForcedDispositions,
+    GlobSetPattern, HearingImpairedDispositions, Input, IsDefault, Keywords, LangCode,
+    LangMetadata, LogLevel, Msg, MuxError, NameMetadata, OriginalDispositions, Output, RangeUsize,
+    RetimingEdition, RetimingOptions, RetimingOptionsCut, StreamType, Streams, SubFormat,
+    SubStretch, SubType, Target, VERSION, Value, VisualImpairedDispositions, undashed,
 };
 use clap::{ArgMatches, Command, CommandFactory, Error, FromArgMatches, Parser};
 use log::LevelFilter;
@@ -174,14 +177,40 @@ impl FromArgMatches for Config {
                 exit_on_err: flag!(m, ExitOnErr),
                 save_config: flag!(m, SaveConfig),
                 reencode: flag!(m, Reencode),
+                reencode_unsupported: flag!(m, ReencodeUnsupported),
+                dry_run: flag!(m, DryRun),
+                sync_audio: flag!(m, SyncAudio),
+                sync_subs: flag!(m, SyncSubs),
+                plan_in: rm!(m, PlanIn, PathBuf),
+                plan_out: rm!(m, PlanOut, PathBuf),
+                keywords: rm_or!(m, Keywords, Keywords, Keywords::default),
                 jobs: rm_or!(m, Jobs, u8, || Config::JOBS_DEFAULT),
                 auto_flags: auto_flags(m),
                 streams: streams!(m, Streams, NoStreams),
                 chapters: get_chapters(m).unwrap_or_else(|| Chapters::default()),
                 defaults: dispositions!(m, Defaults, MaxDefaults, DefaultDispositions),
                 forceds: dispositions!(m, Forceds, MaxForceds, ForcedDispositions),
+                hearing_impaireds: dispositions!(
+                    m,
+                    HearingImpaireds,
+                    MaxHearingImpaireds,
+                    HearingImpairedDispositions
+                ),
+                comments: dispositions!(m, Comments, MaxComments, CommentDispositions),
+                originals: dispositions!(m, Originals, MaxOriginals, OriginalDispositions),
+                visual_impaireds: dispositions!(
+                    m,
+                    VisualImpaireds,
+                    MaxVisualImpaireds,
+                    VisualImpairedDispositions
+                ),
+                dubs: dispositions!(m, Dubs, MaxDubs, DubDispositions),
                 names: rm_or!(m, Names, NameMetadata, NameMetadata::default),
                 langs: rm_or!(m, Langs, LangMetadata, LangMetadata::default),
+                codecs: rm_or!(m, Codecs, CodecMetadata, CodecMetadata::default),
+                bitrates: rm_or!(m, Bitrates, BitrateMetadata, BitrateMetadata::default),
+                delays: rm_or!(m, Delays, DelayMetadata, DelayMetadata::default),
+                sub_format: get_sub_format(m).unwrap_or_default(),
                 retiming_options: retiming_options(m),
                 targets: targets(m),
                 container: Default::default(),
@@ -209,9 +238,15 @@ impl FromArgMatches for Config {
 
             new.defaults = val(flag!(m, AutoDefaults), flag!(m, NoAutoDefaults), pro);
             new.forceds = val(flag!(m, AutoForceds), flag!(m, NoAutoForceds), pro);
+            new.dispositions = val(
+                flag!(m, AutoDispositions),
+                flag!(m, NoAutoDispositions),
+                pro,
+            );
             new.names = val(flag!(m, AutoNames), flag!(m, NoAutoNames), pro);
             new.langs = val(flag!(m, AutoLangs), flag!(m, NoAutoLangs), pro);
             new.encs = val(flag!(m, AutoEncs), flag!(m, NoAutoEncs), pro);
+            new.delays = val(flag!(m, AutoDelays), flag!(m, NoAutoDelays), pro);
 
             return new;
 
@@ -228,7 +263,15 @@ impl FromArgMatches for Config {
 
         fn retiming_options(m: &mut ArgMatches) -> RetimingOptions {
             let mut opts = rm_or!(m, Parts, RetimingOptions, RetimingOptions::default);
+            opts.cut = rm_or!(m, Cut, RetimingOptionsCut, RetimingOptionsCut::default);
+            opts.edition = rm!(m, Edition, RetimingEdition);
+            opts.segments.dirs = m
+                .remove_many::<PathBuf>(undashed!(SegmentsDir))
+                .map_or_else(Vec::new, |dirs| dirs.collect());
+            opts.segments.cache = rm!(m, SegmentsCache, PathBuf);
             opts.no_linked = flag!(m, NoLinked);
+            opts.smart_cut = flag!(m, SmartCut);
+            opts.exact_audio = flag!(m, ExactAudio);
             opts
         }
 
@@ -269,6 +312,13 @@ impl FromArgMatches for Config {
         upd_flag!(self.exit_on_err, m, ExitOnErr);
         upd_flag!(self.save_config, m, SaveConfig);
         upd_flag!(self.reencode, m, Reencode);
+        upd_flag!(self.reencode_unsupported, m, ReencodeUnsupported);
+        upd_flag!(self.dry_run, m, DryRun);
+        upd_flag!(self.sync_audio, m, SyncAudio);
+        upd_flag!(self.sync_subs, m, SyncSubs);
+        upd!(self.plan_in, m, PlanIn, PathBuf, @opt);
+        upd!(self.plan_out, m, PlanOut, PathBuf, @opt);
+        upd!(self.keywords, m, Keywords, Keywords);
         upd!(self.jobs, m, Jobs, u8);
 
         auto_flags(self, m);
@@ -279,6 +329,34 @@ impl FromArgMatches for Config {
 
         upd_dispositions!(self.defaults, m, Defaults, MaxDefaults, DefaultDispositions);
         upd_dispositions!(self.forceds, m, Forceds, MaxForceds, ForcedDispositions);
+        upd_dispositions!(
+            self.hearing_impaireds,
+            m,
+            HearingImpaireds,
+            MaxHearingImpaireds,
+            HearingImpairedDispositions
+        );
+        upd_dispositions!(self.comments, m, Comments, MaxComments, CommentDispositions);
+        upd_dispositions!(
+            self.originals,
+            m,
+            Originals,
+            MaxOriginals,
+            OriginalDispositions
+        );
+        upd_dispositions!(
+            self.visual_impaireds,
+            m,
+            VisualImpaireds,
+            MaxVisualImpaireds,
+            VisualImpairedDispositions
+        );
+        upd_dispositions!(self.dubs, m, Dubs, MaxDubs, DubDispositions);
+
+        upd!(self.codecs, m, Codecs, CodecMetadata);
+        upd!(self.bitrates, m, Bitrates, BitrateMetadata);
+        upd!(self.delays, m, Delays, DelayMetadata);
+        upd_sub_format(&mut self.sub_format, m);
 
         retiming_options(self, m);
         targets(self, m);
@@ -382,6 +460,12 @@ impl FromArgMatches for Config {
                 pro,
                 &mut auto.forceds,
             );
+            upd(
+                flag!(m, AutoDispositions),
+                flag!(m, NoAutoDispositions),
+                pro,
+                &mut auto.dispositions,
+            );
 
             upd(
                 flag!(m, AutoNames),
@@ -401,6 +485,12 @@ impl FromArgMatches for Config {
                 pro,
                 &mut auto.encs,
             );
+            upd(
+                flag!(m, AutoDelays),
+                flag!(m, NoAutoDelays),
+                pro,
+                &mut auto.delays,
+            );
 
             fn upd(arg: bool, no_arg: bool, pro: bool, val: &mut Value<bool>) {
                 if arg {
@@ -415,7 +505,15 @@ impl FromArgMatches for Config {
 
         fn retiming_options(cfg: &mut Config, m: &mut ArgMatches) {
             upd!(cfg.retiming_options, m, Parts, RetimingOptions);
+            upd!(cfg.retiming_options.cut, m, Cut, RetimingOptionsCut);
+            upd!(cfg.retiming_options.edition, m, Edition, RetimingEdition, @opt);
+            if let Some(dirs) = m.remove_many::<PathBuf>(undashed!(SegmentsDir)) {
+                cfg.retiming_options.segments.dirs = dirs.collect();
+            }
+            upd!(cfg.retiming_options.segments.cache, m, SegmentsCache, PathBuf, @opt);
             upd_flag!(cfg.retiming_options.no_linked, m, NoLinked);
+            upd_flag!(cfg.retiming_options.smart_cut, m, SmartCut);
+            upd_flag!(cfg.retiming_options.exact_audio, m, ExactAudio);
         }
 
         fn targets(cfg: &mut Config, m: &mut ArgMatches) {
@@ -533,6 +631,27 @@ fn upd_chapters(chp: &mut Chapters, m: &mut ArgMatches) {
     }
 }
 
+fn get_sub_format(m: &mut ArgMatches) -> Option<SubFormat> {
+    let mut fmt = SubFormat::default();
+    upd_sub_format(&mut fmt, m);
+    (!fmt.is_default()).then_some(fmt)
+}
+
+fn upd_sub_format(fmt: &mut SubFormat, m: &mut ArgMatches) {
+    if let Some(ty) = rm!(m, SubFormat, SubType) {
+        fmt.ty = Some(ty);
+    }
+    if let Some(style) = rm!(m, SubStyle, PathBuf) {
+        fmt.style = Some(style);
+    }
+    if let Some(stretch) = rm!(m, SubStretch, SubStretch) {
+        fmt.stretch = Some(stretch);
+    }
+    if let Some(streams) = rm!(m, SplitForcedPgs, Streams) {
+        fmt.split_forced_pgs = Some(streams);
+    }
+}
+
 macro_rules! trg_upd_dispositions {
     ($field:expr, $matches:ident, $arg:ident, $lim_arg:ident, $ty:ident) => {
         match $field.as_mut() {
@@ -557,8 +676,27 @@ impl FromArgMatches for ConfigTarget {
             chapters: get_chapters(m),
             defaults: get_dispositions!(m, Defaults, MaxDefaults, DefaultDispositions),
             forceds: get_dispositions!(m, Forceds, MaxForceds, ForcedDispositions),
+            hearing_impaireds: get_dispositions!(
+                m,
+                HearingImpaireds,
+                MaxHearingImpaireds,
+                HearingImpairedDispositions
+            ),
+            comments: get_dispositions!(m, Comments, MaxComments, CommentDispositions),
+            originals: get_dispositions!(m, Originals, MaxOriginals, OriginalDispositions),
+            visual_impaireds: get_dispositions!(
+                m,
+                VisualImpaireds,
+                MaxVisualImpaireds,
+                VisualImpairedDispositions
+            ),
+            dubs: get_dispositions!(m, Dubs, MaxDubs, DubDispositions),
             names: rm!(m, Names, NameMetadata),
             langs: rm!(m, Langs, LangMetadata),
+            codecs: rm!(m, Codecs, CodecMetadata),
+            bitrates: rm!(m, Bitrates, BitrateMetadata),
+            delays: rm!(m, Delays, DelayMetadata),
+            sub_format: get_sub_format(m),
         })
     }
 
@@ -568,9 +706,37 @@ impl FromArgMatches for ConfigTarget {
 
         trg_upd_dispositions!(self.defaults, m, Defaults, MaxDefaults, DefaultDispositions);
         trg_upd_dispositions!(self.forceds, m, Forceds, MaxForceds, ForcedDispositions);
+        trg_upd_dispositions!(
+            self.hearing_impaireds,
+            m,
+            HearingImpaireds,
+            MaxHearingImpaireds,
+            HearingImpairedDispositions
+        );
+        trg_upd_dispositions!(self.comments, m, Comments, MaxComments, CommentDispositions);
+        trg_upd_dispositions!(
+            self.originals,
+            m,
+            Originals,
+            MaxOriginals,
+            OriginalDispositions
+        );
+        trg_upd_dispositions!(
+            self.visual_impaireds,
+            m,
+            VisualImpaireds,
+            MaxVisualImpaireds,
+            VisualImpairedDispositions
+        );
+        trg_upd_dispositions!(self.dubs, m, Dubs, MaxDubs, DubDispositions);
 
         upd!(self.names, m, Names, NameMetadata, @opt);
         upd!(self.langs, m, Langs, LangMetadata, @opt);
+        upd!(self.codecs, m, Codecs, CodecMetadata, @opt);
+        upd!(self.bitrates, m, Bitrates, BitrateMetadata, @opt);
+        upd!(self.delays, m, Delays, DelayMetadata, @opt);
+
+        trg_upd_sub_format(&mut self.sub_format, m);
 
         return Ok(());
 
@@ -587,5 +753,12 @@ impl FromArgMatches for ConfigTarget {
                 None => *chp = get_chapters(m),
             }
         }
+
+        fn trg_upd_sub_format(fmt: &mut Option<SubFormat>, m: &mut ArgMatches) {
+            match fmt.as_mut() {
+                Some(fmt) => upd_sub_format(fmt, m),
+                None => *fmt = get_sub_format(m),
+            }
+        }
     }
 }
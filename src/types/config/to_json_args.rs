@@ -50,7 +50,11 @@ impl ToJsonArgs for Config {
 
         append_args_from_fields!(self, args; input, output, log_level);
 
-        to_json_args!(@push_true, self, args; exit_on_err, ExitOnErr, save_config, SaveConfig);
+        to_json_args!(
+            @push_true, self, args;
+            exit_on_err, ExitOnErr, save_config, SaveConfig, reencode, Reencode,
+            reencode_unsupported, ReencodeUnsupported, sync_audio, SyncAudio, sync_subs, SyncSubs
+        );
 
         if self.jobs != Self::JOBS_DEFAULT {
             args.push(to_json_args!(Jobs));
@@ -59,13 +63,23 @@ impl ToJsonArgs for Config {
 
         append_args_from_fields!(
             self, args;
+            keywords,
             auto_flags,
             streams,
             chapters,
             defaults,
             forceds,
+            hearing_impaireds,
+            comments,
+            originals,
+            visual_impaireds,
+            dubs,
             names,
             langs,
+            codecs,
+            bitrates,
+            delays,
+            sub_format,
         );
 
         if let Some(targets) = &self.targets {
@@ -112,8 +126,17 @@ impl ToJsonArgs for ConfigTarget {
             chapters,
             defaults,
             forceds,
+            hearing_impaireds,
+            comments,
+            originals,
+            visual_impaireds,
+            dubs,
             names,
-            langs
+            langs,
+            codecs,
+            bitrates,
+            delays,
+            sub_format
         );
     }
 }
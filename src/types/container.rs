@@ -1,4 +1,5 @@
-//mod is_supported_copy;
+mod fallback_codec;
+mod is_supported_copy;
 mod new;
 
 use crate::IsDefault;
@@ -10,6 +11,10 @@ use strum_macros::Display;
 pub enum Container {
     #[default]
     Matroska,
+    #[strum(serialize = "MP4")]
+    Mp4,
+    #[strum(serialize = "WebM")]
+    Webm,
 }
 
 impl Container {
@@ -18,9 +23,19 @@ impl Container {
     pub(crate) const fn as_ext(self) -> &'static str {
         match self {
             //Self::Avi => "avi",
-            //Self::Mp4 => "mp4",
+            Self::Mp4 => "mp4",
             Self::Matroska => "mkv",
-            //Self::Webm => "webm",
+            Self::Webm => "webm",
+        }
+    }
+
+    /// Returns a name of ffmpeg muxer for a container.
+    #[inline]
+    pub(crate) const fn as_format(self) -> &'static str {
+        match self {
+            Self::Mp4 => "mp4",
+            Self::Matroska => "matroska",
+            Self::Webm => "webm",
         }
     }
 }
@@ -0,0 +1,26 @@
+use super::Container;
+use crate::{
+    CodecId, StreamType,
+    ffmpeg::{codec::id::Id, encoder},
+};
+
+impl Container {
+    /// Returns a codec to transcode a stream into if the container can't copy its codec.
+    ///
+    /// Picks the first codec with an available ffmpeg encoder.
+    pub fn fallback_codec(self, ty: StreamType, codec: CodecId) -> Option<CodecId> {
+        let ids: &[Id] = match (self, ty) {
+            (Self::Mp4, StreamType::Audio) => &[Id::AAC, Id::AC3, Id::MP3],
+            (Self::Mp4, StreamType::Video) => &[Id::H264, Id::HEVC, Id::MPEG4],
+            (Self::Mp4, StreamType::Sub) if codec.is_text_sub() => &[Id::MOV_TEXT],
+            (Self::Webm, StreamType::Audio) => &[Id::OPUS, Id::VORBIS],
+            (Self::Webm, StreamType::Video) => &[Id::VP9, Id::VP8, Id::AV1],
+            (Self::Webm, StreamType::Sub) if codec.is_text_sub() => &[Id::WEBVTT],
+            _ => &[],
+        };
+
+        ids.iter()
+            .find(|id| encoder::find(**id).is_some())
+            .map(|id| CodecId(*id))
+    }
+}
@@ -3,12 +3,9 @@ use crate::{Extension, Msg, MuxLogger, Output};
 
 impl Container {
     pub fn new(output: &Output) -> Container {
-        match Extension::new(output.ext.as_encoded_bytes()) {
-            //Some(Extension::Avi) => Self::Avi,
-            //Some(Extension::Mp4) => Self::Mp4,
-            //Some(Extension::Webm) => Self::Webm,
-            Some(Extension::Mkv) => Self::Matroska,
-            _ => {
+        match Self::from_output(output) {
+            Some(c) => c,
+            None => {
                 eprintln!(
                     "{}{}. {} Matroska (.mkv)",
                     MuxLogger::color_prefix(log::Level::Warn),
@@ -19,4 +16,15 @@ impl Container {
             }
         }
     }
+
+    /// Returns a container for the output extension, if any.
+    pub(crate) fn from_output(output: &Output) -> Option<Container> {
+        match Extension::new(output.ext.as_encoded_bytes())? {
+            //Extension::Avi => Some(Self::Avi),
+            Extension::Mp4 => Some(Self::Mp4),
+            Extension::Webm | Extension::Weba => Some(Self::Webm),
+            Extension::Mkv => Some(Self::Matroska),
+            _ => None,
+        }
+    }
 }
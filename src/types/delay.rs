@@ -0,0 +1,77 @@
+use crate::{
+    IsDefault, MuxError, Result,
+    ffmpeg::{Rational, Rescale},
+};
+use std::{fmt, str::FromStr};
+
+/// A stream delay in milliseconds.
+///
+/// Parses a signed number with an optional `ms` suffix.
+/// ```
+/// use mux_media::Delay;
+///
+/// assert_eq!(Delay(120), "120".parse().unwrap());
+/// assert_eq!(Delay(-42), "-42ms".parse().unwrap());
+/// ```
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+pub struct Delay(pub i64);
+
+deref_singleton_tuple_struct!(Delay, i64);
+
+impl Delay {
+    /// Finds an eac3to-style `DELAY <n>ms` hint in the string.
+    /// ```
+    /// use mux_media::Delay;
+    ///
+    /// assert_eq!(Some(Delay(-42)), Delay::new_from_hint("Ep01.eng DELAY -42ms"));
+    /// assert_eq!(Some(Delay(120)), Delay::new_from_hint("delay 120 ms"));
+    /// assert_eq!(None, Delay::new_from_hint("Ep01.eng"));
+    /// ```
+    pub fn new_from_hint(s: &str) -> Option<Delay> {
+        // Uppercasing ASCII keeps byte indices the same.
+        let i = s.to_ascii_uppercase().find("DELAY")?;
+        let rest = s[i + "DELAY".len()..].trim_start();
+
+        let len = rest
+            .char_indices()
+            .find(|(i, c)| !(c.is_ascii_digit() || (*i == 0 && matches!(c, '-' | '+'))))
+            .map_or(rest.len(), |(i, _)| i);
+        let num = rest[..len].parse::<i64>().ok()?;
+
+        rest[len..]
+            .trim_start()
+            .get(..2)
+            .filter(|unit| unit.eq_ignore_ascii_case("ms"))
+            .map(|_| Delay(num))
+    }
+
+    /// Returns the delay in the time base units.
+    pub(crate) fn to_ts(self, tb: Rational) -> i64 {
+        self.0.rescale(Rational(1, 1000), tb)
+    }
+}
+
+impl IsDefault for Delay {
+    fn is_default(&self) -> bool {
+        self.0 == 0
+    }
+}
+
+impl FromStr for Delay {
+    type Err = MuxError;
+
+    fn from_str(s: &str) -> Result<Delay> {
+        let s = s.trim();
+        let s = match s.len().checked_sub(2) {
+            Some(i) if s.is_char_boundary(i) && s[i..].eq_ignore_ascii_case("ms") => &s[..i],
+            _ => s,
+        };
+        Ok(Delay(s.trim_end().parse::<i64>()?))
+    }
+}
+
+impl fmt::Display for Delay {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
@@ -7,15 +7,35 @@ pub(crate) mod ty;
 use crate::{IsDefault, Lang, RangeUsize};
 use std::collections::HashMap;
 
-/// A `default` dispositions configuraion.
+/// A `default` dispositions configuration.
 #[derive(Clone, Debug, Default, PartialEq, IsDefault)]
 pub struct DefaultDispositions(pub Dispositions);
 
-/// A `forced` dispositions configuraion.
+/// A `forced` dispositions configuration.
 #[derive(Clone, Debug, Default, PartialEq, IsDefault)]
 pub struct ForcedDispositions(pub Dispositions);
 
-/// A dispositions configuraion.
+/// A `hearing impaired` dispositions configuration.
+#[derive(Clone, Debug, Default, PartialEq, IsDefault)]
+pub struct HearingImpairedDispositions(pub Dispositions);
+
+/// A `commentary` dispositions configuration.
+#[derive(Clone, Debug, Default, PartialEq, IsDefault)]
+pub struct CommentDispositions(pub Dispositions);
+
+/// A `original` dispositions configuration.
+#[derive(Clone, Debug, Default, PartialEq, IsDefault)]
+pub struct OriginalDispositions(pub Dispositions);
+
+/// A `visual impaired` dispositions configuration.
+#[derive(Clone, Debug, Default, PartialEq, IsDefault)]
+pub struct VisualImpairedDispositions(pub Dispositions);
+
+/// A `dub` dispositions configuration.
+#[derive(Clone, Debug, Default, PartialEq, IsDefault)]
+pub struct DubDispositions(pub Dispositions);
+
+/// A dispositions configuration.
 #[derive(Clone, Debug, Default, PartialEq, IsDefault)]
 pub struct Dispositions {
     pub max_in_auto: Option<usize>,
@@ -27,3 +47,8 @@ pub struct Dispositions {
 
 deref_singleton_tuple_struct!(DefaultDispositions, Dispositions, @from_str);
 deref_singleton_tuple_struct!(ForcedDispositions, Dispositions, @from_str);
+deref_singleton_tuple_struct!(HearingImpairedDispositions, Dispositions, @from_str);
+deref_singleton_tuple_struct!(CommentDispositions, Dispositions, @from_str);
+deref_singleton_tuple_struct!(OriginalDispositions, Dispositions, @from_str);
+deref_singleton_tuple_struct!(VisualImpairedDispositions, Dispositions, @from_str);
+deref_singleton_tuple_struct!(DubDispositions, Dispositions, @from_str);
@@ -7,6 +7,7 @@ impl Dispositions {
         self.max_in_auto.unwrap_or(match ty {
             DispositionType::Default => 1,
             DispositionType::Forced => 0,
+            _ => usize::MAX,
         })
     }
 }
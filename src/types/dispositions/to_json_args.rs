@@ -19,3 +19,16 @@ macro_rules! to_json_args_impl {
 
 to_json_args_impl!(DefaultDispositions, Defaults, MaxDefaults);
 to_json_args_impl!(ForcedDispositions, Forceds, MaxForceds);
+to_json_args_impl!(
+    HearingImpairedDispositions,
+    HearingImpaireds,
+    MaxHearingImpaireds
+);
+to_json_args_impl!(CommentDispositions, Comments, MaxComments);
+to_json_args_impl!(OriginalDispositions, Originals, MaxOriginals);
+to_json_args_impl!(
+    VisualImpairedDispositions,
+    VisualImpaireds,
+    MaxVisualImpaireds
+);
+to_json_args_impl!(DubDispositions, Dubs, MaxDubs);
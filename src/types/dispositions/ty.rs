@@ -9,6 +9,11 @@ use strum_macros::{AsRefStr, EnumIter};
 pub enum DispositionType {
     Default,
     Forced,
+    HearingImpaired,
+    Comment,
+    Original,
+    VisualImpaired,
+    Dub,
 }
 
 impl DispositionType {
@@ -20,6 +25,11 @@ impl DispositionType {
         match self {
             DispositionType::Default => stream::Disposition::DEFAULT.bits(),
             DispositionType::Forced => stream::Disposition::FORCED.bits(),
+            DispositionType::HearingImpaired => stream::Disposition::HEARING_IMPAIRED.bits(),
+            DispositionType::Comment => stream::Disposition::COMMENT.bits(),
+            DispositionType::Original => stream::Disposition::ORIGINAL.bits(),
+            DispositionType::VisualImpaired => stream::Disposition::VISUAL_IMPAIRED.bits(),
+            DispositionType::Dub => stream::Disposition::DUB.bits(),
         }
     }
 }
@@ -1,4 +1,4 @@
-use super::{Input, InputFileType, iters::DirIter};
+use super::{Input, InputFileType};
 use crate::{Msg, Result, TryFinalizeInit};
 
 macro_rules! collect_dirs {
@@ -26,12 +26,7 @@ impl TryFinalizeInit for Input {
             );
         }
 
-        let skip = match &self.skip {
-            Some(skip) => Some(&skip.glob_set),
-            None => None,
-        };
-
-        let dirs: Vec<_> = DirIter::new(&self.dir, self.depth as usize, skip).collect();
+        let dirs: Vec<_> = self.iter_dirs_in(&self.dir).collect();
 
         self.dirs[InputFileType::Font] = collect_dirs!(self, dirs, iter_fonts_in_dir);
         self.dirs[InputFileType::Media] = dirs
@@ -46,6 +46,13 @@ impl Input {
     iter_any_files_in_dir!(iter_fonts_in_dir, new_and_is_font);
     iter_any_files_in_dir!(iter_matroska_in_dir, new_and_is_matroska);
 
+    /// Returns an iterator over the directory and its subdirectories up to the [`Input::depth`],
+    /// skipping ones matched [`Input::skip`].
+    pub(crate) fn iter_dirs_in(&self, root: &Path) -> impl Iterator<Item = ArcPathBuf> {
+        let skip = self.skip.as_ref().map(|pat| &pat.glob_set);
+        DirIter::new(root, self.depth as usize, skip)
+    }
+
     /// Collects all font files from the discovered directories.
     ///
     /// # Warning
@@ -0,0 +1,54 @@
+use crate::{LangCode, StreamType};
+use enum_map::Enum;
+use strum_macros::{AsRefStr, EnumIter};
+
+/// A kind of stream recognized by keywords in its name or path.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, AsRefStr, Enum, EnumIter)]
+#[non_exhaustive]
+#[strum(serialize_all = "kebab-case")]
+pub enum Keyword {
+    Signs,
+    Forced,
+    Full,
+    Sdh,
+    Commentary,
+}
+
+impl Keyword {
+    pub(crate) fn iter() -> impl Iterator<Item = Self> {
+        <Self as strum::IntoEnumIterator>::iter()
+    }
+
+    /// Returns built-in lowercase keywords by language.
+    pub fn builtin(self) -> &'static [(LangCode, &'static [&'static str])] {
+        match self {
+            Self::Signs => &[(LangCode::Eng, &["signs"]), (LangCode::Rus, &["надписи"])],
+            Self::Forced => &[
+                (LangCode::Eng, &["forced"]),
+                (LangCode::Rus, &["форсированные"]),
+            ],
+            Self::Full => &[(LangCode::Eng, &["full"]), (LangCode::Rus, &["полные"])],
+            Self::Sdh => &[
+                (LangCode::Eng, &["sdh", "cc", "hearing impaired"]),
+                (LangCode::Rus, &["слабослышащих"]),
+            ],
+            Self::Commentary => &[
+                (LangCode::Eng, &["commentary", "commentaries"]),
+                (LangCode::Rus, &["комментарии", "комментарий"]),
+            ],
+        }
+    }
+
+    /// Returns `true` if the keyword is applicable to streams of type `ty`.
+    pub fn is_for(self, ty: StreamType) -> bool {
+        match ty {
+            StreamType::Sub => true,
+            StreamType::Audio => matches!(self, Self::Commentary),
+            _ => false,
+        }
+    }
+
+    pub(crate) fn new(s: &str) -> Option<Keyword> {
+        Self::iter().find(|kw| kw.as_ref() == s)
+    }
+}
@@ -0,0 +1,157 @@
+use crate::{IsDefault, Keyword, Lang, MuxError, Result, ToJsonArgs};
+use enum_map::EnumMap;
+use serde_json::{Map, Value as Json};
+use std::{collections::HashMap, fs, str::FromStr};
+
+/// Keyword dictionaries by [`Keyword`] and language.
+///
+/// Holds user-defined words only. Built-in words of [`Keyword::builtin`] are used for all
+/// languages that are not overridden by user.
+///
+/// Parses a JSON object inline or from a file:
+/// ```
+/// use mux_media::{Keyword, Keywords};
+///
+/// let kws: Keywords = r#"{"signs": {"spa": ["carteles"], "jpn": ["看板"]}}"#.parse().unwrap();
+///
+/// assert!(kws.detect("Carteles")[Keyword::Signs]);
+/// assert!(kws.detect("[Group] 看板")[Keyword::Signs]);
+/// assert!(kws.detect("Надписи")[Keyword::Signs]);
+/// ```
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Keywords(pub HashMap<Keyword, HashMap<Lang, Vec<String>>>);
+
+deref_singleton_tuple_struct!(Keywords, HashMap<Keyword, HashMap<Lang, Vec<String>>>);
+
+impl Keywords {
+    /// Detects keywords in the string.
+    ///
+    /// Words of scripts without spaces (e.g. Chinese or Japanese) match as a substring,
+    /// other match as whole words in a row.
+    /// ```
+    /// use mux_media::{Keyword, Keywords};
+    ///
+    /// let kws = Keywords::default().detect("Director's Commentary [SDH]");
+    /// assert!(kws[Keyword::Commentary] && kws[Keyword::Sdh]);
+    /// assert!(!kws[Keyword::Signs]);
+    /// assert!(!Keywords::default().detect("design")[Keyword::Signs]);
+    /// ```
+    pub fn detect(&self, s: &str) -> EnumMap<Keyword, bool> {
+        let lower = s.to_lowercase();
+        let words: Vec<&str> = str_to_words(&lower).collect();
+
+        EnumMap::from_fn(|kw: Keyword| {
+            self.words(kw).any(|phrase| {
+                if has_unspaced_script(phrase) {
+                    return lower.contains(phrase);
+                }
+                let phrase: Vec<&str> = str_to_words(phrase).collect();
+                !phrase.is_empty() && words.windows(phrase.len()).any(|ws| ws == &phrase[..])
+            })
+        })
+    }
+
+    fn words(&self, kw: Keyword) -> impl Iterator<Item = &str> {
+        let user = self.0.get(&kw);
+
+        let builtin = kw
+            .builtin()
+            .iter()
+            .filter(move |(code, _)| !user.is_some_and(|m| m.contains_key(&Lang::Code(*code))))
+            .flat_map(|(_, xs)| xs.iter().copied());
+
+        user.into_iter()
+            .flat_map(|m| m.values().flatten().map(String::as_str))
+            .chain(builtin)
+    }
+
+    fn to_json(&self) -> Json {
+        let map: Map<String, Json> = self
+            .iter()
+            .map(|(kw, langs)| {
+                let langs: Map<String, Json> = langs
+                    .iter()
+                    .map(|(lang, xs)| (lang.to_string(), Json::from(xs.clone())))
+                    .collect();
+                (kw.as_ref().to_owned(), Json::Object(langs))
+            })
+            .collect();
+        Json::Object(map)
+    }
+
+    fn try_from_json(val: &Json) -> Result<Keywords> {
+        let obj = val
+            .as_object()
+            .ok_or_else(|| err!("Keywords must be an object of categories"))?;
+
+        let mut map = HashMap::new();
+
+        for (kw, val) in obj {
+            let kw = Keyword::new(kw).ok_or_else(|| err!("Unknown keyword category '{}'", kw))?;
+            let obj = val.as_object().ok_or_else(|| {
+                err!(
+                    "Keywords of '{}' must be an object of languages",
+                    kw.as_ref()
+                )
+            })?;
+
+            let langs: &mut HashMap<Lang, Vec<String>> = map.entry(kw).or_default();
+
+            for (lang, xs) in obj {
+                let xs = xs
+                    .as_array()
+                    .and_then(|xs| {
+                        xs.iter()
+                            .map(|x| x.as_str().map(|s| s.trim().to_lowercase()))
+                            .collect::<Option<Vec<_>>>()
+                    })
+                    .ok_or_else(|| err!("Keywords of '{}' must be an array of strings", lang))?;
+
+                langs.insert(Lang::new(lang), xs);
+            }
+        }
+
+        Ok(Keywords(map))
+    }
+}
+
+impl FromStr for Keywords {
+    type Err = MuxError;
+
+    fn from_str(s: &str) -> Result<Keywords> {
+        let val: Json = if s.trim_start().starts_with('{') {
+            serde_json::from_str(s)?
+        } else {
+            serde_json::from_str(&fs::read_to_string(s)?)?
+        };
+        Keywords::try_from_json(&val)
+    }
+}
+
+impl IsDefault for Keywords {
+    fn is_default(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+impl ToJsonArgs for Keywords {
+    fn append_json_args(&self, args: &mut Vec<String>) {
+        if !self.is_default() {
+            args.push(to_json_args!(Keywords));
+            args.push(self.to_json().to_string());
+        }
+    }
+}
+
+fn str_to_words(s: &str) -> impl Iterator<Item = &str> {
+    use lazy_regex::{Lazy, Regex, regex};
+    static REGEX_WORD: &Lazy<Regex> = regex!(r"[\p{L}\p{M}]+");
+    REGEX_WORD.find_iter(s).map(|mat| mat.as_str())
+}
+
+fn has_unspaced_script(s: &str) -> bool {
+    use lazy_regex::{Lazy, Regex, regex};
+    static REGEX_UNSPACED: &Lazy<Regex> =
+        regex!(r"[\p{Han}\p{Hiragana}\p{Katakana}\p{Thai}\p{Lao}\p{Khmer}\p{Myanmar}]");
+    REGEX_UNSPACED.is_match(s)
+}
@@ -8,9 +8,43 @@ impl Lang {
             Lang::Other(s) => &s,
         }
     }
+
+    /// Returns an ISO 639-2/T code, as MP4 expects. Any non-code language is `und`.
+    pub(crate) fn as_iso639_2t(&self) -> &str {
+        match self {
+            Lang::Code(code) => code.as_iso639_2t(),
+            Lang::Other(_) => LangCode::Und.as_ref(),
+        }
+    }
 }
 
 impl LangCode {
+    fn as_iso639_2t(&self) -> &str {
+        match self {
+            Self::Alb => "sqi",
+            Self::Arm => "hye",
+            Self::Baq => "eus",
+            Self::Bur => "mya",
+            Self::Chi => "zho",
+            Self::Cze => "ces",
+            Self::Dut => "nld",
+            Self::Fre => "fra",
+            Self::Geo => "kat",
+            Self::Ger => "deu",
+            Self::Gre => "ell",
+            Self::Ice => "isl",
+            Self::Mac => "mkd",
+            Self::Mao => "mri",
+            Self::May => "msa",
+            Self::Per => "fas",
+            Self::Rum => "ron",
+            Self::Slo => "slk",
+            Self::Tib => "bod",
+            Self::Wel => "cym",
+            _ => self.as_ref(),
+        }
+    }
+
     /// Prints the list of supported language codes to stdout.
     pub(crate) fn print_list_langs() {
         println!("{}", LIST_LANGS)
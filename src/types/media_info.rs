@@ -1,8 +1,10 @@
 pub(crate) mod builders;
 pub(crate) mod cache;
 mod finalize;
-mod it_signs;
+mod keywords;
 pub(crate) mod lazy_fields;
+mod sparse_subs;
+mod sync_audio;
 
 use crate::{ArcPathBuf, Config, Result, i18n::logs};
 use cache::{CacheMI, CacheMIOfFile, CacheMIOfGroup, CacheState};
@@ -2,7 +2,10 @@ mod durations;
 mod streams;
 
 use super::MediaInfo;
-use crate::{CharEncoding, Extension, Result, StreamsOrder, Target, markers::*, types::helpers};
+use crate::{
+    CharEncoding, Delay, Extension, Result, StreamsOrder, SubDensity, SubType, Subs, Target,
+    markers::*, types::helpers,
+};
 use std::{ffi::OsString, path::Path};
 
 impl MediaInfo<'_> {
@@ -31,6 +34,11 @@ impl MediaInfo<'_> {
             })
     }
 
+    pub(super) fn build_delay(&mut self, src: &Path) -> Result<Delay> {
+        let tail = self.try_get(MIPathTail, src)?;
+        Ok(Delay::new_from_hint(tail).unwrap_or_default())
+    }
+
     pub(crate) fn build_relative_upmost(&self, src: &Path) -> Result<String> {
         src.parent()
             .ok_or_else(|| err!("Path '{}' has not parent()", src.display()))
@@ -50,6 +58,11 @@ impl MediaInfo<'_> {
         }
     }
 
+    pub(super) fn build_sub_density(&self, src: &Path) -> Result<SubDensity> {
+        let ty = SubType::new_from_path(src).ok_or_else(|| err!("Is not text subtitle file"))?;
+        Ok(Subs::new(src, ty)?.density())
+    }
+
     pub(super) fn build_target_paths(&self, src: &Path) -> Result<Vec<Target>> {
         let mut targets = Vec::<Target>::new();
 
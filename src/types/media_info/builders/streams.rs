@@ -1,5 +1,6 @@
 use super::MediaInfo;
-use crate::{CodecId, Extension, Lang, Result, Stream, StreamType, Value};
+use crate::{CodecId, DispositionType, Extension, Lang, Result, Stream, StreamType, Value};
+use enum_map::EnumMap;
 use std::path::Path;
 
 impl MediaInfo<'_> {
@@ -23,6 +24,8 @@ impl MediaInfo<'_> {
                     .unwrap_or_default();
                 let name = meta.get("title").map(|v| Value::Auto(v.to_owned()));
                 let filename = meta.get("filename").map(|s| s.to_owned());
+                let bits = stream.disposition().bits();
+                let dispositions = EnumMap::from_fn(|ty: DispositionType| bits & ty.bits() != 0);
 
                 let params = stream.parameters();
                 let codec = CodecId(params.id());
@@ -51,6 +54,9 @@ impl MediaInfo<'_> {
                     lang: Value::Auto(lang),
                     name,
                     filename,
+                    dispositions,
+                    keywords: EnumMap::default(),
+                    is_sparse: false,
                 }
             })
             .collect())
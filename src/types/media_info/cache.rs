@@ -1,5 +1,6 @@
 use crate::{
-    ArcPathBuf, CharEncoding, Duration, IsDefault, MuxError, Result, Stream, StreamsOrder, Target,
+    ArcPathBuf, CharEncoding, Delay, Duration, IsDefault, MuxError, Result, Stream, StreamsOrder,
+    SubDensity, Target,
 };
 use std::{collections::HashMap, ffi::OsString, mem};
 
@@ -33,9 +34,16 @@ pub struct CacheMIOfGroup {
 pub struct CacheMIOfFile {
     pub streams: CacheState<Vec<Stream>>,
     pub path_tail: CacheState<String>,
+
+    /// A delay hint from [`Self::path_tail`] (e.g. `DELAY -42ms`).
+    pub delay: CacheState<Delay>,
+
     pub relative_upmost: CacheState<String>,
     pub sub_char_encoding: CacheState<CharEncoding>,
 
+    /// A density of a text subtitle file.
+    pub sub_density: CacheState<SubDensity>,
+
     /// Targets from file path and parent path, existed in [`Config::targets`](
     /// crate::Config::targets).
     pub target_paths: CacheState<Vec<Target>>,
@@ -14,11 +14,14 @@ impl TryFinalizeInit for MediaInfo<'_> {
             let src = src.as_path();
             self.try_finalize_init_streams_src(src)?;
             self.try_init(MIPathTail, src)?;
+            self.try_init(MIDelay, src)?;
             self.try_init(MIRelativeUpmost, src)?;
             self.try_init(MISubCharEncoding, src)?;
             self.try_init(MITargetPaths, src)?;
             self.try_init(MIPlayableDuration, src)?;
         }
+        self.mark_sparse_subs();
+        self.try_sync_external_audio()?;
 
         Ok(())
     }
@@ -30,6 +33,7 @@ impl MediaInfo<'_> {
         for src in sources.iter() {
             self.try_finalize_init_streams_src(&src)?;
         }
+        self.mark_sparse_subs();
         Ok(())
     }
 
@@ -44,6 +48,7 @@ impl MediaInfo<'_> {
             if let Some(l) = self.get_lang(src, &ts, stream) {
                 stream.lang = l;
             }
+            stream.keywords = self.stream_keywords(src, stream);
         }
 
         self.set(MIStreams, src, streams);
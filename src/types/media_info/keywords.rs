@@ -0,0 +1,32 @@
+use super::*;
+use crate::{Keyword, Stream, markers::*};
+use enum_map::EnumMap;
+
+impl MediaInfo<'_> {
+    /// Returns keywords of the stream from the first of its name, [`MIPathTail`]
+    /// and [`MIRelativeUpmost`] that has any.
+    ///
+    /// So the `Full` in a stream name outweighs a `Signs/` directory.
+    pub(crate) fn stream_keywords(
+        &mut self,
+        src: &Path,
+        stream: &Stream,
+    ) -> EnumMap<Keyword, bool> {
+        let cfg = self.cfg;
+        let parse = |opt_s: Option<&String>| {
+            let mut kws = opt_s.map(|s| cfg.keywords.detect(s)).unwrap_or_default();
+            kws.iter_mut()
+                .for_each(|(kw, v)| *v = *v && kw.is_for(stream.ty));
+            kws.values().any(|v| *v).then_some(kws)
+        };
+
+        if !Keyword::iter().any(|kw| kw.is_for(stream.ty)) {
+            return EnumMap::default();
+        }
+
+        parse(stream.name.as_ref().map(|v| &**v))
+            .or_else(|| parse(self.get(MIPathTail, src)))
+            .or_else(|| parse(self.get(MIRelativeUpmost, src)))
+            .unwrap_or_default()
+    }
+}
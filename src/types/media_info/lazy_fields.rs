@@ -2,7 +2,8 @@ use super::MediaInfo;
 use crate::{
     ArcPathBuf, CacheMIOfFile,
     CacheState::{self, Cached, Failed, NotCached},
-    CharEncoding, Duration, LazyField, LazyPathField, Result, Stream, StreamsOrder, Target,
+    CharEncoding, Delay, Duration, LazyField, LazyPathField, Result, Stream, StreamsOrder,
+    SubDensity, Target,
 };
 use std::{ffi::OsString, mem, path::Path};
 
@@ -432,9 +433,11 @@ lazy_fields!(
 lazy_path_fields!(
     streams, Vec<Stream>, build_streams => MIStreams;
     path_tail, String, build_path_tail => MIPathTail;
+    delay, Delay, build_delay => MIDelay;
     relative_upmost, String, build_relative_upmost => MIRelativeUpmost;
 
     sub_char_encoding, CharEncoding, build_sub_char_encoding => MISubCharEncoding;
+    sub_density, SubDensity, build_sub_density => MISubDensity;
     target_paths, Vec<Target>, build_target_paths => MITargetPaths;
 
     audio_duration, Duration, build_audio_duration => MIAudioDuration;
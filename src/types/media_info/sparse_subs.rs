@@ -0,0 +1,77 @@
+use super::*;
+use crate::{Keyword, SubDensity, SubType, markers::*};
+
+impl MediaInfo<'_> {
+    /// Marks standalone text subs that are sparse against other subs and the video.
+    ///
+    /// Other subs are standalone ones and text subs embedded in files with video.
+    ///
+    /// Subs with any keyword are skipped, a keyword outweighs the density.
+    pub(crate) fn mark_sparse_subs(&mut self) {
+        if !*self.cfg.auto_flags.forceds {
+            return;
+        }
+
+        let mut sources: Vec<ArcPathBuf> = self.cache.of_files.keys().cloned().collect();
+        sources.sort(); // Same base as of StreamsOrder
+
+        let has_video = |mi: &Self, src: &ArcPathBuf| {
+            mi.immut(MIStreams, src)
+                .is_some_and(|xs| xs.iter().any(|s| s.ty.is_video()))
+        };
+        let text_subs = |mi: &Self, src: &ArcPathBuf| -> Vec<usize> {
+            mi.immut(MIStreams, src).map_or(Vec::new(), |xs| {
+                xs.iter()
+                    .filter(|s| s.ty.is_sub() && SubType::new_from_codec(s.codec).is_some())
+                    .map(|s| s.i)
+                    .collect()
+            })
+        };
+        let is_candidate = |mi: &Self, src: &ArcPathBuf| {
+            mi.immut(MIStreams, src).is_some_and(|xs| {
+                xs.len() == 1 && xs[0].ty.is_sub() && !Keyword::iter().any(|kw| xs[0].keywords[kw])
+            })
+        };
+
+        let mut video = None;
+        let mut embedded: Vec<(&ArcPathBuf, usize)> = Vec::new();
+        let mut densities: Vec<(&ArcPathBuf, SubDensity)> = Vec::new();
+
+        for src in sources.iter() {
+            if has_video(self, src) {
+                if video.is_none() {
+                    video = self.get(MIVideoDuration, src).copied();
+                }
+                embedded.extend(text_subs(self, src).into_iter().map(|i| (src, i)));
+            }
+            if is_candidate(self, src) {
+                if let Some(d) = self.get(MISubDensity, src) {
+                    densities.push((src, *d));
+                }
+            }
+        }
+
+        if densities.is_empty() {
+            return;
+        }
+        let embedded: Vec<SubDensity> = embedded
+            .into_iter()
+            .filter_map(|(src, i)| SubDensity::try_from_stream(src, i).ok())
+            .collect();
+
+        for (i, (src, d)) in densities.iter().enumerate() {
+            let others = densities
+                .iter()
+                .enumerate()
+                .filter(|(j, _)| *j != i)
+                .map(|(_, (_, d))| *d)
+                .chain(embedded.iter().copied());
+
+            if d.is_sparse(others, video) {
+                if let Some(s) = self.get_mut(MIStreams, src).and_then(|xs| xs.first_mut()) {
+                    s.is_sparse = true;
+                }
+            }
+        }
+    }
+}
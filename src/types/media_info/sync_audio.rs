@@ -0,0 +1,102 @@
+use super::MediaInfo;
+use crate::{
+    ArcPathBuf, AudioSync, Delay, IsDefault, Result, markers::*, types::audio_sync::try_decode_mono,
+};
+use log::{info, warn};
+
+impl MediaInfo<'_> {
+    /// Detects delays of external audio tracks by cross-correlation with the audio
+    /// of the video source.
+    ///
+    /// Delays from file names take precedence if auto delays are on, and offsets of low
+    /// confidence aren't applied.
+    pub(crate) fn try_sync_external_audio(&mut self) -> Result<()> {
+        if !self.cfg.sync_audio {
+            return Ok(());
+        }
+
+        let mut sources: Vec<ArcPathBuf> = self.cache.of_files.keys().cloned().collect();
+        sources.sort(); // Same base as of StreamsOrder
+
+        // Without auto delays, hints from file names aren't applied, only detected delays.
+        if !*self.cfg.auto_flags.delays {
+            for src in sources.iter() {
+                self.set(MIDelay, src, Delay::default());
+            }
+        }
+
+        let first_audio = |mi: &Self, src: &ArcPathBuf, with_video: bool| {
+            let xs = mi.immut(MIStreams, src)?;
+            if xs.iter().any(|s| s.ty.is_video()) != with_video {
+                return None;
+            }
+            xs.iter().find(|s| s.ty.is_audio()).map(|s| s.i)
+        };
+
+        let (base_src, base_i) = some_or!(
+            sources
+                .iter()
+                .find_map(|src| first_audio(self, src, true).map(|i| (src, i))),
+            return Ok(())
+        );
+
+        let externals: Vec<(&ArcPathBuf, usize)> = sources
+            .iter()
+            .filter(|src| self.immut(MIDelay, src).is_none_or(|d| d.is_default()))
+            .filter_map(|src| first_audio(self, src, false).map(|i| (src, i)))
+            .collect();
+
+        if externals.is_empty() {
+            return Ok(());
+        }
+
+        let base = match try_decode_mono(base_src, base_i, AudioSync::WINDOW) {
+            Ok(xs) => xs,
+            Err(e) if self.cfg.exit_on_err => return Err(e),
+            Err(e) => {
+                warn!(
+                    "Fail decode base audio '{}': {}. Skipping audio sync",
+                    base_src.display(),
+                    e
+                );
+                return Ok(());
+            }
+        };
+
+        for (src, i) in externals {
+            let sync = match try_decode_mono(src, i, AudioSync::WINDOW) {
+                Ok(xs) => AudioSync::new(&base, &xs, AudioSync::SAMPLE_RATE),
+                Err(e) if self.cfg.exit_on_err => return Err(e),
+                Err(e) => {
+                    warn!("Fail decode audio '{}': {}. Skipping", src.display(), e);
+                    continue;
+                }
+            };
+
+            let sync = some_or!(sync, {
+                warn!("Not found audio sync of '{}'. Skipping", src.display());
+                continue;
+            });
+
+            if sync.confidence < AudioSync::MIN_CONFIDENCE {
+                warn!(
+                    "Low confidence {:.2} of audio delay {}ms for '{}'. Skipping",
+                    sync.confidence,
+                    sync.delay,
+                    src.display()
+                );
+                continue;
+            }
+
+            info!(
+                "Detected audio delay {}ms (confidence {:.2}) for '{}'",
+                sync.delay,
+                sync.confidence,
+                src.display()
+            );
+            self.set(MIDelay, src, sync.delay);
+        }
+
+        Ok(())
+    }
+}
@@ -2,7 +2,7 @@ mod get;
 mod new;
 mod to_json_args;
 
-use crate::{IsDefault, Lang, RangeUsize};
+use crate::{Bitrate, Delay, IsDefault, Lang, RangeUsize};
 use std::{
     collections::HashMap,
     fmt::{Debug, Display},
@@ -16,6 +16,18 @@ pub struct NameMetadata(pub Metadata<String>);
 #[derive(Clone, Debug, Default, PartialEq, IsDefault)]
 pub struct LangMetadata(pub Metadata<Lang>);
 
+/// An encoder codec configuration.
+#[derive(Clone, Debug, Default, PartialEq, IsDefault)]
+pub struct CodecMetadata(pub Metadata<String>);
+
+/// An encoder bit rate configuration.
+#[derive(Clone, Debug, Default, PartialEq, IsDefault)]
+pub struct BitrateMetadata(pub Metadata<Bitrate>);
+
+/// A stream delay configuration.
+#[derive(Clone, Debug, Default, PartialEq, IsDefault)]
+pub struct DelayMetadata(pub Metadata<Delay>);
+
 /// A metadata configuration.
 #[derive(Clone, Debug, Default, PartialEq, IsDefault)]
 pub struct Metadata<T>
@@ -30,3 +42,6 @@ where
 
 deref_singleton_tuple_struct!(NameMetadata, Metadata<String>);
 deref_singleton_tuple_struct!(LangMetadata, Metadata<Lang>);
+deref_singleton_tuple_struct!(CodecMetadata, Metadata<String>);
+deref_singleton_tuple_struct!(BitrateMetadata, Metadata<Bitrate>);
+deref_singleton_tuple_struct!(DelayMetadata, Metadata<Delay>);
@@ -70,3 +70,6 @@ macro_rules! from_str_impl {
 
 from_str_impl!(NameMetadata, String);
 from_str_impl!(LangMetadata, Lang);
+from_str_impl!(CodecMetadata, String);
+from_str_impl!(BitrateMetadata, Bitrate);
+from_str_impl!(DelayMetadata, Delay);
@@ -15,3 +15,6 @@ macro_rules! to_json_args_impl {
 
 to_json_args_impl!(NameMetadata, Names);
 to_json_args_impl!(LangMetadata, Langs);
+to_json_args_impl!(CodecMetadata, Codecs);
+to_json_args_impl!(BitrateMetadata, Bitrates);
+to_json_args_impl!(DelayMetadata, Delays);
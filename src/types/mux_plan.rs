@@ -0,0 +1,167 @@
+use crate::{Bitrate, CodecId, Delay, DispositionType, Lang, Result};
+use enum_map::EnumMap;
+use serde_json::{Value as Json, json};
+use std::{
+    fs::File,
+    io::{BufReader, BufWriter},
+    path::{Path, PathBuf},
+};
+
+/// A resolved mux plan of all media groups.
+///
+/// Stored as JSON, so it can be reviewed and edited before replaying.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct MuxPlan(pub Vec<MuxPlanGroup>);
+
+/// A resolved mux plan of one output file.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct MuxPlanGroup {
+    pub output: PathBuf,
+    pub tracks: Vec<MuxPlanTrack>,
+}
+
+/// A resolved output track.
+#[derive(Clone, Debug, PartialEq)]
+pub struct MuxPlanTrack {
+    /// A source file of the track.
+    pub src: PathBuf,
+
+    /// A stream index in the source file.
+    pub i_stream: usize,
+
+    /// A codec to reencode the stream into.
+    pub reencode: Option<CodecId>,
+
+    pub name: Option<String>,
+    pub lang: Lang,
+    pub dispositions: EnumMap<DispositionType, bool>,
+
+    /// A resolved delay, including a detected one.
+    pub delay: Delay,
+
+    /// An encoder or codec name to transcode the stream with.
+    pub codec: Option<String>,
+
+    pub bitrate: Option<Bitrate>,
+}
+
+deref_singleton_tuple_struct!(MuxPlan, Vec<MuxPlanGroup>);
+
+impl MuxPlan {
+    /// Tries read a plan from the JSON file.
+    pub fn try_read(json: &Path) -> Result<MuxPlan> {
+        let reader = BufReader::new(File::open(json)?);
+        let val: Json = serde_json::from_reader(reader)?;
+
+        let groups = val
+            .as_array()
+            .ok_or_else(|| err!("Mux plan must be an array of outputs"))?;
+
+        groups
+            .iter()
+            .map(MuxPlanGroup::try_from_json)
+            .collect::<Result<Vec<_>>>()
+            .map(MuxPlan)
+            .map_err(|e| err!("Invalid mux plan '{}': {}", json.display(), e))
+    }
+
+    /// Tries write the plan to the JSON file.
+    pub fn try_write(&self, json: &Path) -> Result<()> {
+        let val = Json::Array(self.iter().map(MuxPlanGroup::to_json).collect());
+        let writer = BufWriter::new(File::create(json)?);
+        serde_json::to_writer_pretty(writer, &val)?;
+        Ok(())
+    }
+}
+
+impl MuxPlanGroup {
+    fn to_json(&self) -> Json {
+        json!({
+            "output": self.output.to_string_lossy(),
+            "tracks": self.tracks.iter().map(MuxPlanTrack::to_json).collect::<Vec<_>>(),
+        })
+    }
+
+    fn try_from_json(val: &Json) -> Result<MuxPlanGroup> {
+        let output = val
+            .get("output")
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| err!("Not found 'output'"))?;
+
+        let tracks = val
+            .get("tracks")
+            .and_then(|v| v.as_array())
+            .ok_or_else(|| err!("Not found 'tracks' of '{}'", output))?
+            .iter()
+            .map(MuxPlanTrack::try_from_json)
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok(MuxPlanGroup {
+            output: output.into(),
+            tracks,
+        })
+    }
+}
+
+impl MuxPlanTrack {
+    fn to_json(&self) -> Json {
+        let mut val = json!({
+            "source": self.src.to_string_lossy(),
+            "stream": self.i_stream,
+            "reencode": self.reencode.map(|c| c.name()),
+            "name": self.name,
+            "lang": self.lang.to_string(),
+            "delay": *self.delay,
+            "codec": self.codec,
+            "bitrate": self.bitrate.map(|b| b.to_string()),
+        });
+        if let Some(map) = val.as_object_mut() {
+            for (ty, v) in self.dispositions {
+                map.insert(ty.as_ref().to_owned(), Json::Bool(v));
+            }
+        }
+        val
+    }
+
+    fn try_from_json(val: &Json) -> Result<MuxPlanTrack> {
+        let src = val
+            .get("source")
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| err!("Not found track 'source'"))?;
+        let i_stream = val
+            .get("stream")
+            .and_then(|v| v.as_u64())
+            .ok_or_else(|| err!("Not found 'stream' of track '{}'", src))?;
+
+        let get_str = |key: &str| val.get(key).and_then(|v| v.as_str());
+        let get_bool = |key: &str| val.get(key).and_then(|v| v.as_bool()).unwrap_or(false);
+
+        let reencode = match get_str("reencode") {
+            Some(s) => Some(CodecId::from_name(s).ok_or_else(|| err!("Unknown codec '{}'", s))?),
+            None => None,
+        };
+        let delay = match val.get("delay") {
+            Some(Json::Null) | None => Delay::default(),
+            Some(v) => Delay(
+                v.as_i64()
+                    .ok_or_else(|| err!("Invalid 'delay' of track '{}'", src))?,
+            ),
+        };
+        let bitrate = match get_str("bitrate") {
+            Some(s) => Some(s.parse::<Bitrate>()?),
+            None => None,
+        };
+
+        Ok(MuxPlanTrack {
+            src: src.into(),
+            i_stream: i_stream as usize,
+            reencode,
+            name: get_str("name").map(String::from),
+            lang: get_str("lang").map(Lang::new).unwrap_or_default(),
+            dispositions: EnumMap::from_fn(|ty: DispositionType| get_bool(ty.as_ref())),
+            delay,
+            codec: get_str("codec").map(String::from),
+            bitrate,
+        })
+    }
+}
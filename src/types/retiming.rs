@@ -1,7 +1,9 @@
 mod audio;
+mod bitmap_subs;
+pub(crate) mod editions;
 mod new;
 pub(crate) mod options;
-mod subs;
+pub(crate) mod subs;
 mod video;
 
 use crate::ffmpeg::{
@@ -58,13 +60,46 @@ impl Retiming<'_, '_> {
         let ty = item.ty;
 
         match ty {
-            StreamType::Video => self.try_video(src, i_stream),
+            StreamType::Video => self.try_video(i, src, i_stream),
             StreamType::Audio => self.try_audio(i, src, i_stream),
             StreamType::Sub => self.try_sub(i, src, i_stream),
             _ => Err(err!("Unsupported stream {} {:?}", i_stream, ty)),
         }
     }
 
+    /// Returns `(i_chp, start, end)` to split a not base source by: the chapters of
+    /// the parts, shifted by the offsets of the base video parts.
+    fn external_targets(&self) -> Vec<(usize, Duration, Duration)> {
+        let mut targets = Vec::with_capacity(self.chapters.len());
+
+        for p in self.parts.iter() {
+            let uid = &self.chapters[p.i_start_chp].uid;
+            for i_chp in p.i_start_chp..=p.i_end_chp {
+                let chp = &self.chapters[i_chp];
+                if uid != &chp.uid {
+                    continue;
+                }
+
+                let chp_nonuid = self.chapters_nonuid(i_chp);
+                let start = chp.start.as_secs_f64() + p.start_offset + chp_nonuid;
+                let end_offset = if i_chp == p.i_end_chp {
+                    p.end_offset
+                } else {
+                    p.start_offset
+                };
+                let end = chp.end.as_secs_f64() + end_offset + chp_nonuid;
+
+                targets.push((
+                    i_chp,
+                    Duration::from_secs_f64(start.max(0.0)),
+                    Duration::from_secs_f64(end.max(0.0)),
+                ));
+            }
+        }
+
+        targets
+    }
+
     fn chapters_nonuid(&self, i_chp: usize) -> f64 {
         let uid = &self.chapters[i_chp].uid;
         self.chapters[..i_chp]
@@ -149,3 +184,61 @@ Try --no-linked or --parts [!]n[,m] to fix",
     octx.write_trailer()?;
     Ok(())
 }
+
+/// Returns a copy of codec headers kept out of band.
+fn extradata(data: *const u8, size: i32) -> Vec<u8> {
+    if data.is_null() || size <= 0 {
+        Vec::new()
+    } else {
+        unsafe { std::slice::from_raw_parts(data, size as usize) }.to_vec()
+    }
+}
+
+fn len(start: Duration, end: Duration) -> Duration {
+    Duration(end.saturating_sub(*start))
+}
+
+fn duration_to_ts(dur: Duration, tb: Rational) -> i64 {
+    let tb = tb.0 as f64 / tb.1 as f64;
+    (dur.as_secs_f64() / tb).round() as i64
+}
+
+// Unlike try_concat, each split is offset by the expected length of the previous ones,
+// so gaps and trimmed tails of the splits don't shift the next ones.
+fn try_concat_by_len(splits: &Vec<(PathBuf, Duration)>, dest: &Path) -> Result<()> {
+    let mut icontexts = Vec::with_capacity(splits.len());
+    for (p, _) in splits {
+        icontexts.push(format::input(p)?);
+    }
+    let ictx = icontexts
+        .first()
+        .ok_or_else(|| err!("Not found any retimed part"))?;
+
+    let mut octx = format::output(dest)?;
+    let (_, ost_time_base, ost_index) = write_stream_copy_header(ictx, 0, &mut octx)?;
+
+    let mut offset = Duration::default();
+
+    for (ictx, (_, len)) in icontexts.iter_mut().zip(splits) {
+        let offset_ts = duration_to_ts(offset, ost_time_base);
+
+        for (ist, mut packet) in ictx.packets() {
+            let ist_time_base = ist.time_base();
+            let rescale = |ts: i64| ts.rescale(ist_time_base, ost_time_base);
+
+            if let Some(pts) = packet.pts() {
+                let ts = rescale(pts) + offset_ts;
+                packet.set_pts(Some(ts));
+                packet.set_dts(Some(ts));
+            }
+            packet.set_duration(rescale(packet.duration()));
+            packet.set_stream(ost_index);
+            packet.write_interleaved(&mut octx)?;
+        }
+
+        offset = offset + *len;
+    }
+
+    octx.write_trailer()?;
+    Ok(())
+}
@@ -1,12 +1,25 @@
-use super::{RetimedStream, Retiming, try_concat, write_stream_copy_header};
+use super::{
+    RetimedStream, Retiming, duration_to_ts, extradata, len, try_concat, try_concat_by_len,
+    write_stream_copy_header,
+};
 use crate::{
     Duration, Result,
-    ffmpeg::{Rescale, format},
+    ffmpeg::{
+        ChannelLayout, Packet, Rational, Rescale, codec, encoder, ffi, format, frame, packet::Mut,
+    },
+};
+use log::warn;
+use std::{
+    collections::VecDeque,
+    path::{Path, PathBuf},
 };
-use std::path::{Path, PathBuf};
 
 impl Retiming<'_, '_> {
     pub(crate) fn try_audio(&self, i: usize, src: &Path, i_stream: usize) -> Result<RetimedStream> {
+        if self.media_info.cfg.retiming_options.exact_audio {
+            return self.try_exact_audio(i, src, i_stream);
+        }
+
         let splits = if src == **self.base {
             self.try_base_audio(i_stream)
         } else {
@@ -47,43 +60,363 @@ impl Retiming<'_, '_> {
     }
 
     fn try_external_audio(&self, i: usize, src: &Path, i_stream: usize) -> Result<Vec<PathBuf>> {
-        let mut segments: Vec<PathBuf> = Vec::with_capacity(self.chapters.len());
         let mut len_offset = 0f64;
 
-        for p in self.parts.iter() {
-            let uid = &self.chapters[p.i_start_chp].uid;
-            for i_chp in p.i_start_chp..=p.i_end_chp {
-                let chp = &self.chapters[i_chp];
-                if uid != &chp.uid {
-                    continue;
-                }
+        self.external_targets()
+            .into_iter()
+            .map(|(i_chp, start, end)| {
+                let dest = self
+                    .temp_dir
+                    .join(format!("{}-aud-{}-{}.mka", self.job, i, i_chp));
+
+                let start_f64 = start.as_secs_f64();
+                let start = if start_f64 - len_offset > 0.0 {
+                    Duration::from_secs_f64(start_f64 - len_offset)
+                } else {
+                    start
+                };
+
+                len_offset = try_split(src, i_stream, &dest, start, end)?;
+                Ok(dest)
+            })
+            .collect()
+    }
+}
+
+impl Retiming<'_, '_> {
+    fn try_exact_audio(&self, i: usize, src: &Path, i_stream: usize) -> Result<RetimedStream> {
+        let mut targets: Vec<(&Path, PathBuf, Duration, Duration)> =
+            Vec::with_capacity(self.chapters.len());
+
+        if src == **self.base {
+            for (i_part, p) in self.parts.iter().enumerate() {
+                let dest = self
+                    .temp_dir
+                    .join(format!("{}-aud-base-{}-{}.mka", self.job, i_stream, i_part));
+                targets.push((&p.src, dest, p.start, p.end));
+            }
+        } else {
+            for (i_chp, start, end) in self.external_targets() {
                 let dest = self
                     .temp_dir
                     .join(format!("{}-aud-{}-{}.mka", self.job, i, i_chp));
+                targets.push((src, dest, start, end));
+            }
+        }
 
-                let chp_nonuid = self.chapters_nonuid(i_chp);
+        let splits = targets
+            .into_iter()
+            .map(|(src, dest, start, end)| {
+                try_split_exact(src, i_stream, &dest, start, end)?;
+                Ok((dest, len(start, end)))
+            })
+            .collect::<Result<Vec<_>>>()?;
 
-                let mut start = chp.start.as_secs_f64() + p.start_offset + chp_nonuid;
-                if start - len_offset > 0.0 {
-                    start -= len_offset;
+        let dest = self.temp_dir.join(format!("{}-aud-{}.mka", self.job, i));
+        try_concat_by_len(&splits, &dest)?;
+
+        Ok(RetimedStream {
+            src: Some(dest),
+            i_stream: 0,
+        })
+    }
+}
+
+// Keeps timestamps relative to the split start and trims the tail of the last frame
+// by discard padding, so the split lasts exactly trg_end - trg_start.
+// Matroska has no start counterpart of DiscardPadding, so samples of the frame
+// straddling trg_start are re-encoded from the split start instead. If it fails,
+// the frame is dropped, leaving a gap of up to one frame (e.g. 21 ms of AAC).
+fn try_split_exact(
+    src: &Path,
+    i_stream: usize,
+    dest: &Path,
+    trg_start: Duration,
+    trg_end: Duration,
+) -> Result<()> {
+    let mut ictx = format::input(&src)?;
+    let mut octx = format::output(&dest)?;
+
+    // Packets before the straddling one warm up the decoder.
+    const DECODE_PACKETS: usize = 4;
+
+    let params = ictx
+        .stream(i_stream)
+        .ok_or_else(|| err!("Not found stream"))?
+        .parameters();
+    let sample_rate = codec::Context::from_parameters(params.clone())?
+        .decoder()
+        .audio()?
+        .rate() as i32;
+    let (ist_time_base, ost_time_base, ost_index) =
+        write_stream_copy_header(&ictx, i_stream, &mut octx)?;
+
+    let start_ts = duration_to_ts(trg_start, ost_time_base);
+    let end_ts = duration_to_ts(trg_end, ost_time_base);
+    let rescale = |ts: i64| ts.rescale(ist_time_base, ost_time_base);
+
+    let mut before: VecDeque<Packet> = VecDeque::with_capacity(DECODE_PACKETS);
+    let mut is_first = true;
+
+    for (ist, mut packet) in ictx.packets() {
+        if ist.index() != i_stream {
+            continue;
+        }
+        let pts = rescale(some_or!(packet.pts(), continue));
+
+        if pts < start_ts {
+            if before.len() == DECODE_PACKETS {
+                before.pop_front();
+            }
+            before.push_back(packet);
+            continue;
+        }
+
+        let is_straddled = before.back().is_some_and(|b| {
+            b.pts()
+                .is_some_and(|ts| rescale(ts + b.duration()) > start_ts)
+        });
+        if is_first && is_straddled && sample_rate > 0 {
+            let head_end = pts.min(end_ts);
+            let ts = |ts: i64| ts.rescale(ost_time_base, ist_time_base);
+            let head = Head {
+                params: &params,
+                time_base: ist_time_base,
+                start: ts(start_ts),
+                end: ts(head_end),
+                rate: sample_rate,
+            };
+            match head.try_reencode(before.make_contiguous()) {
+                Ok(packets) => {
+                    for mut p in packets {
+                        let samples = Rational(1, sample_rate);
+                        p.set_pts(p.pts().map(|ts| ts.rescale(samples, ost_time_base)));
+                        p.set_dts(p.pts());
+                        p.set_duration(p.duration().rescale(samples, ost_time_base));
+                        p.set_stream(ost_index);
+                        p.write_interleaved(&mut octx)?;
+                    }
                 }
+                Err(e) => warn!(
+                    "Fail re-encode the start of '{}' stream {}: {}. The split starts with a gap",
+                    src.display(),
+                    i_stream,
+                    e
+                ),
+            }
+        }
+        is_first = false;
 
-                let end_offset = if i_chp == p.i_end_chp {
-                    p.end_offset
-                } else {
-                    p.start_offset
-                };
-                let end = chp.end.as_secs_f64() + end_offset + chp_nonuid;
+        if pts >= end_ts {
+            break;
+        }
+
+        let duration = rescale(packet.duration());
+        if duration > 0 && sample_rate > 0 && pts + duration > end_ts {
+            let tail = pts + duration - end_ts;
+            let samples = tail.rescale(ost_time_base, Rational(1, sample_rate));
+            set_discard_padding(&mut packet, samples as u32);
+        }
+
+        let ts = pts - start_ts;
+        packet.set_pts(Some(ts));
+        packet.set_dts(Some(ts));
+        packet.set_stream(ost_index);
+        packet.write_interleaved(&mut octx)?;
+    }
+
+    octx.write_trailer()?;
+    Ok(())
+}
+
+/// Samples from `start` to `end` (the first copied packet) to re-encode, in the time
+/// base of the source packets.
+struct Head<'a> {
+    params: &'a codec::Parameters,
+    time_base: Rational,
+    start: i64,
+    end: i64,
+    rate: i32,
+}
+
+impl Head<'_> {
+    /// Returns packets of the samples, decoded from `packets` and re-encoded by the
+    /// source codec, with timestamps and durations in samples from `start`.
+    ///
+    /// An encoder outputs a frame of samples with a delay (initial padding), so it's
+    /// fed from before `start` to put a packet boundary on it, and earlier packets
+    /// are dropped. The tail of the last packet is trimmed by discard padding.
+    fn try_reencode(&self, packets: &[Packet]) -> Result<Vec<Packet>> {
+        let mut dec = codec::Context::from_parameters(self.params.clone())?
+            .decoder()
+            .audio()?;
+        dec.set_packet_time_base(self.time_base);
+
+        let id = self.params.id();
+        let codec = encoder::find(id).ok_or_else(|| err!("Not found encoder of {:?}", id))?;
+        let layout = match dec.channel_layout() {
+            l if l.is_empty() => ChannelLayout::default(dec.channels() as i32),
+            l => l,
+        };
+
+        let mut enc = codec::Context::new_with_codec(codec).encoder().audio()?;
+        enc.set_rate(self.rate);
+        enc.set_channel_layout(layout);
+        enc.set_format(dec.format());
+        enc.set_time_base((1, self.rate));
+
+        let src = unsafe { &*self.params.as_ptr() };
+        let src_extradata = extradata(src.extradata, src.extradata_size);
+        if src.bit_rate > 0 {
+            enc.set_bit_rate(src.bit_rate as usize);
+        }
+        if !src_extradata.is_empty() {
+            enc.set_flags(codec::Flags::GLOBAL_HEADER);
+        }
+        let mut enc = enc.open_as(codec)?;
+
+        // Re-encoded packets are muxed under the source stream header.
+        let enc_extradata =
+            unsafe { extradata((*enc.as_ptr()).extradata, (*enc.as_ptr()).extradata_size) };
+        if enc_extradata != src_extradata {
+            return Err(err!("Encoder headers of {:?} don't match the source", id));
+        }
 
-                let trg_start = Duration::from_secs_f64(start);
-                let trg_end = Duration::from_secs_f64(end);
-                len_offset = try_split(src, i_stream, &dest, trg_start, trg_end)?;
+        let (frame_size, padding) = unsafe {
+            let ctx = enc.as_ptr();
+            ((*ctx).frame_size as i64, (*ctx).initial_padding as i64)
+        };
+        let prefix = match frame_size {
+            0 => 0,
+            n => (padding + n - 1) / n * n - padding,
+        };
+        let len = self.to_samples(self.end);
 
-                segments.push(dest);
+        // Samples from -prefix to len, silent where nothing is decoded.
+        let mut samples = Samples::new(dec.format(), layout.channels(), (prefix + len) as usize);
+        let mut frame = frame::Audio::empty();
+        for packet in packets {
+            dec.send_packet(packet)?;
+            while dec.receive_frame(&mut frame).is_ok() {
+                if let Some(ts) = frame.timestamp() {
+                    samples.put(self.to_samples(ts) + prefix, &frame);
+                }
+            }
+        }
+        dec.send_eof()?;
+        while dec.receive_frame(&mut frame).is_ok() {
+            if let Some(ts) = frame.timestamp() {
+                samples.put(self.to_samples(ts) + prefix, &frame);
             }
         }
 
-        Ok(segments)
+        let mut out = Vec::new();
+        let mut pos = -prefix - padding;
+        let mut receive = |enc: &mut encoder::audio::Encoder, out: &mut Vec<Packet>| {
+            let mut packet = Packet::empty();
+            while enc.receive_packet(&mut packet).is_ok() {
+                let duration = match frame_size {
+                    0 => packet.duration(),
+                    n => n,
+                };
+                if pos >= 0 && pos < len {
+                    if pos + duration > len {
+                        set_discard_padding(&mut packet, (pos + duration - len) as u32);
+                    }
+                    packet.set_pts(Some(pos));
+                    packet.set_dts(Some(pos));
+                    packet.set_duration(duration);
+                    packet.set_position(-1);
+                    out.push(packet.clone());
+                }
+                pos += duration;
+            }
+        };
+
+        let chunk = match frame_size {
+            0 => samples.len.max(1),
+            n => n as usize,
+        };
+        for i in (0..samples.len).step_by(chunk) {
+            let n = chunk.min(samples.len - i);
+            let mut frame = frame::Audio::new(dec.format(), n, layout);
+            frame.set_rate(self.rate as u32);
+            frame.set_pts(Some(i as i64 - prefix));
+            samples.get(i, &mut frame);
+            enc.send_frame(&frame)?;
+            receive(&mut enc, &mut out);
+        }
+        enc.send_eof()?;
+        receive(&mut enc, &mut out);
+
+        Ok(out)
+    }
+
+    fn to_samples(&self, ts: i64) -> i64 {
+        (ts - self.start).rescale(self.time_base, Rational(1, self.rate))
+    }
+}
+
+/// Raw audio samples by planes.
+struct Samples {
+    planes: Vec<Vec<u8>>,
+    /// Bytes of a sample in a plane.
+    unit: usize,
+    len: usize,
+}
+
+impl Samples {
+    fn new(format: format::Sample, channels: i32, len: usize) -> Samples {
+        let channels = channels.max(1) as usize;
+        let (num, unit) = match format.is_planar() {
+            true => (channels, format.bytes()),
+            false => (1, format.bytes() * channels),
+        };
+        Samples {
+            planes: vec![vec![0; len * unit]; num],
+            unit,
+            len,
+        }
+    }
+
+    /// Copies the samples of the frame starting at `pos`, within the bounds.
+    fn put(&mut self, pos: i64, frame: &frame::Audio) {
+        let from = pos.max(0) as usize;
+        let to = (pos + frame.samples() as i64).min(self.len as i64);
+        if to <= from as i64 {
+            return;
+        }
+        let skip = (from as i64 - pos) as usize * self.unit;
+        let bytes = (to as usize - from) * self.unit;
+
+        for (i, plane) in self.planes.iter_mut().enumerate().take(frame.planes()) {
+            let data = &frame.data(i)[skip..skip + bytes];
+            plane[from * self.unit..from * self.unit + bytes].copy_from_slice(data);
+        }
+    }
+
+    /// Copies samples starting at `pos` to the frame.
+    fn get(&self, pos: usize, frame: &mut frame::Audio) {
+        let bytes = frame.samples() * self.unit;
+        for (i, plane) in self.planes.iter().enumerate().take(frame.planes()) {
+            frame.data_mut(i)[..bytes].copy_from_slice(&plane[pos * self.unit..][..bytes]);
+        }
+    }
+}
+
+// Matroska muxer writes the end samples of AV_PKT_DATA_SKIP_SAMPLES as DiscardPadding.
+fn set_discard_padding(packet: &mut Packet, samples: u32) {
+    unsafe {
+        let data = ffi::av_packet_new_side_data(
+            packet.as_mut_ptr(),
+            ffi::AVPacketSideDataType::AV_PKT_DATA_SKIP_SAMPLES,
+            10,
+        );
+        if !data.is_null() {
+            let xs = std::slice::from_raw_parts_mut(data, 10);
+            xs.fill(0);
+            xs[4..8].copy_from_slice(&samples.to_le_bytes());
+        }
     }
 }
 
@@ -0,0 +1,187 @@
+use super::{
+    RetimedStream, Retiming, duration_to_ts, len, try_concat_by_len, write_stream_copy_header,
+};
+use crate::{
+    Duration, Pgs, Result,
+    ffmpeg::{Packet, Rescale, codec::Id, format},
+    markers::MIStreams,
+};
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
+};
+
+impl Retiming<'_, '_> {
+    /// Retimes a bitmap sub stream (PGS, VobSub) by packets, keeping display sets
+    /// that start within the parts and captions showing at their bounds.
+    pub(super) fn try_bitmap_sub(
+        &self,
+        i: usize,
+        src: &Path,
+        i_stream: usize,
+    ) -> Result<RetimedStream> {
+        let is_pgs = self.is_pgs(src, i_stream);
+        let splits = if src == **self.base {
+            self.try_base_bitmap_sub(i_stream, is_pgs)
+        } else {
+            self.try_external_bitmap_sub(i, src, i_stream, is_pgs)
+        }?;
+
+        let dest = self.temp_dir.join(format!("{}-sub-{}.mks", self.job, i));
+        try_concat_by_len(&splits, &dest)?;
+
+        Ok(RetimedStream {
+            src: Some(dest),
+            i_stream: 0,
+        })
+    }
+
+    fn try_base_bitmap_sub(
+        &self,
+        i_stream: usize,
+        is_pgs: bool,
+    ) -> Result<Vec<(PathBuf, Duration)>> {
+        // Parts of linked segments may come from other files; each is read once.
+        let mut streams: HashMap<&Path, Pgs> = HashMap::new();
+
+        self.parts
+            .iter()
+            .enumerate()
+            .map(|(i, p)| {
+                let src = p.src.as_path();
+                if is_pgs && !streams.contains_key(src) {
+                    streams.insert(src, Pgs::try_from_stream(src, i_stream)?);
+                }
+                let pgs = streams.get(src);
+                let dest = self
+                    .temp_dir
+                    .join(format!("{}-sub-base-{}-{}.mks", self.job, i_stream, i));
+                try_split(src, i_stream, pgs, &dest, p.start, p.end)?;
+                Ok((dest, len(p.start, p.end)))
+            })
+            .collect()
+    }
+
+    fn try_external_bitmap_sub(
+        &self,
+        i: usize,
+        src: &Path,
+        i_stream: usize,
+        is_pgs: bool,
+    ) -> Result<Vec<(PathBuf, Duration)>> {
+        let pgs = match is_pgs {
+            true => Some(Pgs::try_from_stream(src, i_stream)?),
+            false => None,
+        };
+
+        self.external_targets()
+            .into_iter()
+            .map(|(i_chp, start, end)| {
+                let dest = self
+                    .temp_dir
+                    .join(format!("{}-sub-{}-{}.mks", self.job, i, i_chp));
+                try_split(src, i_stream, pgs.as_ref(), &dest, start, end)?;
+                Ok((dest, len(start, end)))
+            })
+            .collect()
+    }
+
+    fn is_pgs(&self, src: &Path, i_stream: usize) -> bool {
+        self.media_info
+            .immut(MIStreams, src)
+            .and_then(|xs| xs.get(i_stream))
+            .is_some_and(|s| s.codec.0 == Id::HDMV_PGS_SUBTITLE)
+    }
+}
+
+// Unlike audio, timestamps are shifted by the split start, not by the first packet:
+// subs are sparse and a gap before the first display set must be kept.
+// A PGS stream is retimed by display sets of the already read `pgs`.
+fn try_split(
+    src: &Path,
+    i_stream: usize,
+    pgs: Option<&Pgs>,
+    dest: &Path,
+    trg_start: Duration,
+    trg_end: Duration,
+) -> Result<()> {
+    let mut ictx = format::input(&src)?;
+    let mut octx = format::output(&dest)?;
+
+    let (ist_time_base, ost_time_base, ost_index) =
+        write_stream_copy_header(&ictx, i_stream, &mut octx)?;
+
+    if let Some(pgs) = pgs {
+        let pgs = pgs.retimed(trg_start, trg_end);
+        for (pts, data) in pgs.packets() {
+            let ts = (pts as i64).rescale(Pgs::TIME_BASE, ost_time_base);
+            let mut packet = Packet::copy(&data);
+            packet.set_pts(Some(ts));
+            packet.set_dts(Some(ts));
+            packet.set_stream(ost_index);
+            packet.write_interleaved(&mut octx)?;
+        }
+        octx.write_trailer()?;
+        return Ok(());
+    }
+
+    let start_ts = duration_to_ts(trg_start, ost_time_base);
+    let end_ts = duration_to_ts(trg_end, ost_time_base);
+    let rescale = |ts: i64| ts.rescale(ist_time_base, ost_time_base);
+
+    // A VobSub packet carries its own display time, so one showing at the start
+    // is kept from zero for the rest of it.
+    let mut showing: Option<(i64, i64, Packet)> = None;
+
+    for (ist, packet) in ictx.packets() {
+        if ist.index() != i_stream {
+            continue;
+        }
+        let pts = rescale(some_or!(packet.pts(), continue));
+        let pts_end = pts + rescale(packet.duration());
+
+        if pts < start_ts {
+            showing = (pts_end > start_ts).then_some((pts, pts_end, packet));
+            continue;
+        }
+        if let Some((pts, pts_end, packet)) = showing.take() {
+            let ts = (pts.max(start_ts), pts_end.min(end_ts));
+            write_packet(packet, ts, start_ts, ost_index, &mut octx)?;
+        }
+        if pts >= end_ts {
+            break;
+        }
+        write_packet(
+            packet,
+            (pts, pts_end.min(end_ts)),
+            start_ts,
+            ost_index,
+            &mut octx,
+        )?;
+    }
+
+    if let Some((pts, pts_end, packet)) = showing {
+        let ts = (pts.max(start_ts), pts_end.min(end_ts));
+        write_packet(packet, ts, start_ts, ost_index, &mut octx)?;
+    }
+
+    octx.write_trailer()?;
+    Ok(())
+}
+
+/// Writes the packet showing within `(pts, pts_end)`, shifted by the split start.
+fn write_packet(
+    mut packet: Packet,
+    (pts, pts_end): (i64, i64),
+    start_ts: i64,
+    ost_index: usize,
+    octx: &mut format::context::Output,
+) -> Result<()> {
+    let ts = pts - start_ts;
+    packet.set_duration((pts_end - pts).max(0));
+    packet.set_pts(Some(ts));
+    packet.set_dts(Some(ts));
+    packet.set_stream(ost_index);
+    packet.write_interleaved(octx)?;
+    Ok(())
+}
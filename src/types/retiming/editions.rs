@@ -0,0 +1,259 @@
+use crate::{Result, RetimingEdition};
+use matroska::{Matroska, Tag, TagValue, TargetTypeValue};
+use std::{
+    fs::File,
+    io::{BufReader, Read, Seek, SeekFrom},
+    path::Path,
+};
+
+const EBML: u32 = 0x1A45DFA3;
+const SEGMENT: u32 = 0x18538067;
+const SEEK_HEAD: u32 = 0x114D9B74;
+const SEEK: u32 = 0x4DBB;
+const SEEK_ID: u32 = 0x53AB;
+const SEEK_POSITION: u32 = 0x53AC;
+const CLUSTER: u32 = 0x1F43B675;
+const CHAPTERS: u32 = 0x1043A770;
+const EDITION_ENTRY: u32 = 0x45B9;
+const EDITION_DISPLAY: u32 = 0x4520;
+const EDITION_STRING: u32 = 0x4521;
+
+/// A matroska chapter edition.
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) struct Edition {
+    pub name: Option<String>,
+    pub is_default: bool,
+    pub is_hidden: bool,
+    pub is_ordered: bool,
+    pub is_linked: bool,
+}
+
+impl Edition {
+    /// Returns editions of the matroska file in order of the file.
+    pub(crate) fn list(src: &Path, mat: &Matroska) -> Vec<Edition> {
+        let mut names = names(src, mat).into_iter();
+
+        mat.chapters
+            .iter()
+            .map(|ed| Edition {
+                name: names.next().flatten(),
+                is_default: ed.default,
+                is_hidden: ed.hidden,
+                is_ordered: ed.ordered,
+                is_linked: ed.chapters.iter().any(|c| c.segment_uid.is_some()),
+            })
+            .collect()
+    }
+}
+
+impl RetimingEdition {
+    /// Returns an index of the matching edition of the matroska file.
+    pub(crate) fn find(&self, src: &Path, mat: &Matroska) -> Option<usize> {
+        match self {
+            Self::Num(n) => (*n < mat.chapters.len()).then_some(*n),
+            Self::Name(name) => {
+                let name = name.to_lowercase();
+                names(src, mat)
+                    .iter()
+                    .position(|n| n.as_ref().is_some_and(|n| n.to_lowercase() == name))
+            }
+        }
+    }
+}
+
+/// Returns names of chapter editions: `EditionDisplay` ones, which the matroska crate
+/// skips, or else `TITLE` tags of the editions, as mkvmerge names them.
+fn names(src: &Path, mat: &Matroska) -> Vec<Option<String>> {
+    let displays = try_display_names(src).unwrap_or_default();
+
+    mat.chapters
+        .iter()
+        .enumerate()
+        .map(|(i, ed)| {
+            displays
+                .get(i)
+                .cloned()
+                .flatten()
+                .or_else(|| tag_title(&mat.tags, ed.uid?))
+        })
+        .collect()
+}
+
+/// Returns the `TITLE` of the edition level (50) tag of the edition.
+fn tag_title(tags: &[Tag], edition_uid: u64) -> Option<String> {
+    tags.iter()
+        .filter(|tag| {
+            tag.targets.as_ref().is_some_and(|t| {
+                // The matroska crate names the level 50 `Episode`.
+                t.edition_uids.contains(&edition_uid)
+                    && t.target_type_value
+                        .is_none_or(|v| v == TargetTypeValue::Episode)
+            })
+        })
+        .flat_map(|tag| tag.simple.iter())
+        .filter(|simple| simple.name.eq_ignore_ascii_case("TITLE"))
+        .find_map(|simple| match &simple.value {
+            Some(TagValue::String(s)) => Some(s.clone()),
+            _ => None,
+        })
+}
+
+/// Reads names of chapter editions (`EditionDisplay`).
+fn try_display_names(src: &Path) -> Result<Vec<Option<String>>> {
+    let mut r = BufReader::new(File::open(src)?);
+    let file_len = r.get_ref().metadata()?.len();
+
+    let (id, size) = read_header(&mut r)?;
+    if id != EBML {
+        return Err(err!("Not found EBML header"));
+    }
+    r.seek_relative(size.ok_or_else(|| err!("Unknown EBML header size"))? as i64)?;
+
+    let (id, size) = read_header(&mut r)?;
+    if id != SEGMENT {
+        return Err(err!("Not found matroska segment"));
+    }
+    let segment_start = r.stream_position()?;
+    let segment_end = size.map(|s| segment_start + s);
+    let mut chapters_pos = None;
+
+    while let Ok((id, size)) = read_header(&mut r) {
+        let start = r.stream_position()?;
+        match id {
+            CHAPTERS => return Ok(edition_names(&read_data(&mut r, size, file_len)?)),
+            SEEK_HEAD => {
+                if let Some(pos) = chapters_seek_position(&read_data(&mut r, size, file_len)?) {
+                    chapters_pos = Some(segment_start + pos);
+                }
+            }
+            CLUSTER => break,
+            _ => {}
+        }
+
+        let end = start + size.ok_or_else(|| err!("Unknown element size"))?;
+        if segment_end.is_some_and(|e| end >= e) {
+            break;
+        }
+        r.seek(SeekFrom::Start(end))?;
+    }
+
+    if let Some(pos) = chapters_pos {
+        r.seek(SeekFrom::Start(pos))?;
+        if let (CHAPTERS, size) = read_header(&mut r)? {
+            return Ok(edition_names(&read_data(&mut r, size, file_len)?));
+        }
+    }
+
+    Ok(Vec::new())
+}
+
+fn read_header(r: &mut impl Read) -> Result<(u32, Option<u64>)> {
+    let id = read_vint(r)?.raw as u32;
+    let size = read_vint(r)?;
+    let size = (!size.is_unknown()).then_some(size.value);
+
+    Ok((id, size))
+}
+
+fn read_data(r: &mut (impl Read + Seek), size: Option<u64>, file_len: u64) -> Result<Vec<u8>> {
+    let size = size.ok_or_else(|| err!("Unknown element size"))?;
+    // A corrupted size mustn't allocate more than the file has.
+    if size > file_len.saturating_sub(r.stream_position()?) {
+        return Err(err!("Element size exceeds the file"));
+    }
+    let mut data = vec![0; size as usize];
+    r.read_exact(&mut data)?;
+    Ok(data)
+}
+
+struct Vint {
+    /// With the length marker, as element ids are stored.
+    raw: u64,
+    /// Without the length marker.
+    value: u64,
+    len: usize,
+}
+
+impl Vint {
+    fn is_unknown(&self) -> bool {
+        self.value == (1u64 << (7 * self.len)) - 1
+    }
+}
+
+fn read_vint(r: &mut impl Read) -> Result<Vint> {
+    let mut byte = [0u8];
+    r.read_exact(&mut byte)?;
+    let mut bytes = [0u8; 8];
+    bytes[0] = byte[0];
+
+    let len = byte[0].leading_zeros() as usize + 1;
+    if len > 8 {
+        return Err(err!("Invalid EBML variable size integer"));
+    }
+    r.read_exact(&mut bytes[1..len])?;
+
+    Ok(vint_from(&bytes[..len]))
+}
+
+fn vint_from(bytes: &[u8]) -> Vint {
+    let len = bytes.len();
+    let raw = bytes.iter().fold(0u64, |acc, b| acc << 8 | *b as u64);
+    Vint {
+        raw,
+        value: raw & ((1u64 << (7 * len)) - 1),
+        len,
+    }
+}
+
+/// Returns child elements of the element data; stops on malformed data.
+fn children(mut data: &[u8]) -> Vec<(u32, &[u8])> {
+    let mut xs = Vec::new();
+
+    while let Some((id, rest)) = slice_vint(data) {
+        let (size, rest) = some_or!(slice_vint(rest), break);
+        let size = size.value as usize;
+        if size > rest.len() {
+            break;
+        }
+        xs.push((id.raw as u32, &rest[..size]));
+        data = &rest[size..];
+    }
+
+    xs
+}
+
+fn slice_vint(data: &[u8]) -> Option<(Vint, &[u8])> {
+    let len = data.first()?.leading_zeros() as usize + 1;
+    if len > 8 || data.len() < len {
+        return None;
+    }
+    Some((vint_from(&data[..len]), &data[len..]))
+}
+
+fn chapters_seek_position(seek_head: &[u8]) -> Option<u64> {
+    children(seek_head)
+        .into_iter()
+        .filter(|(id, _)| *id == SEEK)
+        .find_map(|(_, seek)| {
+            let xs = children(seek);
+            let id = xs.iter().find(|(id, _)| *id == SEEK_ID)?.1;
+            let pos = xs.iter().find(|(id, _)| *id == SEEK_POSITION)?.1;
+            let as_u64 = |bytes: &[u8]| bytes.iter().fold(0u64, |acc, b| acc << 8 | *b as u64);
+            (as_u64(id) == CHAPTERS as u64).then(|| as_u64(pos))
+        })
+}
+
+fn edition_names(chapters: &[u8]) -> Vec<Option<String>> {
+    children(chapters)
+        .into_iter()
+        .filter(|(id, _)| *id == EDITION_ENTRY)
+        .map(|(_, entry)| {
+            children(entry)
+                .into_iter()
+                .filter(|(id, _)| *id == EDITION_DISPLAY)
+                .flat_map(|(_, display)| children(display))
+                .find(|(id, _)| *id == EDITION_STRING)
+                .map(|(_, s)| String::from_utf8_lossy(s).trim_end_matches('\0').to_owned())
+        })
+        .collect()
+}
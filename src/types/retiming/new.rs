@@ -1,5 +1,6 @@
 mod cache;
 mod external_segments;
+mod segments_index;
 
 use super::{Retiming, RetimingChapter, RetimingPart};
 use crate::{
@@ -21,21 +22,35 @@ impl Retiming<'_, '_> {
         let (base, i_base_stream, i_matroska_chapters) = try_base(mi, order, &mut cache)?;
         let base_dir = base.parent().unwrap_or(&mi.cfg.input.dir);
 
-        let cs = try_chapters(mi, &cache, &base, i_matroska_chapters, base_dir)?;
+        let is_cut = !mi.cfg.retiming_options.cut.is_default();
+        let cs = if is_cut {
+            try_cut_chapters(mi, &base)?
+        } else {
+            try_chapters(mi, &cache, &base, i_matroska_chapters, base_dir)?
+        };
         let len = cs.len();
 
         let mut parts: Vec<RetimingPart> = Vec::with_capacity(len);
         let mut i = 0usize;
 
         while i < len {
-            let src = match save_then_src(mi, &cache, &base, &base_dir, &cs[i]) {
-                Some(src) => src,
-                None => {
-                    i += 1;
-                    continue;
+            let src = if is_cut {
+                base.clone()
+            } else {
+                match save_then_src(mi, &cache, &base, &base_dir, &cs[i]) {
+                    Some(src) => src,
+                    None => {
+                        i += 1;
+                        continue;
+                    }
                 }
             };
-            let i_end_chp = i_end_chp(&mi.cfg, &cs, i);
+            // cut ranges are already merged, the gaps between them are dropped
+            let i_end_chp = if is_cut {
+                i
+            } else {
+                i_end_chp(&mi.cfg, &cs, i)
+            };
 
             let (start, start_offset, end, end_offset) =
                 try_times(mi, &src, i_base_stream, cs[i].start, cs[i_end_chp].end)?;
@@ -82,6 +97,12 @@ fn try_times(
     const ACCEPT_VIDEO_OFFSET: f64 = 10.0; // seconds
 
     let duration = *mi.try_get(MIVideoDuration, src)?;
+
+    // Smart cut splits exactly, so targets aren't moved to keyframes or file bounds.
+    if mi.cfg.retiming_options.smart_cut {
+        return Ok((start, 0.0, end.min(duration), 0.0));
+    }
+
     let zero_start_offset = start.as_secs_f64();
     let end_offset = duration.as_secs_f64() - end.as_secs_f64();
 
@@ -272,17 +293,69 @@ fn try_chapters(
     Ok(chapters)
 }
 
+fn try_cut_chapters(mi: &mut MediaInfo, base: &Path) -> Result<Vec<RetimingChapter>> {
+    let duration = *mi.try_get(MIVideoDuration, base)?;
+
+    let chapters: Vec<_> = mi
+        .cfg
+        .retiming_options
+        .cut
+        .save_ranges(duration)
+        .into_iter()
+        .map(|(start, end)| RetimingChapter {
+            start,
+            end,
+            uid: None,
+            title: None,
+        })
+        .collect();
+
+    if chapters.is_empty() {
+        return Err(err!("Not found any cut range within the video duration"));
+    }
+
+    Ok(chapters)
+}
+
+fn is_flattened(ed: &matroska::ChapterEdition) -> bool {
+    ed.ordered || ed.chapters.iter().any(|c| c.segment_uid.is_some())
+}
+
 fn try_base(
     mi: &mut MediaInfo,
     order: &StreamsOrder,
     cache: &mut CacheMatroska,
 ) -> Result<(ArcPathBuf, usize, Option<usize>)> {
+    let opts = &mi.cfg.retiming_options;
+
     for m in order.iter() {
         if !m.ty.is_video() {
             break;
         }
         let mat = some_or!(cache.get(&m.key), continue);
 
+        if let Some(ed) = opts.edition.as_ref() {
+            match ed.find(&m.key, mat) {
+                Some(i) if is_flattened(&mat.chapters[i]) || !opts.parts.is_default() => {
+                    return Ok((m.key.clone(), m.i_stream, Some(i)));
+                }
+                // chapters of not ordered edition are only markers, nothing to flatten
+                Some(_) => {
+                    warn!(
+                        "Edition '{}' in '{}' is neither ordered nor linked. Nothing to retime by",
+                        ed,
+                        m.key.display()
+                    );
+                    break;
+                }
+                None => warn!(
+                    "Not found edition '{}' in '{}'. Using the first linked",
+                    ed,
+                    m.key.display()
+                ),
+            }
+        }
+
         if let Some(i) = mat.chapters.iter().enumerate().find_map(|(i, chp)| {
             chp.chapters
                 .iter()
@@ -293,7 +366,7 @@ fn try_base(
         }
     }
 
-    if mi.cfg.retiming_options.parts.is_default() {
+    if opts.parts.is_default() && opts.cut.is_default() {
         return Err(MuxError::new_ok());
     }
 
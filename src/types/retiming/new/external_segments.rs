@@ -1,8 +1,12 @@
-use super::CacheMatroska;
+use super::{
+    CacheMatroska,
+    segments_index::{self, SegmentsIndex},
+};
 use crate::{ArcPathBuf, MediaInfo, MuxError, Result};
+use log::warn;
 use rayon::prelude::*;
 use std::{
-    collections::{HashMap, HashSet},
+    collections::HashMap,
     path::{Path, PathBuf},
     sync::{LazyLock, RwLock},
 };
@@ -10,66 +14,151 @@ use std::{
 static EXTERNAL_SEGMENTS: LazyLock<RwLock<ExternalSegments>> =
     LazyLock::new(|| RwLock::new(ExternalSegments::default()));
 
-#[derive(Clone, Debug, Default)]
+#[derive(Debug, Default)]
 struct ExternalSegments {
-    pub map: HashMap<Box<[u8]>, ArcPathBuf>,
-    pub dir_set: HashSet<PathBuf>,
+    /// Matroska files by UIDs in every scanned directory.
+    pub dirs: HashMap<PathBuf, HashMap<Box<[u8]>, ArcPathBuf>>,
+    pub index: Option<SegmentsIndex>,
+    /// Segments directories with subdirectories; collected once, as the config is the same.
+    pub segments_dirs: Option<Vec<PathBuf>>,
 }
 
+/// Finds a linked segment in the directory of the base file, then in
+/// [`RetimingOptionsSegments::dirs`](crate::RetimingOptionsSegments::dirs) recursively
+/// up to the [`Input::depth`](crate::Input::depth).
 pub(super) fn find_external_segment(
     mi: &MediaInfo,
     cache: &CacheMatroska,
     dir: &Path,
     uid: &[u8],
 ) -> Result<ArcPathBuf> {
-    return if let Some(res) = get_cached(dir, uid) {
+    let segments_dirs = segments_dirs(mi);
+    let dirs: Vec<&Path> = std::iter::once(dir)
+        .chain(segments_dirs.iter().map(|d| d.as_path()))
+        .collect();
+
+    return if let Some(res) = get_cached(&dirs, uid) {
         res
     } else {
-        insert_all_in_dir(mi, cache, dir);
-        get_cached(dir, uid).unwrap()
+        for dir in dirs.iter() {
+            insert_all_in_dir(mi, cache, dir);
+        }
+        write_index();
+        get_cached(&dirs, uid).unwrap()
     };
 
-    fn get_cached(dir: &Path, uid: &[u8]) -> Option<Result<ArcPathBuf>> {
+    fn segments_dirs(mi: &MediaInfo) -> Vec<PathBuf> {
+        if let Some(xs) = EXTERNAL_SEGMENTS.read().unwrap().segments_dirs.as_ref() {
+            return xs.clone();
+        }
+
+        let input = &mi.cfg.input;
+        let xs: Vec<PathBuf> = mi
+            .cfg
+            .retiming_options
+            .segments
+            .dirs
+            .iter()
+            .flat_map(|d| input.iter_dirs_in(d))
+            .map(|d| d.to_path_buf())
+            .collect();
+
+        EXTERNAL_SEGMENTS.write().unwrap().segments_dirs = Some(xs.clone());
+        xs
+    }
+
+    // Dirs are looked up in order, so the directory of the base file takes precedence
+    // over segments directories cached by another file.
+    fn get_cached(dirs: &[&Path], uid: &[u8]) -> Option<Result<ArcPathBuf>> {
         let es = EXTERNAL_SEGMENTS.read().unwrap();
 
-        if let Some(p) = es.map.get(uid) {
-            Some(Ok(p.clone()))
-        } else if es.dir_set.contains(dir) {
-            Some(Err(error(dir, uid)))
-        } else {
-            None
+        for dir in dirs {
+            if let Some(p) = es.dirs.get(*dir)?.get(uid) {
+                return Some(Ok(p.clone()));
+            }
         }
+        Some(Err(error(dirs[0], uid)))
     }
 
     fn insert_all_in_dir(mi: &MediaInfo, cache: &CacheMatroska, dir: &Path) {
-        let xs: Vec<(Box<[u8]>, ArcPathBuf)> = mi
+        if EXTERNAL_SEGMENTS.read().unwrap().dirs.contains_key(dir) {
+            return;
+        }
+
+        if EXTERNAL_SEGMENTS.read().unwrap().index.is_none() {
+            let index = SegmentsIndex::read(mi.cfg.retiming_options.segments.cache.as_deref());
+            EXTERNAL_SEGMENTS
+                .write()
+                .unwrap()
+                .index
+                .get_or_insert(index);
+        }
+
+        let files: Vec<(PathBuf, Option<(u64, u64)>)> = mi
             .cfg
             .input
             .iter_matroska_in_dir(dir)
-            .par_bridge()
-            .filter_map(|m| {
-                if let Some(u) = match cache.immut(&m) {
-                    Some(mat) => mat.info.uid.clone(),
-                    None => matroska::open(&m).ok().map_or(None, |mat| mat.info.uid),
-                } {
-                    Some((u.into(), m.into()))
-                } else {
-                    None
+            .map(|m| {
+                let stat = segments_index::stat(&m);
+                (m, stat)
+            })
+            .collect();
+
+        let files: Vec<_> = {
+            let es = EXTERNAL_SEGMENTS.read().unwrap();
+            files
+                .into_iter()
+                .map(|(m, stat)| {
+                    let indexed = stat.and_then(|st| es.index.as_ref()?.get(&m, st));
+                    (m, stat, indexed)
+                })
+                .collect()
+        };
+
+        let xs: Vec<(PathBuf, Option<Box<[u8]>>, Option<(u64, u64)>)> = files
+            .into_par_iter()
+            .map(|(m, stat, indexed)| match indexed {
+                Some(uid) => (m, uid, None),
+                None => {
+                    let uid = match cache.immut(&m) {
+                        Some(mat) => mat.info.uid.clone(),
+                        None => matroska::open(&m).ok().map_or(None, |mat| mat.info.uid),
+                    };
+                    (m, uid.map(Into::into), stat)
                 }
             })
             .collect();
 
         let mut es = EXTERNAL_SEGMENTS.write().unwrap();
+        let mut map = HashMap::new();
+
+        for (m, uid, new_stat) in xs {
+            if let (Some(index), Some(st)) = (es.index.as_mut(), new_stat) {
+                index.insert(m.clone(), st, uid.clone());
+            }
+            if let Some(u) = uid {
+                map.entry(u).or_insert_with(|| m.into());
+            }
+        }
+        es.dirs.insert(dir.to_owned(), map);
+    }
+
+    fn write_index() {
+        let index = EXTERNAL_SEGMENTS
+            .write()
+            .unwrap()
+            .index
+            .as_mut()
+            .and_then(SegmentsIndex::take_changed);
 
-        for (k, v) in xs {
-            es.map.insert(k, v);
+        if let Some(Err(e)) = index.map(|mut index| index.try_write()) {
+            warn!("Fail save segments index: {}", e);
         }
-        es.dir_set.insert(dir.to_owned());
     }
 
     fn error(dir: &Path, uid: &[u8]) -> MuxError {
         err!(
-            "Not found external matroska segment '{:?}' in the directory '{}'",
+            "Not found external matroska segment '{:?}' in the directory '{}' and segments directories",
             uid,
             dir.display()
         )
@@ -0,0 +1,148 @@
+use crate::Result;
+use serde_json::{Map, Value as Json, json};
+use std::{
+    collections::HashMap,
+    env, fs,
+    fs::File,
+    io::{BufReader, BufWriter},
+    path::{Path, PathBuf},
+    time::UNIX_EPOCH,
+};
+
+/// An on-disk index of matroska segment UIDs, reused between runs.
+///
+/// Entries are valid while the modification time and the size of a file are the same.
+#[derive(Clone, Debug, Default)]
+pub(super) struct SegmentsIndex {
+    json: PathBuf,
+    files: HashMap<PathBuf, IndexedFile>,
+    is_changed: bool,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+struct IndexedFile {
+    mtime: u64,
+    size: u64,
+    uid: Option<Box<[u8]>>,
+}
+
+impl SegmentsIndex {
+    const JSON_NAME: &str = "mux-media-segments.json";
+
+    /// Reads the index from JSON or from the default file in the temp directory.
+    /// Starts an empty index if there's no valid one.
+    pub(super) fn read(json: Option<&Path>) -> SegmentsIndex {
+        let json = json.map_or_else(|| env::temp_dir().join(Self::JSON_NAME), Path::to_owned);
+
+        let files = match try_read_files(&json) {
+            Ok(files) => files,
+            Err(e) => {
+                if json.exists() {
+                    log::debug!("Fail read segments index '{}': {}", json.display(), e);
+                }
+                HashMap::new()
+            }
+        };
+
+        SegmentsIndex {
+            json,
+            files,
+            is_changed: false,
+        }
+    }
+
+    /// Returns an indexed UID of the file of the [`stat`], or [`None`] if the file isn't
+    /// indexed or changed.
+    pub(super) fn get(&self, path: &Path, stat: (u64, u64)) -> Option<Option<Box<[u8]>>> {
+        let f = self.files.get(path)?;
+        (stat == (f.mtime, f.size)).then(|| f.uid.clone())
+    }
+
+    pub(super) fn insert(&mut self, path: PathBuf, stat: (u64, u64), uid: Option<Box<[u8]>>) {
+        let (mtime, size) = stat;
+        self.files.insert(path, IndexedFile { mtime, size, uid });
+        self.is_changed = true;
+    }
+
+    /// Returns a copy to write if the index is changed, then counts it as written.
+    pub(super) fn take_changed(&mut self) -> Option<SegmentsIndex> {
+        let changed = self.is_changed.then(|| self.clone());
+        self.is_changed = false;
+        changed
+    }
+
+    /// Tries write the index to JSON if it's changed, dropping removed files.
+    pub(super) fn try_write(&mut self) -> Result<()> {
+        if !self.is_changed {
+            return Ok(());
+        }
+        self.files.retain(|path, _| path.exists());
+
+        let files: Map<String, Json> = self
+            .files
+            .iter()
+            .map(|(path, f)| {
+                let val = json!({
+                    "mtime": f.mtime,
+                    "size": f.size,
+                    "uid": f.uid.as_ref().map(|u| to_hex(u)),
+                });
+                (path.to_string_lossy().into_owned(), val)
+            })
+            .collect();
+
+        // Writes to a temp file first, so parallel runs never read a half-written index.
+        let tmp = self
+            .json
+            .with_extension(format!("{}.tmp", std::process::id()));
+        let writer = BufWriter::new(File::create(&tmp)?);
+        serde_json::to_writer(writer, &Json::Object(files))?;
+        fs::rename(&tmp, &self.json)?;
+
+        self.is_changed = false;
+        Ok(())
+    }
+}
+
+fn try_read_files(json: &Path) -> Result<HashMap<PathBuf, IndexedFile>> {
+    let reader = BufReader::new(File::open(json)?);
+    let val: Json = serde_json::from_reader(reader)?;
+
+    let files = val
+        .as_object()
+        .ok_or_else(|| err!("Segments index must be an object of files"))?
+        .iter()
+        .filter_map(|(path, f)| {
+            let mtime = f.get("mtime")?.as_u64()?;
+            let size = f.get("size")?.as_u64()?;
+            let uid = match f.get("uid")? {
+                Json::String(s) => Some(from_hex(s)?),
+                _ => None,
+            };
+            Some((path.into(), IndexedFile { mtime, size, uid }))
+        })
+        .collect();
+
+    Ok(files)
+}
+
+/// Returns `(mtime, size)` of the file.
+pub(super) fn stat(path: &Path) -> Option<(u64, u64)> {
+    let meta = fs::metadata(path).ok()?;
+    let mtime = meta.modified().ok()?.duration_since(UNIX_EPOCH).ok()?;
+    Some((mtime.as_nanos() as u64, meta.len()))
+}
+
+fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+fn from_hex(s: &str) -> Option<Box<[u8]>> {
+    if s.len() % 2 != 0 {
+        return None;
+    }
+    (0..s.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(s.get(i..i + 2)?, 16).ok())
+        .collect()
+}
@@ -1,14 +1,20 @@
 mod new;
 mod to_json_args;
 
-use crate::{GlobSetPattern, IsDefault, RetimingChapter};
+use crate::{Duration, GlobSetPattern, IsDefault, RetimingChapter};
+use std::path::PathBuf;
 
 /// A retiming configuration.
 #[derive(Clone, Debug, Default, PartialEq, IsDefault)]
 #[non_exhaustive]
 pub struct RetimingOptions {
     pub parts: RetimingOptionsParts,
+    pub cut: RetimingOptionsCut,
+    pub edition: Option<RetimingEdition>,
+    pub segments: RetimingOptionsSegments,
     pub no_linked: bool,
+    pub smart_cut: bool,
+    pub exact_audio: bool,
 }
 
 /// A retiming configuration by chapter titles.
@@ -18,6 +24,87 @@ pub struct RetimingOptionsParts {
     pub pattern: Option<GlobSetPattern>,
 }
 
+/// A retiming configuration by time ranges, independent of chapters.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct RetimingOptionsCut {
+    pub inverse: bool,
+    /// Ranges of (start, end); [`None`] end is the end of the video.
+    pub ranges: Vec<(Duration, Option<Duration>)>,
+}
+
+/// A configuration of the search for linked matroska segments.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct RetimingOptionsSegments {
+    /// Directories to search besides the directory of the base file.
+    pub dirs: Vec<PathBuf>,
+    /// An index of segment UIDs; [`None`] is a file in the temp directory.
+    pub cache: Option<PathBuf>,
+}
+
+impl IsDefault for RetimingOptionsSegments {
+    fn is_default(&self) -> bool {
+        self.dirs.is_empty() && self.cache.is_none()
+    }
+}
+
+/// A matroska chapter edition to retime by.
+#[derive(Clone, Debug, PartialEq)]
+pub enum RetimingEdition {
+    /// A 0-based number of the edition.
+    Num(usize),
+    /// A case-insensitive name of the edition.
+    Name(String),
+}
+
+impl IsDefault for RetimingOptionsCut {
+    fn is_default(&self) -> bool {
+        self.ranges.is_empty()
+    }
+}
+
+impl RetimingOptionsCut {
+    /// Returns sorted non-overlapping ranges to save within the video duration.
+    pub fn save_ranges(&self, duration: Duration) -> Vec<(Duration, Duration)> {
+        let mut xs: Vec<(Duration, Duration)> = self
+            .ranges
+            .iter()
+            .map(|(start, end)| {
+                (
+                    (*start).min(duration),
+                    end.unwrap_or(duration).min(duration),
+                )
+            })
+            .filter(|(start, end)| start < end)
+            .collect();
+        xs.sort();
+
+        let mut merged: Vec<(Duration, Duration)> = Vec::with_capacity(xs.len());
+        for (start, end) in xs {
+            match merged.last_mut() {
+                Some(last) if start <= last.1 => last.1 = last.1.max(end),
+                _ => merged.push((start, end)),
+            }
+        }
+
+        if !self.inverse {
+            return merged;
+        }
+
+        let mut inverted = Vec::with_capacity(merged.len() + 1);
+        let mut start = Duration::default();
+        for (s, e) in merged {
+            if start < s {
+                inverted.push((start, s));
+            }
+            start = e;
+        }
+        if start < duration {
+            inverted.push((start, duration));
+        }
+        inverted
+    }
+}
+
 impl RetimingOptions {
     pub(crate) fn is_save_chapter(&self, chp: &RetimingChapter) -> bool {
         if self.no_linked && chp.uid.is_some() {
@@ -1,5 +1,6 @@
 use super::*;
 use crate::{Result, helpers};
+use std::{fmt, str::FromStr};
 
 impl RetimingOptions {
     pub(crate) fn from_str_parts(s: &str) -> Result<Self> {
@@ -14,7 +15,85 @@ impl RetimingOptions {
 
         Ok(Self {
             parts,
-            no_linked: false,
+            ..Default::default()
         })
     }
 }
+
+impl FromStr for RetimingOptionsCut {
+    type Err = crate::MuxError;
+
+    /// Parses `[!]start-end[,start-end]...`, where a time is `[[HH:]MM:]SS[.nn]`.
+    /// An empty start is the start of the video, an empty end is its end.
+    fn from_str(s: &str) -> Result<Self> {
+        let s = s.trim();
+        let (inverse, s) = helpers::parse_inverse_str(s);
+
+        let ranges = s
+            .split(',')
+            .map(str::trim)
+            .filter(|s| !s.is_empty())
+            .map(parse_range)
+            .collect::<Result<Vec<_>>>()?;
+
+        if ranges.is_empty() {
+            return Err(err!("No time ranges found"));
+        }
+
+        Ok(Self { inverse, ranges })
+    }
+}
+
+impl FromStr for RetimingEdition {
+    type Err = crate::MuxError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let s = s.trim();
+        if s.is_empty() {
+            return Err(err!("Empty edition"));
+        }
+
+        Ok(match s.parse::<usize>() {
+            Ok(n) => Self::Num(n),
+            Err(_) => Self::Name(s.to_owned()),
+        })
+    }
+}
+
+impl fmt::Display for RetimingEdition {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Num(n) => write!(f, "{}", n),
+            Self::Name(s) => write!(f, "{}", s),
+        }
+    }
+}
+
+fn parse_range(s: &str) -> Result<(Duration, Option<Duration>)> {
+    let (start, end) = s
+        .split_once('-')
+        .ok_or_else(|| err!("Invalid time range '{}', must be start-end", s))?;
+
+    let start = match start.trim() {
+        "" => Duration::default(),
+        t => parse_time(t)?,
+    };
+    let end = match end.trim() {
+        "" => None,
+        t => Some(parse_time(t)?),
+    };
+
+    if end.is_some_and(|end| end <= start) {
+        return Err(err!("End of time range '{}' must be greater than start", s));
+    }
+
+    Ok((start, end))
+}
+
+fn parse_time(s: &str) -> Result<Duration> {
+    match s.matches(':').count() {
+        0 => format!("00:00:{}", s).parse(),
+        1 => format!("00:{}", s).parse(),
+        _ => s.parse(),
+    }
+}
@@ -4,7 +4,15 @@ use crate::ToJsonArgs;
 impl ToJsonArgs for RetimingOptions {
     fn append_json_args(&self, args: &mut Vec<String>) {
         self.parts.append_json_args(args);
+        self.cut.append_json_args(args);
+        if let Some(ed) = self.edition.as_ref() {
+            args.push(to_json_args!(Edition));
+            args.push(ed.to_string());
+        }
+        self.segments.append_json_args(args);
         to_json_args!(@push_true, self, args; no_linked, NoLinked);
+        to_json_args!(@push_true, self, args; smart_cut, SmartCut);
+        to_json_args!(@push_true, self, args; exact_audio, ExactAudio);
     }
 }
 
@@ -24,3 +32,41 @@ impl ToJsonArgs for RetimingOptionsParts {
         }
     }
 }
+
+impl ToJsonArgs for RetimingOptionsCut {
+    fn append_json_args(&self, args: &mut Vec<String>) {
+        if self.ranges.is_empty() {
+            return;
+        }
+
+        let ranges = self
+            .ranges
+            .iter()
+            .map(|(start, end)| match end {
+                Some(end) => format!("{}-{}", start, end),
+                None => format!("{}-", start),
+            })
+            .collect::<Vec<_>>()
+            .join(",");
+
+        args.push(to_json_args!(Cut));
+        args.push(if self.inverse {
+            format!("!{}", ranges)
+        } else {
+            ranges
+        });
+    }
+}
+
+impl ToJsonArgs for RetimingOptionsSegments {
+    fn append_json_args(&self, args: &mut Vec<String>) {
+        for dir in self.dirs.iter() {
+            args.push(to_json_args!(SegmentsDir));
+            args.push(dir.to_string_lossy().into_owned());
+        }
+        if let Some(json) = self.cache.as_ref() {
+            args.push(to_json_args!(SegmentsCache));
+            args.push(json.to_string_lossy().into_owned());
+        }
+    }
+}
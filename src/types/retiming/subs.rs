@@ -1,21 +1,21 @@
 mod base;
 mod destination;
 mod external;
-mod ty;
-mod xs;
 
 use super::{RetimedStream, Retiming};
-use crate::{Duration, Result};
+use crate::{Duration, Result, SubType, Subs, markers::*, try_extract_sub};
 use destination::Destination;
 use log::warn;
 use rsubs_lib::{SRT, SRTLine, SSA, SSAEvent, VTT, VTTLine};
 use std::{collections::HashMap, fs, path::Path, time::Duration as StdDuration};
 use time::Time;
-use ty::SubType;
-use xs::Subs;
 
 impl Retiming<'_, '_> {
     pub(crate) fn try_sub(&self, i: usize, src: &Path, i_stream: usize) -> Result<RetimedStream> {
+        if self.is_bitmap_sub(src, i_stream) {
+            return self.try_bitmap_sub(i, src, i_stream);
+        }
+
         let is_base = src == **self.base;
 
         let fall = |dest: &mut Destination, err| -> Result<()> {
@@ -55,7 +55,28 @@ impl Retiming<'_, '_> {
     }
 }
 
+/// Shifts all subtitle timings by the offset in seconds.
+///
+/// Cues which would start before zero are dropped.
+pub(crate) fn shift_subs(old: Subs, offset: f64) -> Subs {
+    let min_start = Duration::from_secs_f64((-offset).max(0.0));
+    let idxs = old
+        .iter_i_start_end()
+        .filter(|(_, start, _)| Duration::from(*start) >= min_start)
+        .map(|(i, _, _)| i)
+        .collect();
+
+    external::retime(old, vec![(idxs, offset)])
+}
+
 impl Retiming<'_, '_> {
+    fn is_bitmap_sub(&self, src: &Path, i_stream: usize) -> bool {
+        self.media_info
+            .immut(MIStreams, src)
+            .and_then(|xs| xs.get(i_stream))
+            .is_some_and(|s| s.codec.is_bitmap_sub())
+    }
+
     fn len_prev_uid_parts(&self, i_part: usize) -> f64 {
         let src = &self.parts[i_part].src;
         self.parts[..i_part]
@@ -173,42 +194,3 @@ fn sign_duration(offset: f64) -> (bool, StdDuration) {
         StdDuration::from_secs_f64(offset.abs()),
     )
 }
-
-fn try_extract(src: &Path, i_stream: usize, dest: &Destination) -> Result<()> {
-    use crate::ffmpeg::{Rational, format};
-
-    let mut ictx = format::input(&src)?;
-    let istream = ictx
-        .stream(i_stream)
-        .ok_or_else(|| err!("invalid stream index"))?;
-
-    let out_time_base = match dest.ty {
-        SubType::Ssa => Rational::new(1, 100),
-        _ => Rational::new(1, 1000),
-    };
-    let codec_id = istream.parameters().id();
-
-    let mut octx = format::output(&dest.path)?;
-
-    let ostream_index = {
-        let mut ostream = octx.add_stream(codec_id)?;
-        ostream.set_parameters(istream.parameters());
-        ostream.set_time_base(out_time_base);
-        ostream.index()
-    };
-
-    octx.write_header()?;
-
-    for (stream, mut packet) in ictx.packets() {
-        if stream.index() != i_stream {
-            continue;
-        }
-
-        packet.set_stream(ostream_index);
-        packet.rescale_ts(stream.time_base(), out_time_base);
-        packet.write(&mut octx)?;
-    }
-
-    octx.write_trailer()?;
-    Ok(())
-}
@@ -104,7 +104,7 @@ fn try_map_old<'a>(
         let src = p.src.as_path();
         if !map.contains_key(&src) {
             let _ = fs::remove_file(&dest.path);
-            try_extract(src, i_stream, dest)?;
+            try_extract_sub(src, i_stream, dest.ty, &dest.path)?;
             let sub = Subs::new(&dest.path, dest.ty)?;
             map.insert(src, sub);
         }
@@ -10,7 +10,7 @@ impl Retiming<'_, '_> {
         let old = if let Some(_) = SubType::new_from_extension(dest.src_ext) {
             Subs::new(src, dest.ty)?
         } else {
-            try_extract(src, i_stream, dest)?;
+            try_extract_sub(src, i_stream, dest.ty, &dest.path)?;
             Subs::new(&dest.path, dest.ty)?
         };
 
@@ -33,7 +33,7 @@ impl Retiming<'_, '_> {
     }
 }
 
-fn retime(old: Subs, idxs_offset: Vec<(Vec<usize>, f64)>) -> Subs {
+pub(super) fn retime(old: Subs, idxs_offset: Vec<(Vec<usize>, f64)>) -> Subs {
     return match old {
         Subs::Srt(old) => Subs::Srt(srt(old, idxs_offset)),
         Subs::Ssa(old) => Subs::Ssa(ssa(old, idxs_offset)),
@@ -1,25 +1,41 @@
-use super::{RetimedStream, Retiming, try_concat, write_stream_copy_header};
+mod smart_cut;
+
+use super::{RetimedStream, Retiming, duration_to_ts, try_concat, write_stream_copy_header};
 use crate::{
     Duration, Result,
-    ffmpeg::{Packet, Rescale, format},
+    ffmpeg::{
+        Packet, Rescale,
+        format::{self, context},
+        util::media::Type,
+    },
 };
+use log::warn;
 use rayon::prelude::*;
 use std::path::{Path, PathBuf};
 
 impl Retiming<'_, '_> {
-    pub(super) fn try_video(&self, src: &Path, i_stream: usize) -> Result<RetimedStream> {
-        if i_stream != self.i_base_stream && src != self.base.as_path() {
-            return Err(err!(
-                "Unsupported retiming more than 1 video track at a time. Skipping {} stream {}",
-                src.display(),
-                i_stream
-            ));
+    pub(super) fn try_video(&self, i: usize, src: &Path, i_stream: usize) -> Result<RetimedStream> {
+        if src == **self.base && i_stream == self.i_base_stream {
+            return self.try_base_video();
+        }
+
+        let dest = self.temp_dir.join(format!("{}-vid-{}.mkv", self.job, i));
+        if src == **self.base {
+            let splits = self.try_base_extra_video(i_stream)?;
+            try_concat_at(&splits, &dest)?;
+        } else {
+            let splits = self.try_external_video(i, src, i_stream)?;
+            try_concat(src, &splits, &dest)?;
         }
 
-        self.try_base_video()
+        Ok(RetimedStream {
+            src: Some(dest),
+            i_stream: 0,
+        })
     }
 
     pub(super) fn init_base_splits(&mut self) -> Result<()> {
+        let is_smart = self.media_info.cfg.retiming_options.smart_cut;
         let raw_splits: Vec<(usize, f64, f64, PathBuf)> = self
             .parts
             .par_iter()
@@ -29,7 +45,7 @@ impl Retiming<'_, '_> {
                     .temp_dir
                     .join(format!("{}-vid-base-{}.mkv", self.job, i));
 
-                try_split(&p.src, self.i_base_stream, &split, p.start, p.end)
+                try_split_any(is_smart, &p.src, self.i_base_stream, &split, p.start, p.end)
                     .map(|(start, end)| (i, start, end, split))
             })
             .collect::<Result<_>>()?;
@@ -51,6 +67,79 @@ impl Retiming<'_, '_> {
         Ok(())
     }
 
+    /// Splits a not base video stream of the base (e.g. an alternate angle) at the
+    /// boundaries of the base video parts.
+    ///
+    /// Returns splits with their positions in the retimed base video (seconds): a split
+    /// is placed by its actual start, so it doesn't drift against the base video
+    /// whatever its own keyframes. Parts whose source lacks the stream are skipped.
+    fn try_base_extra_video(&self, i_stream: usize) -> Result<Vec<(PathBuf, f64)>> {
+        let is_smart = self.media_info.cfg.retiming_options.smart_cut;
+
+        let mut pos = 0.0;
+        let targets: Vec<_> = self
+            .parts
+            .iter()
+            .enumerate()
+            .map(|(i, p)| {
+                let target = (i, p, pos);
+                pos += p.end.as_secs_f64() - p.start.as_secs_f64();
+                target
+            })
+            .collect();
+
+        let splits = targets
+            .into_par_iter()
+            .map(|(i, p, pos)| {
+                let i_stream = some_or!(self.try_part_stream(&p.src, i_stream)?, return Ok(None));
+                let dest = self
+                    .temp_dir
+                    .join(format!("{}-vid-base-{}-{}.mkv", self.job, i_stream, i));
+                let (start, _) = try_split_any(is_smart, &p.src, i_stream, &dest, p.start, p.end)?;
+                Ok(Some((dest, pos + start - p.start.as_secs_f64())))
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok(splits.into_iter().flatten().collect())
+    }
+
+    /// Returns an index of the stream of the part source matching the `i_stream` stream
+    /// of the base by type and order, if any.
+    fn try_part_stream(&self, src: &Path, i_stream: usize) -> Result<Option<usize>> {
+        if src == **self.base {
+            return Ok(Some(i_stream));
+        }
+
+        let videos = |ictx: &context::Input| -> Vec<usize> {
+            ictx.streams()
+                .filter(|s| s.parameters().medium() == Type::Video)
+                .map(|s| s.index())
+                .collect()
+        };
+
+        let n = videos(&format::input(&**self.base)?)
+            .iter()
+            .position(|i| *i == i_stream)
+            .ok_or_else(|| err!("Not found video stream {}", i_stream))?;
+
+        Ok(videos(&format::input(&src)?).get(n).copied())
+    }
+
+    fn try_external_video(&self, i: usize, src: &Path, i_stream: usize) -> Result<Vec<PathBuf>> {
+        let is_smart = self.media_info.cfg.retiming_options.smart_cut;
+
+        self.external_targets()
+            .into_par_iter()
+            .map(|(i_chp, start, end)| {
+                let dest = self
+                    .temp_dir
+                    .join(format!("{}-vid-{}-{}.mkv", self.job, i, i_chp));
+                try_split_any(is_smart, src, i_stream, &dest, start, end)?;
+                Ok(dest)
+            })
+            .collect()
+    }
+
     fn try_base_video(&self) -> Result<RetimedStream> {
         let dest = self.temp_dir.join(format!("{}-vid-base.mkv", self.job));
         try_concat(&self.base, &self.base_splits, &dest)?;
@@ -62,6 +151,75 @@ impl Retiming<'_, '_> {
     }
 }
 
+// Unlike try_concat, each split is placed at its position in the base video, so the
+// stream keeps sync with it; packets reaching the next split are dropped.
+fn try_concat_at(splits: &[(PathBuf, f64)], dest: &Path) -> Result<()> {
+    let mut icontexts = Vec::with_capacity(splits.len());
+    for (p, _) in splits {
+        icontexts.push(format::input(p)?);
+    }
+    let ictx = icontexts
+        .first()
+        .ok_or_else(|| err!("Not found any retimed part"))?;
+
+    let mut octx = format::output(dest)?;
+    let (_, ost_time_base, ost_index) = write_stream_copy_header(ictx, 0, &mut octx)?;
+
+    let to_ts = |secs: f64| duration_to_ts(Duration::from_secs_f64(secs.max(0.0)), ost_time_base);
+    let mut last_dts = None::<i64>;
+
+    for (i, ictx) in icontexts.iter_mut().enumerate() {
+        let offset = to_ts(splits[i].1);
+        let limit = splits.get(i + 1).map(|(_, pos)| to_ts(*pos));
+
+        for (ist, mut packet) in ictx.packets() {
+            let ist_time_base = ist.time_base();
+            let rescale = |ts: i64| ts.rescale(ist_time_base, ost_time_base);
+
+            let pts = rescale(some_or!(packet.pts(), continue)) + offset;
+            if limit.is_some_and(|limit| pts >= limit) || last_dts.is_some_and(|last| pts <= last) {
+                continue;
+            }
+            let mut dts = packet.dts().map_or(pts, |dts| rescale(dts) + offset);
+            if let Some(last) = last_dts {
+                dts = dts.max(last + 1).min(pts);
+            }
+            last_dts = Some(dts);
+
+            packet.set_pts(Some(pts));
+            packet.set_dts(Some(dts));
+            packet.set_duration(rescale(packet.duration()));
+            packet.set_stream(ost_index);
+            packet.write_interleaved(&mut octx)?;
+        }
+    }
+
+    octx.write_trailer()?;
+    Ok(())
+}
+
+fn try_split_any(
+    is_smart: bool,
+    src: &Path,
+    i_stream: usize,
+    dest: &Path,
+    trg_start: Duration,
+    trg_end: Duration,
+) -> Result<(f64, f64)> {
+    if is_smart {
+        match smart_cut::try_split(src, i_stream, dest, trg_start, trg_end) {
+            Ok(start_end) => return Ok(start_end),
+            Err(e) => warn!(
+                "Fail smart cut '{}' stream {}: {}. Cut on keyframes",
+                src.display(),
+                i_stream,
+                e
+            ),
+        }
+    }
+    try_split(src, i_stream, dest, trg_start, trg_end)
+}
+
 fn try_split(
     src: &Path,
     i_stream: usize,
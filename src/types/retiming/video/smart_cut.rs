@@ -0,0 +1,502 @@
+mod nal;
+
+use super::super::{extradata, write_stream_copy_header};
+use crate::{
+    Duration, Result,
+    ffmpeg::{
+        Codec, Dictionary, Packet, Rational, Rescale, codec, decoder, encoder, format, frame,
+        picture,
+    },
+};
+use nal::NalHeaders;
+use std::{ops::Range, path::Path};
+
+/// Splits the video stream exactly on `trg_start..trg_end`.
+///
+/// GOP fragments at both boundaries are re-encoded, other GOPs are stream copied.
+/// H.264 and HEVC fragments carry their own parameter sets in-band; other codecs
+/// fail if the encoder headers (extradata) don't match the source ones, so mixed
+/// packets would be undecodable. Fails if there's no encoder of the codec.
+/// Returns the (start, end) in seconds, same as keyframe splitting.
+pub(super) fn try_split(
+    src: &Path,
+    i_stream: usize,
+    dest: &Path,
+    trg_start: Duration,
+    trg_end: Duration,
+) -> Result<(f64, f64)> {
+    let (time_base, packets) = try_scan(src, i_stream, trg_end)?;
+    let to_ts = |dur: Duration| {
+        let tb = time_base.0 as f64 / time_base.1 as f64;
+        (dur.as_secs_f64() / tb).round() as i64
+    };
+    let plan = Plan::new(&packets, to_ts(trg_start), to_ts(trg_end))
+        .ok_or_else(|| err!("Not found any frame in the range"))?;
+
+    let mut ictx = format::input(&src)?;
+    let reencoder = {
+        let ist = ictx
+            .stream(i_stream)
+            .ok_or_else(|| err!("Not found stream"))?;
+        Reencoder::try_new(&ist)?
+    };
+
+    let mut octx = format::output(&dest)?;
+    let (ist_time_base, ost_time_base, ost_index) =
+        write_stream_copy_header(&ictx, i_stream, &mut octx)?;
+
+    let mut writer = Writer {
+        octx: &mut octx,
+        ost_index,
+        ist_time_base,
+        ost_time_base,
+        start: plan.start,
+        last_dts: None,
+        max_pts: None,
+        headers: reencoder
+            .nal
+            .as_ref()
+            .map(|nal| nal.sets())
+            .unwrap_or_default(),
+        is_after_fragment: false,
+    };
+
+    let mut head = plan
+        .head
+        .as_ref()
+        .map(|(_, frames)| reencoder.try_fragment(frames.clone()))
+        .transpose()?;
+    let mut tail = plan
+        .tail
+        .as_ref()
+        .map(|(_, frames)| reencoder.try_fragment(frames.clone()))
+        .transpose()?;
+    let mut copies: Vec<Packet> = Vec::new();
+
+    let mut idx = 0usize;
+    for (ist, packet) in ictx.packets() {
+        if ist.index() != i_stream {
+            continue;
+        }
+        if idx >= plan.last_idx {
+            break;
+        }
+        let i = idx;
+        idx += 1;
+        let pts = some_or!(packet.pts(), continue);
+
+        let mut is_head_done = false;
+        if let (Some((decode, _)), Some(fragment)) = (&plan.head, head.as_mut()) {
+            if decode.contains(&i) {
+                fragment.try_push(&packet)?;
+            }
+            is_head_done = i + 1 == decode.end;
+        }
+        if let Some(fragment) = head.take_if(|_| is_head_done) {
+            writer.write_fragment(fragment.try_finish()?)?;
+            writer.write_all(copies.split_off(0))?;
+        }
+        if let (Some((decode, _)), Some(fragment)) = (&plan.tail, tail.as_mut()) {
+            if decode.contains(&i) {
+                fragment.try_push(&packet)?;
+            }
+        }
+
+        if plan.copy.contains(&pts) {
+            match head {
+                Some(_) => copies.push(packet),
+                None => writer.write(packet)?,
+            }
+        }
+    }
+
+    if let Some(fragment) = head {
+        writer.write_fragment(fragment.try_finish()?)?;
+    }
+    writer.write_all(copies)?;
+    if let Some(fragment) = tail {
+        writer.write_fragment(fragment.try_finish()?)?;
+    }
+
+    let max_pts = writer.max_pts.ok_or_else(|| err!("Not written a packet"))?;
+    octx.write_trailer()?;
+
+    let to_seconds = |ts: i64| ts as f64 * time_base.0 as f64 / time_base.1 as f64;
+    Ok((
+        to_seconds(plan.start),
+        to_seconds(plan.end.min(max_pts.max(plan.start))),
+    ))
+}
+
+/// Returns the stream time base and `(pts, is_key)` of its packets in decode order,
+/// up to the first GOP after `trg_end`.
+fn try_scan(
+    src: &Path,
+    i_stream: usize,
+    trg_end: Duration,
+) -> Result<(Rational, Vec<(i64, bool)>)> {
+    let mut ictx = format::input(&src)?;
+    let time_base = ictx
+        .stream(i_stream)
+        .ok_or_else(|| err!("Not found stream"))?
+        .time_base();
+    let end = (trg_end.as_secs_f64() * time_base.1 as f64 / time_base.0 as f64).round() as i64;
+
+    let mut packets = Vec::new();
+    let mut key_after = None::<i64>;
+
+    for (ist, packet) in ictx.packets() {
+        if ist.index() != i_stream {
+            continue;
+        }
+        let pts = packet.pts().unwrap_or(i64::MIN);
+        if key_after.is_some_and(|k| pts > k) {
+            break;
+        }
+        if packet.is_key() && pts > end && key_after.is_none() {
+            key_after = Some(pts);
+        }
+        packets.push((pts, packet.is_key()));
+    }
+
+    Ok((time_base, packets))
+}
+
+/// Packet indexes (in decode order) to decode boundary fragments from, and shown
+/// timestamps to re-encode or copy.
+#[derive(Debug)]
+struct Plan {
+    start: i64,
+    end: i64,
+    head: Option<(Range<usize>, Range<i64>)>,
+    tail: Option<(Range<usize>, Range<i64>)>,
+    copy: Range<i64>,
+    last_idx: usize,
+}
+
+impl Plan {
+    fn new(packets: &[(i64, bool)], start: i64, end: i64) -> Option<Plan> {
+        let is_key_at = |i: usize| packets[i].1;
+        let first_key = |pred: &dyn Fn(i64) -> bool| {
+            (0..packets.len()).find(|i| is_key_at(*i) && pred(packets[*i].0))
+        };
+        let last_key = |pred: &dyn Fn(i64) -> bool| {
+            (0..packets.len())
+                .rev()
+                .find(|i| is_key_at(*i) && pred(packets[*i].0))
+        };
+        // Decoding from the keyframe before `from` keeps references of leading frames.
+        let decode = |frames: &Range<i64>| {
+            let last = (0..packets.len())
+                .rev()
+                .find(|i| frames.contains(&packets[*i].0))?;
+            let first = last_key(&|pts| pts <= frames.start).unwrap_or(0);
+            Some(first..last + 1)
+        };
+
+        let i_k1 = first_key(&|pts| pts >= start).filter(|i| packets[*i].0 < end);
+        let k1 = i_k1.map(|i| packets[i].0);
+
+        // The last GOP is re-encoded only if a keyframe after `end` cuts it.
+        let i_tail = first_key(&|pts| pts > end)
+            .filter(|_| first_key(&|pts| pts == end).is_none())
+            .and_then(|_| last_key(&|pts| pts < end));
+        // Leading frames of the keyframe are shown before it.
+        let t_from = i_tail
+            .filter(|i| k1.is_some_and(|k1| packets[*i].0 >= k1))
+            .map(|i| {
+                packets[i..]
+                    .iter()
+                    .map(|p| p.0)
+                    .filter(|pts| *pts != i64::MIN)
+                    .min()
+                    .unwrap_or(end)
+                    .max(start)
+            });
+
+        let head_frames = start..k1.unwrap_or(end).min(t_from.unwrap_or(end));
+        let tail_frames = t_from.map(|from| from..end);
+        let copy = match k1 {
+            Some(k1) => k1..t_from.unwrap_or(end),
+            None => 0..0,
+        };
+
+        let head = (!head_frames.is_empty())
+            .then(|| decode(&head_frames).map(|d| (d, head_frames)))
+            .flatten();
+        let tail = tail_frames
+            .filter(|f| !f.is_empty())
+            .and_then(|f| decode(&f).map(|d| (d, f)));
+
+        if head.is_none() && tail.is_none() && copy.is_empty() {
+            return None;
+        }
+
+        let last_idx = [
+            head.as_ref().map(|h| h.0.end),
+            tail.as_ref().map(|t| t.0.end),
+            (!copy.is_empty()).then(|| {
+                (0..packets.len())
+                    .rev()
+                    .find(|i| copy.contains(&packets[*i].0))
+                    .map_or(0, |i| i + 1)
+            }),
+        ]
+        .into_iter()
+        .flatten()
+        .max()
+        .unwrap_or(0);
+
+        Some(Plan {
+            start,
+            end,
+            head,
+            tail,
+            copy,
+            last_idx,
+        })
+    }
+}
+
+struct Writer<'a> {
+    octx: &'a mut format::context::Output,
+    ost_index: usize,
+    ist_time_base: Rational,
+    ost_time_base: Rational,
+    start: i64,
+    last_dts: Option<i64>,
+    max_pts: Option<i64>,
+    /// Source parameter sets to send in-band after a re-encoded fragment.
+    headers: &'a [u8],
+    is_after_fragment: bool,
+}
+
+impl Writer<'_> {
+    fn write_fragment(&mut self, packets: Vec<Packet>) -> Result<()> {
+        for packet in packets {
+            self.write_packet(packet)?;
+        }
+        self.is_after_fragment = true;
+        Ok(())
+    }
+
+    fn write_all(&mut self, packets: Vec<Packet>) -> Result<()> {
+        for packet in packets {
+            self.write(packet)?;
+        }
+        Ok(())
+    }
+
+    /// Writes a copied packet.
+    fn write(&mut self, packet: Packet) -> Result<()> {
+        // Decoders switch back to the source parameter sets at the first copied
+        // packet after a fragment, even if the encoder reused their ids.
+        let packet = match self.is_after_fragment && !self.headers.is_empty() {
+            true => with_data(
+                &packet,
+                [self.headers, packet.data().unwrap_or_default()].concat(),
+            ),
+            false => packet,
+        };
+        self.is_after_fragment = false;
+        self.write_packet(packet)
+    }
+
+    fn write_packet(&mut self, mut packet: Packet) -> Result<()> {
+        let pts = some_or!(packet.pts(), return Ok(()));
+        let rescale = |ts: i64| (ts - self.start).rescale(self.ist_time_base, self.ost_time_base);
+
+        let new_pts = rescale(pts);
+        // Copied and re-encoded fragments meet here, so dts is kept increasing.
+        let mut new_dts = rescale(packet.dts().unwrap_or(pts));
+        if let Some(last) = self.last_dts {
+            new_dts = new_dts.max(last + 1).min(new_pts);
+        }
+        self.last_dts = Some(new_dts);
+        self.max_pts = Some(self.max_pts.map_or(pts, |m| m.max(pts)));
+
+        let duration = packet
+            .duration()
+            .rescale(self.ist_time_base, self.ost_time_base);
+        packet.set_pts(Some(new_pts));
+        packet.set_dts(Some(new_dts));
+        packet.set_duration(duration);
+        packet.set_position(-1);
+        packet.set_stream(self.ost_index);
+        packet.write_interleaved(self.octx)?;
+        Ok(())
+    }
+}
+
+struct Reencoder {
+    params: codec::Parameters,
+    codec: Codec,
+    time_base: Rational,
+    frame_rate: Option<Rational>,
+    extradata: Vec<u8>,
+    nal: Option<NalHeaders>,
+}
+
+impl Reencoder {
+    /// Lambda of a quantizer step (`FF_QP2LAMBDA`).
+    const QP2LAMBDA: i32 = 118;
+
+    fn try_new(ist: &format::stream::Stream) -> Result<Reencoder> {
+        let params = ist.parameters().clone();
+        let codec = encoder::find(params.id())
+            .ok_or_else(|| err!("Not found encoder of {:?}", params.id()))?;
+        let extradata = unsafe {
+            let par = &*params.as_ptr();
+            extradata(par.extradata, par.extradata_size)
+        };
+        let nal = NalHeaders::new(params.id(), &extradata);
+
+        Ok(Reencoder {
+            params,
+            codec,
+            time_base: ist.time_base(),
+            frame_rate: Some(ist.avg_frame_rate()).filter(|r| r.numerator() > 0),
+            extradata,
+            nal,
+        })
+    }
+
+    fn try_fragment(&self, frames: Range<i64>) -> Result<Fragment<'_>> {
+        let mut dec = codec::Context::from_parameters(self.params.clone())?
+            .decoder()
+            .video()?;
+        dec.set_packet_time_base(self.time_base);
+
+        let mut enc = codec::Context::new_with_codec(self.codec)
+            .encoder()
+            .video()?;
+        enc.set_width(dec.width());
+        enc.set_height(dec.height());
+        enc.set_format(dec.format());
+        enc.set_aspect_ratio(dec.aspect_ratio());
+        enc.set_frame_rate(self.frame_rate);
+        enc.set_time_base(self.time_base);
+        // Without B-frames dts of the fragment equals pts, so it joins the copied GOPs.
+        enc.set_max_b_frames(0);
+
+        let src = unsafe { &*self.params.as_ptr() };
+        unsafe {
+            let ctx = enc.as_mut_ptr();
+            (*ctx).profile = src.profile;
+            (*ctx).level = src.level;
+        }
+
+        let mut opts = Dictionary::new();
+        // Without a global header H.264 and HEVC encoders repeat parameter sets
+        // in-band at keyframes. libx264 takes ids unused by the source, so copied
+        // packets keep referring to the source sets.
+        let mut flags = codec::Flags::empty();
+        match &self.nal {
+            Some(nal) if self.codec.name() == "libx264" => {
+                if let Some(id) = nal.free_id() {
+                    opts.set("x264-params", &format!("sps-id={}", id));
+                }
+            }
+            Some(_) => {}
+            // Other headers kept out of band are required from the encoder as well.
+            None if !self.extradata.is_empty() => flags |= codec::Flags::GLOBAL_HEADER,
+            None => {}
+        }
+
+        if src.bit_rate > 0 {
+            enc.set_bit_rate(src.bit_rate as usize);
+        } else {
+            // Native encoders (mpeg2video, mpeg4) take a fixed quantizer,
+            // libx264 and libx265 take crf.
+            flags |= codec::Flags::QSCALE;
+            enc.set_global_quality(2 * Self::QP2LAMBDA);
+            opts.set("crf", "16");
+        }
+        enc.set_flags(flags);
+        let enc = enc.open_as_with(self.codec, opts)?;
+
+        // Re-encoded packets are muxed under the source stream header, so it must
+        // describe them as well.
+        let enc_extradata =
+            unsafe { extradata((*enc.as_ptr()).extradata, (*enc.as_ptr()).extradata_size) };
+        if self.nal.is_none() && enc_extradata != self.extradata {
+            return Err(err!(
+                "Encoder headers of {:?} don't match the source",
+                self.params.id()
+            ));
+        }
+
+        Ok(Fragment {
+            dec,
+            enc,
+            frames,
+            nal: self.nal.as_ref(),
+            out: Vec::new(),
+        })
+    }
+}
+
+/// A boundary fragment being re-encoded.
+struct Fragment<'a> {
+    dec: decoder::Video,
+    enc: encoder::video::Encoder,
+    frames: Range<i64>,
+    nal: Option<&'a NalHeaders>,
+    out: Vec<Packet>,
+}
+
+impl Fragment<'_> {
+    fn try_push(&mut self, packet: &Packet) -> Result<()> {
+        self.dec.send_packet(packet)?;
+        self.receive_decoded()
+    }
+
+    fn try_finish(mut self) -> Result<Vec<Packet>> {
+        self.dec.send_eof()?;
+        self.receive_decoded()?;
+        self.enc.send_eof()?;
+        self.receive_encoded();
+        Ok(self.out)
+    }
+
+    fn receive_decoded(&mut self) -> Result<()> {
+        let mut frame = frame::Video::empty();
+
+        while self.dec.receive_frame(&mut frame).is_ok() {
+            let pts = some_or!(frame.timestamp(), continue);
+            if !self.frames.contains(&pts) {
+                continue;
+            }
+            frame.set_pts(Some(pts));
+            frame.set_kind(picture::Type::None);
+            self.enc.send_frame(&frame)?;
+            self.receive_encoded();
+        }
+
+        Ok(())
+    }
+
+    fn receive_encoded(&mut self) {
+        let mut packet = Packet::empty();
+        while self.enc.receive_packet(&mut packet).is_ok() {
+            let packet = match self.nal {
+                Some(nal) => {
+                    let data = nal.to_length_prefixed(packet.data().unwrap_or_default());
+                    with_data(&packet, data)
+                }
+                None => packet.clone(),
+            };
+            self.out.push(packet);
+        }
+    }
+}
+
+/// Returns a copy of the packet with other data.
+fn with_data(packet: &Packet, data: Vec<u8>) -> Packet {
+    let mut new = Packet::copy(&data);
+    new.set_pts(packet.pts());
+    new.set_dts(packet.dts());
+    new.set_duration(packet.duration());
+    new.set_flags(packet.flags());
+    new
+}
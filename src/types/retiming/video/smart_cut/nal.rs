@@ -0,0 +1,176 @@
+use crate::ffmpeg::codec::Id;
+
+/// Out of band headers of a stream of length-prefixed NAL units (H.264 avcC,
+/// HEVC hvcC).
+pub(super) struct NalHeaders {
+    /// Size of the length prefix of NAL units.
+    len_size: usize,
+    /// Parameter sets as length-prefixed NAL units.
+    sets: Vec<u8>,
+    /// H.264 SPS and PPS ids of the sets.
+    ids: Vec<u32>,
+}
+
+impl NalHeaders {
+    /// Returns the headers of the codec extradata, or [`None`] if it's not avcC or hvcC.
+    pub(super) fn new(id: Id, extradata: &[u8]) -> Option<NalHeaders> {
+        if extradata.first() != Some(&1) {
+            return None;
+        }
+        let (len_size, nals) = match id {
+            Id::H264 => avcc(extradata)?,
+            Id::HEVC => hvcc(extradata)?,
+            _ => return None,
+        };
+
+        let ids = match id {
+            Id::H264 => nals.iter().filter_map(|nal| h264_id(nal)).collect(),
+            _ => Vec::new(),
+        };
+        let mut sets = Vec::new();
+        for nal in nals {
+            push_nal(&mut sets, nal, len_size);
+        }
+
+        Some(NalHeaders {
+            len_size,
+            sets,
+            ids,
+        })
+    }
+
+    /// Returns the parameter sets as length-prefixed NAL units.
+    pub(super) fn sets(&self) -> &[u8] {
+        &self.sets
+    }
+
+    /// Returns the least H.264 parameter set id the source doesn't use.
+    pub(super) fn free_id(&self) -> Option<u32> {
+        (0..32).find(|id| !self.ids.contains(id))
+    }
+
+    /// Returns Annex B `data` as length-prefixed NAL units. Any other data is kept.
+    pub(super) fn to_length_prefixed(&self, data: &[u8]) -> Vec<u8> {
+        if !data.starts_with(&[0, 0, 1]) && !data.starts_with(&[0, 0, 0, 1]) {
+            return data.to_vec();
+        }
+        let mut out = Vec::with_capacity(data.len() + self.len_size);
+        for nal in annexb_nals(data) {
+            push_nal(&mut out, nal, self.len_size);
+        }
+        out
+    }
+}
+
+fn avcc(x: &[u8]) -> Option<(usize, Vec<&[u8]>)> {
+    let len_size = (*x.get(4)? & 3) as usize + 1;
+    let mut x = x.get(5..)?;
+    let mut nals = Vec::new();
+
+    for mask in [0x1F, 0xFF] {
+        let num = take(&mut x, 1)?[0] & mask;
+        for _ in 0..num {
+            nals.push(take_nal(&mut x)?);
+        }
+    }
+
+    Some((len_size, nals))
+}
+
+fn hvcc(x: &[u8]) -> Option<(usize, Vec<&[u8]>)> {
+    let len_size = (*x.get(21)? & 3) as usize + 1;
+    let mut x = x.get(22..)?;
+    let mut nals = Vec::new();
+
+    let arrays = take(&mut x, 1)?[0];
+    for _ in 0..arrays {
+        take(&mut x, 1)?;
+        let num = take(&mut x, 2)?;
+        for _ in 0..u16::from_be_bytes([num[0], num[1]]) {
+            nals.push(take_nal(&mut x)?);
+        }
+    }
+
+    Some((len_size, nals))
+}
+
+fn take<'a>(x: &mut &'a [u8], n: usize) -> Option<&'a [u8]> {
+    let head = x.get(..n)?;
+    *x = &x[n..];
+    Some(head)
+}
+
+fn take_nal<'a>(x: &mut &'a [u8]) -> Option<&'a [u8]> {
+    let len = take(x, 2)?;
+    take(x, u16::from_be_bytes([len[0], len[1]]) as usize)
+}
+
+fn push_nal(out: &mut Vec<u8>, nal: &[u8], len_size: usize) {
+    let len = (nal.len() as u32).to_be_bytes();
+    out.extend_from_slice(&len[4 - len_size..]);
+    out.extend_from_slice(nal);
+}
+
+/// Returns NAL units of Annex B data, split by start codes.
+fn annexb_nals(data: &[u8]) -> Vec<&[u8]> {
+    let mut nals = Vec::new();
+    let mut start = None::<usize>;
+    let mut i = 0;
+
+    while i + 3 <= data.len() {
+        if data[i..i + 3] != [0, 0, 1] {
+            i += 1;
+            continue;
+        }
+        if let Some(s) = start {
+            nals.push(trim_zeros(&data[s..i]));
+        }
+        i += 3;
+        start = Some(i);
+    }
+    if let Some(s) = start {
+        nals.push(&data[s..]);
+    }
+
+    nals.retain(|nal| !nal.is_empty());
+    nals
+}
+
+// A NAL unit never ends with a zero byte, those start the next 4-byte start code.
+fn trim_zeros(nal: &[u8]) -> &[u8] {
+    let len = nal.iter().rposition(|b| *b != 0).map_or(0, |i| i + 1);
+    &nal[..len]
+}
+
+/// Returns the id of the H.264 SPS or PPS NAL unit.
+fn h264_id(nal: &[u8]) -> Option<u32> {
+    match nal.first()? & 0x1F {
+        // profile, constraints and level precede the SPS id
+        7 => read_ue(nal, 32),
+        8 => read_ue(nal, 8),
+        _ => None,
+    }
+}
+
+/// Reads an unsigned Exp-Golomb code at the bit offset.
+fn read_ue(data: &[u8], mut bit: usize) -> Option<u32> {
+    let get = |bit: usize| data.get(bit / 8).map(|b| (b >> (7 - bit % 8)) & 1);
+
+    let mut zeros = 0;
+    while get(bit)? == 0 {
+        zeros += 1;
+        bit += 1;
+        if zeros > 31 {
+            return None;
+        }
+    }
+    bit += 1;
+
+    let mut value = 0u32;
+    for _ in 0..zeros {
+        value = (value << 1) | get(bit)? as u32;
+        bit += 1;
+    }
+
+    Some((1u32 << zeros) - 1 + value)
+}
@@ -3,7 +3,8 @@ pub(crate) mod streams;
 pub(crate) mod supported;
 pub(crate) mod ty;
 
-use crate::{CodecId, Lang, StreamType, Value};
+use crate::{CodecId, DispositionType, Keyword, Lang, StreamType, Value};
+use enum_map::EnumMap;
 
 /// A stream info.
 #[derive(Clone, Debug)]
@@ -27,4 +28,20 @@ pub struct Stream {
 
     /// Metadata `filename`.
     pub filename: Option<String>,
+
+    /// Dispositions of the source stream.
+    pub dispositions: EnumMap<DispositionType, bool>,
+
+    /// Keywords found in the name or path of the stream.
+    pub keywords: EnumMap<Keyword, bool>,
+
+    /// Whether the text sub has few cues against other subs and the video.
+    pub is_sparse: bool,
+}
+
+impl Stream {
+    /// Returns `true` if the stream is likely forced: signs or forced by keywords, or sparse.
+    pub fn is_forced_hint(&self) -> bool {
+        self.keywords[Keyword::Signs] || self.keywords[Keyword::Forced] || self.is_sparse
+    }
 }
@@ -1,6 +1,9 @@
+mod convert_subs;
+mod forced_pgs;
 mod new;
 
-use crate::{ArcPathBuf, StreamType};
+use crate::{ArcPathBuf, Bitrate, CodecId, Delay, DispositionType, StreamType};
+use enum_map::EnumMap;
 use std::path::{Path, PathBuf};
 
 /// A sorted order of streams.
@@ -123,11 +126,13 @@ use std::path::{Path, PathBuf};
 ///     assert_eq!(&o[1].src(), &first);
 ///     ```
 ///
-/// 4. It `Sub` stream and has an signs-key in stream name or Path:
-///     - true
-///     - false
+/// 4. A [`Keyword`](crate::Keyword) in stream name or Path:
+///     - `Signs` or `Forced`, or a sparse text sub ([`SubDensity`](crate::SubDensity))
+///     - None or `Full`
+///     - `Sdh`
+///     - `Commentary`
 ///
-///    Its affected only `Sub` streams if they has same 1-3.
+///    Its affected only streams that has same 1-3.
 ///
 ///     ```
 ///     # use mux_media::*;
@@ -258,6 +263,29 @@ pub struct StreamsOrderItem {
 
     /// Whether this is the first occurrence of the src in the [`StreamsOrder`].
     pub is_first_entry: bool,
+
+    /// A codec to reencode the stream into if the container can't copy it.
+    pub reencode: Option<CodecId>,
+
+    /// Fixed dispositions (e.g. from a [`MuxPlan`](crate::MuxPlan)), overriding configured
+    /// and auto ones.
+    pub dispositions: Option<EnumMap<DispositionType, bool>>,
+
+    /// A fixed encoding (e.g. from a [`MuxPlan`](crate::MuxPlan)), overriding configured
+    /// and auto ones.
+    pub encoding: Option<TrackEncoding>,
+}
+
+/// A resolved encoding of an output track.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct TrackEncoding {
+    /// A delay to apply to the track.
+    pub delay: Delay,
+
+    /// An encoder or codec name to transcode the track with.
+    pub codec: Option<String>,
+
+    pub bitrate: Option<Bitrate>,
 }
 
 deref_singleton_tuple_struct!(StreamsOrder, Vec<StreamsOrderItem>);
@@ -0,0 +1,317 @@
+use super::{StreamsOrder, StreamsOrderItem};
+use crate::{
+    CodecId, Config, Duration, IsDefault, MediaInfo, Result, Stream, SubFormat, SubStretch,
+    SubSync, SubType, Subs, Target, markers::*, shift_subs, try_extract_sub,
+};
+use log::{info, warn};
+use rsubs_lib::SSA;
+use std::{
+    collections::{HashMap, hash_map::Entry},
+    fs,
+    path::PathBuf,
+};
+
+/// Returns a codec of the sub stream after conversion, if it'll be converted.
+pub(super) fn converted_codec(
+    cfg: &Config,
+    target_paths: &Vec<Target>,
+    stream: &Stream,
+) -> Option<CodecId> {
+    let (_, fmt) = sub_format(cfg, target_paths, stream)?;
+    fmt.ty.map(|ty| ty.codec_id())
+}
+
+pub(super) fn try_convert_subs(mi: &mut MediaInfo, order: StreamsOrder) -> Result<StreamsOrder> {
+    let fmts: Vec<Option<SubFormat>> = order
+        .iter()
+        .map(|item| item_sub_format(mi, item).cloned())
+        .collect();
+
+    if fmts.iter().all(|fmt| fmt.is_none()) {
+        return Ok(order);
+    }
+
+    let video_duration = fmts
+        .iter()
+        .flatten()
+        .any(|fmt| fmt.stretch == Some(SubStretch::Auto))
+        .then(|| {
+            let video = order.iter().find(|m| m.ty.is_video())?;
+            mi.get(MIVideoDuration, &video.key).copied()
+        })
+        .flatten();
+
+    let is_any_sync = fmts
+        .iter()
+        .zip(order.iter())
+        .any(|(fmt, item)| fmt.is_some() && is_sync_sub(mi, item));
+
+    let speech = if is_any_sync {
+        match try_base_speech(mi, &order) {
+            Ok(xs) => xs,
+            Err(e) if mi.cfg.exit_on_err => return Err(e),
+            Err(e) => {
+                warn!("Fail detect speech of base audio: {}. Skipping sub sync", e);
+                None
+            }
+        }
+    } else {
+        None
+    };
+
+    let mut items: Vec<StreamsOrderItem> = Vec::with_capacity(order.len());
+
+    for (i, (mut item, fmt)) in order.0.into_iter().zip(fmts).enumerate() {
+        let fmt = match fmt {
+            Some(fmt) => fmt,
+            None => {
+                items.push(item);
+                continue;
+            }
+        };
+
+        let speech = speech.as_deref().filter(|_| is_sync_sub(mi, &item));
+
+        match try_convert(mi, i, &item, &fmt, video_duration, speech) {
+            Ok(dest) => {
+                item.src = Some(dest);
+                item.i_stream = 0;
+                items.push(item);
+            }
+            Err(e) if mi.cfg.exit_on_err => return Err(e),
+            Err(e) => warn!(
+                "Fail process '{}' sub stream {}: {}. Skipping",
+                item.key.display(),
+                item.key_i_stream,
+                e
+            ),
+        }
+    }
+
+    if items.is_empty() {
+        return Err(err!("Not save any track"));
+    }
+    renumber_sources(&mut items);
+
+    Ok(StreamsOrder(items))
+}
+
+fn sub_format<'a>(
+    cfg: &'a Config,
+    target_paths: &Vec<Target>,
+    stream: &Stream,
+) -> Option<(SubType, &'a SubFormat)> {
+    if !stream.ty.is_sub() {
+        return None;
+    }
+    let from = SubType::new_from_codec(stream.codec)?;
+
+    let (_, fmt) = cfg.stream_val(CfgSubFormat, target_paths, stream);
+    let to = fmt.ty.unwrap_or(from);
+
+    let is_need = to != from
+        || (to == SubType::Ssa && fmt.style.is_some())
+        || fmt.stretch.is_some_and(|s| s != SubStretch::Ratio(1.0));
+
+    is_need.then_some((from, fmt))
+}
+
+fn item_sub_format<'a>(mi: &'a MediaInfo, item: &StreamsOrderItem) -> Option<&'a SubFormat> {
+    let stream = mi.immut(MIStreams, &item.key)?.get(item.key_i_stream)?;
+    let target_paths = mi.immut(MITargetPaths, &item.key)?;
+
+    match sub_format(mi.cfg, target_paths, stream) {
+        Some((_, fmt)) => Some(fmt),
+        // Synced subs are rewritten even if they aren't converted.
+        None if is_sync_sub(mi, item) => {
+            Some(mi.cfg.stream_val(CfgSubFormat, target_paths, stream).1)
+        }
+        None => None,
+    }
+}
+
+/// Returns `true` if the external text sub should be synced to speech.
+///
+/// A delay set by the user or by the file name takes precedence.
+fn is_sync_sub(mi: &MediaInfo, item: &StreamsOrderItem) -> bool {
+    if !mi.cfg.sync_subs || SubType::new_from_path(&item.key).is_none() {
+        return false;
+    }
+
+    let stream = some_or!(
+        mi.immut(MIStreams, &item.key)
+            .and_then(|xs| xs.get(item.key_i_stream)),
+        return false
+    );
+    let target_paths = mi
+        .immut(MITargetPaths, &item.key)
+        .map_or(&[][..], |xs| xs.as_slice());
+    let (i, delays) = mi.cfg.stream_val(CfgDelays, target_paths, stream);
+
+    let is_user_delay = delays.get(&i, &stream.lang).is_some();
+    let is_hint_delay = *mi.cfg.auto_flags.delays
+        && mi
+            .immut(MIDelay, &item.key)
+            .is_some_and(|d| !d.is_default());
+
+    !is_user_delay && !is_hint_delay
+}
+
+/// Tries detect speech of the first audio track of the video source.
+fn try_base_speech(mi: &mut MediaInfo, order: &StreamsOrder) -> Result<Option<Vec<bool>>> {
+    let has_video = |mi: &MediaInfo, item: &StreamsOrderItem| {
+        mi.immut(MIStreams, &item.key)
+            .is_some_and(|xs| xs.iter().any(|s| s.ty.is_video()))
+    };
+
+    let item = some_or!(
+        order.iter().find(|m| m.ty.is_audio() && has_video(mi, m)),
+        return Ok(None)
+    );
+    let duration = *mi.try_get(MIPlayableDuration, &item.key)?;
+
+    SubSync::try_speech(item.src(), item.i_stream, duration).map(Some)
+}
+
+fn try_convert(
+    mi: &MediaInfo,
+    i: usize,
+    item: &StreamsOrderItem,
+    fmt: &SubFormat,
+    video_duration: Option<Duration>,
+    speech: Option<&[bool]>,
+) -> Result<PathBuf> {
+    let temp_dir = &mi.cfg.output.temp_dir;
+    let src = item.src();
+
+    // A standalone text file is read as is, other sources are extracted first.
+    let (src, from) = match SubType::new_from_path(src) {
+        Some(ty) if item.i_stream == 0 => (src.to_path_buf(), ty),
+        _ => {
+            let ty = SubType::from_codec_id(mi, item.key.as_path(), item.key_i_stream);
+            let dest = temp_dir.join(format!("{}-sub-format-{}-src.{}", mi.job, i, ty.as_ext()));
+            try_extract_sub(src, item.i_stream, ty, &dest)?;
+            (dest, ty)
+        }
+    };
+    let to = fmt.ty.unwrap_or(from);
+
+    let mut subs = Subs::new(&src, from)?;
+    let is_auto_stretch = fmt.stretch == Some(SubStretch::Auto);
+
+    if let Some(ratio) = match fmt.stretch {
+        Some(SubStretch::Ratio(r)) => Some(r),
+        // Is detected on sync.
+        Some(SubStretch::Auto) if speech.is_some() => None,
+        Some(SubStretch::Auto) => {
+            suggest_ratio(item, &subs, video_duration);
+            None
+        }
+        None => None,
+    } {
+        subs.stretch(ratio);
+    }
+
+    if let Some(speech) = speech {
+        subs = sync(item, subs, speech, is_auto_stretch);
+    }
+
+    let style = match fmt.style.as_ref().filter(|_| to == SubType::Ssa) {
+        Some(p) => Some(SSA::parse(fs::read_to_string(p)?)?),
+        None => None,
+    };
+
+    let dest = temp_dir.join(format!("{}-sub-format-{}.{}", mi.job, i, to.as_ext()));
+    subs.convert(to, style.as_ref()).try_write(&dest)?;
+
+    Ok(dest)
+}
+
+/// Logs a ratio detected by the video duration, but doesn't apply it: credits without
+/// cues make it unreliable.
+fn suggest_ratio(item: &StreamsOrderItem, subs: &Subs, video: Option<Duration>) {
+    let ratio = match (subs.last_end(), video) {
+        (Some(last_end), Some(video)) => SubStretch::detect_ratio(last_end, video),
+        _ => None,
+    };
+
+    match ratio {
+        Some(r) if r != 1.0 => warn!(
+            "Detected stretch ratio {:.5} of '{}' sub stream {}. Keep timings; set '--sub-stretch {:.5}' to apply it",
+            r,
+            item.key.display(),
+            item.key_i_stream,
+            r
+        ),
+        Some(_) => (),
+        None => warn!(
+            "Not detected stretch ratio of '{}' sub stream {}. Keep timings",
+            item.key.display(),
+            item.key_i_stream,
+        ),
+    }
+}
+
+fn sync(item: &StreamsOrderItem, mut subs: Subs, speech: &[bool], is_stretch: bool) -> Subs {
+    let cues: Vec<(Duration, Duration)> = subs
+        .iter_i_start_end()
+        .map(|(_, start, end)| (start.into(), end.into()))
+        .collect();
+
+    let ratios: Vec<f64> = if is_stretch {
+        let mut xs: Vec<f64> = SubStretch::fps_ratios().collect();
+        xs.sort_by(f64::total_cmp);
+        xs.dedup();
+        xs
+    } else {
+        vec![1.0]
+    };
+
+    match SubSync::new(speech, &cues, &ratios) {
+        Some(s) if s.confidence >= SubSync::MIN_CONFIDENCE => {
+            info!(
+                "Sync '{}' sub stream {} by offset {}ms and ratio {:.5} (confidence {:.2})",
+                item.key.display(),
+                item.key_i_stream,
+                s.offset,
+                s.ratio,
+                s.confidence
+            );
+            if s.ratio != 1.0 {
+                subs.stretch(s.ratio);
+            }
+            shift_subs(subs, *s.offset as f64 / 1000.0)
+        }
+        Some(s) => {
+            warn!(
+                "Low confidence {:.2} of '{}' sub stream {} sync. Keep timings",
+                s.confidence,
+                item.key.display(),
+                item.key_i_stream
+            );
+            subs
+        }
+        None => {
+            warn!(
+                "Not detected sync of '{}' sub stream {}. Keep timings",
+                item.key.display(),
+                item.key_i_stream
+            );
+            subs
+        }
+    }
+}
+
+pub(super) fn renumber_sources(items: &mut Vec<StreamsOrderItem>) {
+    let mut numbers: HashMap<PathBuf, usize> = HashMap::new();
+
+    for item in items {
+        let len = numbers.len();
+        let (num, is_first) = match numbers.entry(item.src().to_path_buf()) {
+            Entry::Occupied(e) => (*e.get(), false),
+            Entry::Vacant(e) => (*e.insert(len), true),
+        };
+        item.src_num = num;
+        item.is_first_entry = is_first;
+    }
+}
@@ -0,0 +1,90 @@
+use super::{StreamsOrder, StreamsOrderItem, convert_subs::renumber_sources};
+use crate::{DispositionType, MediaInfo, Pgs, Result, ffmpeg::codec::Id, markers::*};
+use enum_map::EnumMap;
+use log::{info, warn};
+use std::path::PathBuf;
+
+/// Inserts a forced-only copy before each selected PGS sub stream that has forced captions.
+pub(super) fn try_split_forced_pgs(
+    mi: &mut MediaInfo,
+    order: StreamsOrder,
+) -> Result<StreamsOrder> {
+    if !order.iter().any(|item| is_selected(mi, item)) {
+        return Ok(order);
+    }
+
+    let mut items: Vec<StreamsOrderItem> = Vec::with_capacity(order.len() + 1);
+
+    for (i, item) in order.0.into_iter().enumerate() {
+        if !is_selected(mi, &item) {
+            items.push(item);
+            continue;
+        }
+
+        match try_forced(mi, i, &item) {
+            Ok(Some(dest)) => {
+                let mut dispositions = EnumMap::default();
+                dispositions[DispositionType::Forced] = true;
+                items.push(StreamsOrderItem {
+                    src: Some(dest),
+                    i_stream: 0,
+                    dispositions: Some(dispositions),
+                    ..item.clone()
+                });
+            }
+            Ok(None) => info!(
+                "Not found forced captions in '{}' sub stream {}",
+                item.key.display(),
+                item.key_i_stream
+            ),
+            Err(e) if mi.cfg.exit_on_err => return Err(e),
+            Err(e) => warn!(
+                "Fail split forced '{}' sub stream {}: {}. Skipping",
+                item.key.display(),
+                item.key_i_stream,
+                e
+            ),
+        }
+
+        items.push(item);
+    }
+
+    renumber_sources(&mut items);
+    Ok(StreamsOrder(items))
+}
+
+fn is_selected(mi: &MediaInfo, item: &StreamsOrderItem) -> bool {
+    if !item.ty.is_sub() || item.reencode.is_some() {
+        return false;
+    }
+    let stream = some_or!(
+        mi.immut(MIStreams, &item.key)
+            .and_then(|xs| xs.get(item.key_i_stream)),
+        return false
+    );
+    let target_paths = some_or!(mi.immut(MITargetPaths, &item.key), return false);
+
+    let (i, fmt) = mi.cfg.stream_val(CfgSubFormat, target_paths, stream);
+
+    stream.codec.0 == Id::HDMV_PGS_SUBTITLE
+        && fmt
+            .split_forced_pgs
+            .as_ref()
+            .is_some_and(|xs| xs.is_save(&i, &stream.lang))
+}
+
+fn try_forced(mi: &MediaInfo, i: usize, item: &StreamsOrderItem) -> Result<Option<PathBuf>> {
+    let forced = match Pgs::try_from_stream(item.src(), item.i_stream)?.forced_only()? {
+        Some(pgs) => pgs,
+        None => return Ok(None),
+    };
+
+    let dest = mi
+        .cfg
+        .output
+        .temp_dir
+        .join(format!("{}-forced-pgs-{}.sup", mi.job, i));
+    forced.try_write(&dest)?;
+
+    Ok(Some(dest))
+}
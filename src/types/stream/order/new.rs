@@ -1,7 +1,11 @@
-use super::{StreamsOrder, StreamsOrderItem};
+use super::{
+    StreamsOrder, StreamsOrderItem,
+    convert_subs::{converted_codec, try_convert_subs},
+    forced_pgs::try_split_forced_pgs,
+};
 use crate::{
-    ArcPathBuf, Container, Lang, LangCode, MediaInfo, Result, RetimedStream, Retiming, StreamType,
-    StreamsSupported, i18n::logs, markers::*,
+    ArcPathBuf, CodecId, Config, Keyword, Lang, LangCode, MediaInfo, Result, RetimedStream,
+    Retiming, Stream, StreamType, StreamsSupported, Target, i18n::logs, markers::*,
 };
 use log::warn;
 use rayon::prelude::*;
@@ -30,20 +34,30 @@ impl StreamsOrder {
     ///
     /// - Fais retiming all files.
     ///
+    /// - Fails converting any sub **only if** `exit_on_err` is `true`.
+    ///
+    /// - Fails splitting forced PGS captions **only if** `exit_on_err` is `true`.
+    ///
     /// # Logging
     ///
     /// - **Only if** [`log`] is initialized with at least [`LevelFilter::Warn`](
     ///   log::LevelFilter::Warn) and `exit_on_err` is `false`.
     ///
     /// - Warning: fails retiming any media.
+    ///
+    /// - Warning: fails converting any sub to the target format.
+    ///
+    /// - Warning: fails splitting forced PGS captions.
     pub fn new(mi: &mut MediaInfo) -> Result<StreamsOrder> {
         if mi.cache.of_files.is_empty() {
             Err(err!("Not found any cached media file"))
         } else {
             let sources = sources(mi);
             let sorted_src_stream_ty = try_sorted_src_stream_ty(mi, &sources)?;
-            let items = items(mi.cfg.container, sources, sorted_src_stream_ty);
-            try_order(mi, items)
+            let items = items(mi.cfg, sources, sorted_src_stream_ty);
+            let order = try_order(mi, items)?;
+            let order = try_convert_subs(mi, order)?;
+            try_split_forced_pgs(mi, order)
         }
     }
 }
@@ -57,31 +71,31 @@ fn sources(mi: &mut MediaInfo) -> Vec<ArcPathBuf> {
 fn try_sorted_src_stream_ty(
     mi: &mut MediaInfo,
     sources: &Vec<ArcPathBuf>,
-) -> Result<Vec<(usize, usize, StreamType)>> {
+) -> Result<Vec<(usize, usize, StreamType, OutCodec)>> {
     let cfg = mi.cfg;
     let locale = cfg.locale;
 
-    let mut track_streams: Vec<(usize, usize, StreamType, OrderSortKey)> = Vec::new();
-    let mut attach_streams: Vec<(usize, usize, StreamType, Option<String>)> = Vec::new();
+    let mut track_streams: Vec<(usize, usize, StreamType, OutCodec, OrderSortKey)> = Vec::new();
+    let mut attach_streams: Vec<(usize, usize, StreamType, OutCodec, Option<String>)> = Vec::new();
     let mut attach_names: HashSet<String> = HashSet::new();
 
     for (i_src, src) in sources.iter().enumerate() {
         let streams = mi.try_take(MIStreams, src)?;
         let target_paths = mi.try_take(MITargetPaths, src)?;
 
-        streams.iter().for_each(|stream| {
+        for stream in streams.iter() {
             let ty = stream.ty;
             // skip temp dummy sub
             if ty.is_sub()
                 && stream.i == 0
                 && src.parent().map_or(false, |p| p == cfg.output.temp_dir)
             {
-                return;
+                continue;
             }
 
             let (i, cfg_streams) = cfg.stream_val(CfgStreams, &target_paths, stream);
             if !cfg_streams.is_save(&i, &stream.lang) {
-                return;
+                continue;
             }
 
             if ty.is_an_attach() {
@@ -91,60 +105,127 @@ fn try_sorted_src_stream_ty(
                     None => true,
                 } {
                     let fname = stream.filename.as_ref().map(|s| s.to_lowercase());
-                    attach_streams.push((i_src, stream.i, ty, fname));
+                    attach_streams.push((i_src, stream.i, ty, OutCodec::source(stream), fname));
 
                     if let Some(s) = &stream.filename {
                         let _ = attach_names.insert(s.clone());
                     }
                 }
-                return;
+                continue;
             }
 
             let lang = &stream.lang;
-            let it_signs = mi.it_signs(src, stream);
 
             let (i, defaults) = cfg.stream_val(CfgDefaults, &target_paths, stream);
             let default = defaults.get(&i, &lang);
             let (i, forceds) = cfg.stream_val(CfgForceds, &target_paths, stream);
             let forced = forceds.get(&i, &lang);
 
-            let key = OrderSortKey::new(ty, default, forced, it_signs, lang, locale);
-            track_streams.push((i_src, stream.i, ty, key));
-        });
+            let key = OrderSortKey::new(ty, default, forced, stream, lang, locale);
+            let codec = OutCodec::try_new(cfg, &target_paths, stream)?;
+            track_streams.push((i_src, stream.i, ty, codec, key));
+        }
 
         mi.set(MIStreams, src, streams);
         mi.set(MITargetPaths, src, target_paths);
     }
 
-    track_streams.sort_by(|a, b| a.3.cmp(&b.3));
-    attach_streams.sort_by(|a, b| a.2.cmp(&b.2).then(a.3.cmp(&b.3)));
+    track_streams.sort_by(|a, b| a.4.cmp(&b.4));
+    attach_streams.sort_by(|a, b| a.2.cmp(&b.2).then(a.4.cmp(&b.4)));
 
-    let mut streams: Vec<(usize, usize, StreamType)> =
+    let mut streams: Vec<(usize, usize, StreamType, OutCodec)> =
         Vec::with_capacity(track_streams.len() + attach_streams.len());
 
-    for (i_src, i_stream, ty, _) in track_streams {
-        streams.push((i_src, i_stream, ty));
+    for (i_src, i_stream, ty, codec, _) in track_streams {
+        streams.push((i_src, i_stream, ty, codec));
     }
-    for (i_src, i_stream, ty, _) in attach_streams {
-        streams.push((i_src, i_stream, ty));
+    for (i_src, i_stream, ty, codec, _) in attach_streams {
+        streams.push((i_src, i_stream, ty, codec));
     }
 
     Ok(streams)
 }
 
+/// A codec of the stream in the output.
+#[derive(Copy, Clone, Debug)]
+struct OutCodec {
+    id: CodecId,
+    /// Set by the user `--codecs`.
+    is_user: bool,
+}
+
+impl OutCodec {
+    fn source(stream: &Stream) -> OutCodec {
+        OutCodec {
+            id: stream.codec,
+            is_user: false,
+        }
+    }
+
+    /// Resolves a converted sub codec or a user `--codecs` codec, falling back to the
+    /// source codec.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if there's no encoder of the user codec.
+    fn try_new(cfg: &Config, target_paths: &Vec<Target>, stream: &Stream) -> Result<OutCodec> {
+        if let Some(id) = converted_codec(cfg, target_paths, stream) {
+            return Ok(OutCodec { id, is_user: true });
+        }
+        if stream.ty.is_sub() {
+            return Ok(Self::source(stream));
+        }
+
+        let (i, codecs) = cfg.stream_val(CfgCodecs, target_paths, stream);
+        Ok(match codecs.get(&i, &stream.lang) {
+            Some(name) => OutCodec {
+                id: CodecId::try_from_encoder_name(name)?,
+                is_user: true,
+            },
+            None => Self::source(stream),
+        })
+    }
+}
+
 fn items(
-    cont: Container,
+    cfg: &Config,
     sources: Vec<ArcPathBuf>,
-    sorted_src_stream_ty: Vec<(usize, usize, StreamType)>,
+    sorted_src_stream_ty: Vec<(usize, usize, StreamType, OutCodec)>,
 ) -> Vec<StreamsOrderItem> {
     let mut items: Vec<StreamsOrderItem> = Vec::with_capacity(sorted_src_stream_ty.len());
     let mut src_numbers = vec![Option::<usize>::None; sources.len()];
     let mut src_num = 0usize;
+    let cont = cfg.container;
     let mut sup = StreamsSupported::new(cont);
 
-    for (i_src, i_stream, ty) in sorted_src_stream_ty {
+    for (i_src, i_stream, ty, codec) in sorted_src_stream_ty {
+        let src = &sources[i_src];
+        let OutCodec { id: codec, is_user } = codec;
+
+        let reencode = if cont.is_supported_copy(codec) {
+            None
+        } else if is_user {
+            // A user codec is never replaced by a fallback one.
+            logs::warn_container_does_not_support_codec(cont, codec, src, i_stream);
+            continue;
+        } else {
+            match cfg
+                .reencode_unsupported
+                .then(|| cont.fallback_codec(ty, codec))
+                .flatten()
+            {
+                Some(to) => {
+                    logs::info_reencode_unsupported_codec(cont, codec, to, src, i_stream);
+                    Some(to)
+                }
+                None => {
+                    logs::warn_container_does_not_support_codec(cont, codec, src, i_stream);
+                    continue;
+                }
+            }
+        };
         if !sup.is_supported(ty) {
-            logs::warn_container_does_not_support(cont, &sources[i_src], i_stream);
+            logs::warn_container_does_not_support(cont, src, i_stream);
             continue;
         }
         let (num, is_first) = num_is_first(i_src, &mut src_numbers, &mut src_num);
@@ -157,6 +238,9 @@ fn items(
             i_stream,
             src_num: num,
             is_first_entry: is_first,
+            reencode,
+            dispositions: None,
+            encoding: None,
         })
     }
 
@@ -224,6 +308,9 @@ fn try_order(mi: &mut MediaInfo, items: Vec<StreamsOrderItem>) -> Result<Streams
                 i_stream: rtm.i_stream,
                 src_num: num,
                 is_first_entry: is_first,
+                reencode: item.reencode,
+                dispositions: item.dispositions,
+                encoding: item.encoding.clone(),
             }
         })
         .collect();
@@ -251,7 +338,7 @@ struct OrderSortKey {
     ty: StreamType,
     default: u8,
     forced: u8,
-    it_signs: u8,
+    keyword: u8,
     lang: u8,
 }
 
@@ -260,7 +347,7 @@ impl OrderSortKey {
         ty: StreamType,
         default: Option<bool>,
         forced: Option<bool>,
-        it_signs: bool,
+        stream: &Stream,
         lang: &Lang,
         locale: LangCode,
     ) -> Self {
@@ -273,7 +360,15 @@ impl OrderSortKey {
         let default = flag_order(default);
         let forced = flag_order(forced);
 
-        let it_signs = if it_signs { 0 } else { 1 };
+        let keyword = if stream.is_forced_hint() {
+            0
+        } else if stream.keywords[Keyword::Commentary] {
+            3
+        } else if stream.keywords[Keyword::Sdh] {
+            2
+        } else {
+            1
+        };
 
         let lang = match lang {
             Lang::Code(c) if c == &locale => 0,
@@ -286,7 +381,7 @@ impl OrderSortKey {
             ty,
             default,
             forced,
-            it_signs,
+            keyword,
             lang,
         }
     }
@@ -297,7 +392,7 @@ impl PartialEq for OrderSortKey {
         self.ty == other.ty
             && self.default == other.default
             && self.forced == other.forced
-            && self.it_signs == other.it_signs
+            && self.keyword == other.keyword
             && self.lang == other.lang
     }
 }
@@ -310,11 +405,11 @@ impl PartialOrd for OrderSortKey {
 }
 impl Ord for OrderSortKey {
     fn cmp(&self, other: &Self) -> Ordering {
-        (self.ty, self.default, self.forced, self.it_signs, self.lang).cmp(&(
+        (self.ty, self.default, self.forced, self.keyword, self.lang).cmp(&(
             other.ty,
             other.default,
             other.forced,
-            other.it_signs,
+            other.keyword,
             other.lang,
         ))
     }
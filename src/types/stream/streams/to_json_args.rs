@@ -22,6 +22,13 @@ impl ToJsonArgs for Streams {
     }
 }
 
+impl Streams {
+    /// Returns a value of the CLI argument, e.g. `!0,eng`.
+    pub(crate) fn to_arg(&self) -> String {
+        arg(self)
+    }
+}
+
 fn arg(streams: &Streams) -> String {
     let mut s = std::collections::BTreeSet::<String>::new();
 
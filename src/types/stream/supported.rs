@@ -32,11 +32,11 @@ impl StreamsSupported {
     }
 }
 
-const fn is_supported_ty(sup: &StreamsSupported, _ty: StreamType) -> bool {
+const fn is_supported_ty(sup: &StreamsSupported, ty: StreamType) -> bool {
     match sup.container {
         //Container::Avi => matches!(ty, StreamType::Audio | StreamType::Video),
-        //Container::Mp4 => ty.is_track(),
+        Container::Mp4 => ty.is_track(),
         Container::Matroska => true,
-        //Container::Webm => matches!(ty, StreamType::Audio | StreamType::Video),
+        Container::Webm => ty.is_track(),
     }
 }
@@ -0,0 +1,17 @@
+mod density;
+mod extract;
+mod format;
+mod pgs;
+mod stretch;
+mod sync;
+mod ty;
+mod xs;
+
+pub use density::SubDensity;
+pub use extract::try_extract_sub;
+pub use format::SubFormat;
+pub use pgs::Pgs;
+pub use stretch::SubStretch;
+pub use sync::SubSync;
+pub use ty::SubType;
+pub use xs::Subs;
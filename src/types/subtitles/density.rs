@@ -0,0 +1,115 @@
+use super::Subs;
+use crate::{
+    Duration, IsDefault, Result,
+    ffmpeg::{Rational, Rescale, format},
+};
+use std::{path::Path, time};
+
+/// A density of subtitle cues.
+#[derive(Copy, Clone, Debug, Default, PartialEq, IsDefault)]
+pub struct SubDensity {
+    /// A number of cues.
+    pub cues: usize,
+
+    /// A total time any cue is shown. Overlapped cues count once.
+    pub shown: Duration,
+}
+
+impl SubDensity {
+    /// Max part of the video duration shown by sparse subs.
+    const MAX_SHOWN_OF_VIDEO: f64 = 0.15;
+
+    /// Max part of cues and shown time of the densest other subs.
+    const MAX_OF_OTHERS: f64 = 0.25;
+
+    /// Returns `true` if the subs are sparse (likely signs-only) against `others` subs
+    /// of the same video or against the `video` duration.
+    /// ```
+    /// use mux_media::{Duration, SubDensity};
+    ///
+    /// let full = SubDensity { cues: 400, shown: Duration::new(900, 0) };
+    /// let signs = SubDensity { cues: 30, shown: Duration::new(60, 0) };
+    /// let video = Some(Duration::new(1440, 0));
+    ///
+    /// assert!(signs.is_sparse([full], None));
+    /// assert!(signs.is_sparse([], video));
+    /// assert!(!full.is_sparse([signs], video));
+    /// ```
+    pub fn is_sparse(
+        &self,
+        others: impl IntoIterator<Item = SubDensity>,
+        video: Option<Duration>,
+    ) -> bool {
+        if self.cues == 0 {
+            return false;
+        }
+        let shown = self.shown.as_secs_f64();
+
+        let (max_cues, max_shown) = others.into_iter().fold((0, 0.0f64), |(c, s), d| {
+            (c.max(d.cues), s.max(d.shown.as_secs_f64()))
+        });
+
+        let by_others = max_cues > 0
+            && (self.cues as f64) < max_cues as f64 * Self::MAX_OF_OTHERS
+            && shown < max_shown * Self::MAX_OF_OTHERS;
+
+        let by_video = video
+            .map(|v| v.as_secs_f64())
+            .is_some_and(|v| v > 0.0 && shown < v * Self::MAX_SHOWN_OF_VIDEO);
+
+        by_others || by_video
+    }
+
+    /// Tries get a density of a text sub stream of the media file by its packets.
+    pub(crate) fn try_from_stream(src: &Path, i_stream: usize) -> Result<SubDensity> {
+        let mut ictx = format::input(&src)?;
+        let mut cues = Vec::new();
+
+        for (stream, packet) in ictx.packets() {
+            if stream.index() != i_stream {
+                continue;
+            }
+            let tb = stream.time_base();
+            let ms = |ts: i64| {
+                let ms = ts.rescale(tb, Rational(1, 1000)).max(0) as u64;
+                Duration::from(time::Duration::from_millis(ms))
+            };
+            let start = some_or!(packet.pts(), continue);
+            cues.push((ms(start), ms(start + packet.duration())));
+        }
+
+        Ok(Self::from_cues(cues))
+    }
+
+    fn from_cues(mut xs: Vec<(Duration, Duration)>) -> SubDensity {
+        xs.retain(|(start, end)| end > start);
+        xs.sort_by(|a, b| a.0.cmp(&b.0));
+
+        let mut shown = time::Duration::ZERO;
+        let mut last_end = Duration::default();
+
+        for (start, end) in xs.iter() {
+            let start = (*start).max(last_end);
+            if *end > start {
+                shown += end.0 - start.0;
+                last_end = *end;
+            }
+        }
+
+        SubDensity {
+            cues: xs.len(),
+            shown: Duration(shown),
+        }
+    }
+}
+
+impl Subs {
+    /// Returns a density of cues.
+    pub fn density(&self) -> SubDensity {
+        SubDensity::from_cues(
+            self.iter_i_start_end()
+                .map(|(_, start, end)| (Duration::from(start), Duration::from(end)))
+                .collect(),
+        )
+    }
+}
@@ -0,0 +1,44 @@
+use super::SubType;
+use crate::{
+    Result,
+    ffmpeg::{Rational, format},
+};
+use std::path::Path;
+
+/// Extracts a text subtitle stream to the `dest` file of the type.
+pub fn try_extract_sub(src: &Path, i_stream: usize, ty: SubType, dest: &Path) -> Result<()> {
+    let mut ictx = format::input(&src)?;
+    let istream = ictx
+        .stream(i_stream)
+        .ok_or_else(|| err!("invalid stream index"))?;
+
+    let out_time_base = match ty {
+        SubType::Ssa => Rational::new(1, 100),
+        _ => Rational::new(1, 1000),
+    };
+    let codec_id = istream.parameters().id();
+
+    let mut octx = format::output(&dest)?;
+
+    let ostream_index = {
+        let mut ostream = octx.add_stream(codec_id)?;
+        ostream.set_parameters(istream.parameters());
+        ostream.set_time_base(out_time_base);
+        ostream.index()
+    };
+
+    octx.write_header()?;
+
+    for (stream, mut packet) in ictx.packets() {
+        if stream.index() != i_stream {
+            continue;
+        }
+
+        packet.set_stream(ostream_index);
+        packet.rescale_ts(stream.time_base(), out_time_base);
+        packet.write(&mut octx)?;
+    }
+
+    octx.write_trailer()?;
+    Ok(())
+}
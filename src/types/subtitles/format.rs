@@ -0,0 +1,53 @@
+use super::{SubStretch, SubType};
+use crate::{IsDefault, Streams, ToJsonArgs};
+use std::path::PathBuf;
+
+/// A subtitle format configuration.
+#[derive(Clone, Debug, Default, PartialEq)]
+#[non_exhaustive]
+pub struct SubFormat {
+    /// A format to convert text subtitles into.
+    pub ty: Option<SubType>,
+
+    /// An ASS file to take styles from when converting into ASS.
+    pub style: Option<PathBuf>,
+
+    /// A stretch of subtitle timings.
+    pub stretch: Option<SubStretch>,
+
+    /// PGS sub streams to split forced captions from into a separate track.
+    pub split_forced_pgs: Option<Streams>,
+}
+
+impl IsDefault for SubFormat {
+    fn is_default(&self) -> bool {
+        self.ty.is_none()
+            && self.style.is_none()
+            && self.stretch.is_none()
+            && self.split_forced_pgs.is_none()
+    }
+}
+
+impl ToJsonArgs for SubFormat {
+    fn append_json_args(&self, args: &mut Vec<String>) {
+        if let Some(ty) = self.ty {
+            args.push(to_json_args!(SubFormat));
+            args.push(ty.to_string());
+        }
+
+        if let Some(style) = self.style.as_ref().and_then(|p| p.to_str()) {
+            args.push(to_json_args!(SubStyle));
+            args.push(style.to_owned());
+        }
+
+        if let Some(stretch) = self.stretch {
+            args.push(to_json_args!(SubStretch));
+            args.push(stretch.to_string());
+        }
+
+        if let Some(streams) = &self.split_forced_pgs {
+            args.push(to_json_args!(SplitForcedPgs));
+            args.push(streams.to_arg());
+        }
+    }
+}
@@ -0,0 +1,343 @@
+use crate::{
+    Duration, Result,
+    ffmpeg::{Rational, Rescale, format},
+};
+use std::{fs, path::Path};
+
+/// A PGS (Blu-ray bitmap) subtitle stream as a sequence of segments.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Pgs(Vec<PgsSegment>);
+
+#[derive(Clone, Debug, PartialEq)]
+struct PgsSegment {
+    /// Timestamps in 90 kHz ticks.
+    pts: u32,
+    dts: u32,
+    ty: u8,
+    data: Vec<u8>,
+}
+
+impl Pgs {
+    const MAGIC: &[u8; 2] = b"PG";
+    pub(crate) const TIME_BASE: Rational = Rational(1, 90_000);
+
+    /// Presentation composition segment.
+    const PCS: u8 = 0x16;
+    const PCS_HEADER_LEN: usize = 11;
+    const PCS_STATE: usize = 7;
+    const PCS_STATE_NORMAL: u8 = 0;
+    /// Window definition segment.
+    const WDS: u8 = 0x17;
+    const END: u8 = 0x80;
+    const OBJECT_LEN: usize = 8;
+    const OBJECT_CROPPED: u8 = 0x80;
+    const OBJECT_FORCED: u8 = 0x40;
+
+    /// Tries read a PGS sub stream of the media file.
+    ///
+    /// Negative timestamps are clamped to zero.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a timestamp exceeds the 32-bit 90 kHz range (about 13 hours).
+    pub fn try_from_stream(src: &Path, i_stream: usize) -> Result<Pgs> {
+        let mut ictx = format::input(&src)?;
+        let mut segments = Vec::new();
+
+        for (stream, packet) in ictx.packets() {
+            if stream.index() != i_stream {
+                continue;
+            }
+            let tb = stream.time_base();
+            let ts = |ts: Option<i64>| {
+                let t = ts.map_or(0, |t| t.rescale(tb, Self::TIME_BASE)).max(0);
+                u32::try_from(t).map_err(|_| err!("PGS timestamp {} is out of 90 kHz range", t))
+            };
+            let pts = ts(packet.pts())?;
+            let dts = ts(packet.dts().or(packet.pts()))?;
+
+            let mut data = packet.data().unwrap_or_default();
+            while !data.is_empty() {
+                let (ty, len) = match data {
+                    [ty, hi, lo, ..] => (*ty, u16::from_be_bytes([*hi, *lo]) as usize),
+                    _ => return Err(err!("Truncated PGS segment header")),
+                };
+                let seg = data
+                    .get(3..3 + len)
+                    .ok_or_else(|| err!("Truncated PGS segment"))?;
+                segments.push(PgsSegment {
+                    pts,
+                    dts,
+                    ty,
+                    data: seg.to_vec(),
+                });
+                data = &data[3 + len..];
+            }
+        }
+
+        Ok(Pgs(segments))
+    }
+
+    /// Tries parse a `.sup` file content.
+    pub fn try_from_sup(mut bytes: &[u8]) -> Result<Pgs> {
+        let mut segments = Vec::new();
+
+        while !bytes.is_empty() {
+            if bytes.len() < 13 || &bytes[..2] != Self::MAGIC {
+                return Err(err!("Invalid PGS segment header"));
+            }
+            let u32_at =
+                |i: usize| u32::from_be_bytes([bytes[i], bytes[i + 1], bytes[i + 2], bytes[i + 3]]);
+            let len = u16::from_be_bytes([bytes[11], bytes[12]]) as usize;
+            let data = bytes
+                .get(13..13 + len)
+                .ok_or_else(|| err!("Truncated PGS segment"))?;
+
+            segments.push(PgsSegment {
+                pts: u32_at(2),
+                dts: u32_at(6),
+                ty: bytes[10],
+                data: data.to_vec(),
+            });
+            bytes = &bytes[13 + len..];
+        }
+
+        Ok(Pgs(segments))
+    }
+
+    /// Returns a `.sup` file content.
+    pub fn to_sup(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(self.0.iter().map(|s| 13 + s.data.len()).sum());
+        for s in self.0.iter() {
+            bytes.extend_from_slice(Self::MAGIC);
+            bytes.extend_from_slice(&s.pts.to_be_bytes());
+            bytes.extend_from_slice(&s.dts.to_be_bytes());
+            bytes.push(s.ty);
+            bytes.extend_from_slice(&(s.data.len() as u16).to_be_bytes());
+            bytes.extend_from_slice(&s.data);
+        }
+        bytes
+    }
+
+    /// Tries write the stream to the `.sup` file.
+    pub fn try_write(&self, dest: &Path) -> Result<()> {
+        fs::write(dest, self.to_sup())?;
+        Ok(())
+    }
+
+    /// Returns composition objects as `(pts, is_forced)`.
+    pub fn objects(&self) -> Vec<(Duration, bool)> {
+        self.0
+            .iter()
+            .filter(|s| s.ty == Self::PCS)
+            .flat_map(|s| {
+                let pts = Duration::from_secs_f64(s.pts as f64 / 90_000.0);
+                composition_objects(&s.data)
+                    .unwrap_or_default()
+                    .into_iter()
+                    .map(move |(_, flags)| (pts, flags & Self::OBJECT_FORCED != 0))
+            })
+            .collect()
+    }
+
+    /// Returns a copy that shows forced objects only, or [`None`] if there's none.
+    ///
+    /// Every display set is kept, so epochs and clearing of the screen stay as is;
+    /// non-forced objects are only removed from the compositions.
+    pub fn forced_only(&self) -> Result<Option<Pgs>> {
+        let mut is_any = false;
+        let mut segments = self.0.clone();
+
+        for s in segments.iter_mut().filter(|s| s.ty == Self::PCS) {
+            let objects = composition_objects(&s.data)?;
+            let mut data = s.data[..Self::PCS_HEADER_LEN].to_vec();
+            let mut num = 0u8;
+
+            for (range, flags) in objects {
+                if flags & Self::OBJECT_FORCED != 0 {
+                    data.extend_from_slice(&s.data[range]);
+                    num += 1;
+                }
+            }
+
+            data[Self::PCS_HEADER_LEN - 1] = num;
+            is_any |= num > 0;
+            s.data = data;
+        }
+
+        Ok(is_any.then_some(Pgs(segments)))
+    }
+
+    /// Returns a copy of the `start..end` part, shifted to zero.
+    ///
+    /// A caption showing at `start` is kept from zero along with the display sets
+    /// of its epoch, and a caption showing at `end` is cleared at the part end.
+    pub fn retimed(&self, start: Duration, end: Duration) -> Pgs {
+        let ticks = |d: Duration| (d.as_secs_f64() * 90_000.0).round().min(u32::MAX as f64) as u32;
+        let (start, end) = (ticks(start), ticks(end));
+
+        let mut sets = self.display_sets().into_iter().peekable();
+        let mut epoch = Vec::new();
+        while let Some(set) = sets.next_if(|set| set[0].pts < start) {
+            if set[0].is_refresh() {
+                epoch.clear();
+            }
+            epoch.push(set);
+        }
+
+        let mut segments = Vec::new();
+        let mut last: Option<&[PgsSegment]> = None;
+
+        if epoch.last().is_some_and(|set| set[0].is_shows()) {
+            for set in epoch {
+                segments.extend(set.iter().map(|s| s.at(0)));
+                last = Some(set);
+            }
+        }
+
+        for set in sets {
+            if set[0].pts >= end {
+                match last {
+                    Some(last) if last[0].is_shows() && set[0].ty == Self::PCS => {
+                        let ts = end.saturating_sub(start);
+                        if set[0].is_shows() {
+                            segments.extend(clear_set(last, ts));
+                        } else {
+                            segments.extend(set.iter().map(|s| s.at(ts)));
+                        }
+                    }
+                    _ => {}
+                }
+                break;
+            }
+            segments.extend(set.iter().map(|s| s.shifted(start)));
+            last = Some(set);
+        }
+
+        Pgs(segments)
+    }
+
+    /// Returns display sets as `(pts, data)` packets, one per set as in Matroska.
+    pub(crate) fn packets(&self) -> Vec<(u32, Vec<u8>)> {
+        self.display_sets()
+            .into_iter()
+            .map(|set| {
+                let mut data = Vec::with_capacity(set.iter().map(|s| 3 + s.data.len()).sum());
+                for s in set {
+                    data.push(s.ty);
+                    data.extend_from_slice(&(s.data.len() as u16).to_be_bytes());
+                    data.extend_from_slice(&s.data);
+                }
+                (set[0].pts, data)
+            })
+            .collect()
+    }
+
+    /// Returns segments grouped by display sets, each started by a PCS.
+    fn display_sets(&self) -> Vec<&[PgsSegment]> {
+        let mut sets = Vec::new();
+        let mut i_start = 0;
+
+        for i in 1..self.0.len() {
+            if self.0[i].ty == Self::PCS {
+                sets.push(&self.0[i_start..i]);
+                i_start = i;
+            }
+        }
+        if i_start < self.0.len() {
+            sets.push(&self.0[i_start..]);
+        }
+
+        sets
+    }
+}
+
+/// Returns a display set clearing the screen after the `last` one.
+fn clear_set(last: &[PgsSegment], ts: u32) -> Vec<PgsSegment> {
+    let mut pcs = last[0].data[..Pgs::PCS_HEADER_LEN].to_vec();
+    let number = u16::from_be_bytes([pcs[5], pcs[6]]).wrapping_add(1);
+    pcs[5..7].copy_from_slice(&number.to_be_bytes());
+    pcs[Pgs::PCS_STATE] = Pgs::PCS_STATE_NORMAL;
+    pcs[Pgs::PCS_STATE + 1] = 0;
+    pcs[Pgs::PCS_HEADER_LEN - 1] = 0;
+
+    let segment = |ty: u8, data: Vec<u8>| PgsSegment {
+        pts: ts,
+        dts: ts,
+        ty,
+        data,
+    };
+
+    let mut set = vec![segment(Pgs::PCS, pcs)];
+    if let Some(wds) = last.iter().find(|s| s.ty == Pgs::WDS) {
+        set.push(segment(Pgs::WDS, wds.data.clone()));
+    }
+    set.push(segment(Pgs::END, Vec::new()));
+    set
+}
+
+impl PgsSegment {
+    fn at(&self, ts: u32) -> PgsSegment {
+        PgsSegment {
+            pts: ts,
+            dts: ts,
+            ty: self.ty,
+            data: self.data.clone(),
+        }
+    }
+
+    fn shifted(&self, start: u32) -> PgsSegment {
+        PgsSegment {
+            pts: self.pts.saturating_sub(start),
+            dts: self.dts.saturating_sub(start),
+            ty: self.ty,
+            data: self.data.clone(),
+        }
+    }
+
+    /// Whether it's a composition showing any object.
+    fn is_shows(&self) -> bool {
+        self.ty == Pgs::PCS
+            && self
+                .data
+                .get(Pgs::PCS_HEADER_LEN - 1)
+                .is_some_and(|num| *num > 0)
+    }
+
+    /// Whether it's a composition of an epoch start or an acquisition point,
+    /// which defines every object it shows.
+    fn is_refresh(&self) -> bool {
+        self.ty == Pgs::PCS
+            && self
+                .data
+                .get(Pgs::PCS_STATE)
+                .is_some_and(|state| *state != Pgs::PCS_STATE_NORMAL)
+    }
+}
+
+/// Returns byte ranges and flags of composition objects of the PCS data.
+fn composition_objects(data: &[u8]) -> Result<Vec<(std::ops::Range<usize>, u8)>> {
+    let num = *data
+        .get(Pgs::PCS_HEADER_LEN - 1)
+        .ok_or_else(|| err!("Truncated PGS composition"))?;
+
+    let mut objects = Vec::with_capacity(num as usize);
+    let mut i = Pgs::PCS_HEADER_LEN;
+
+    for _ in 0..num {
+        let flags = *data
+            .get(i + 3)
+            .ok_or_else(|| err!("Truncated PGS composition object"))?;
+        let len = match flags & Pgs::OBJECT_CROPPED {
+            0 => Pgs::OBJECT_LEN,
+            _ => Pgs::OBJECT_LEN * 2,
+        };
+        if data.len() < i + len {
+            return Err(err!("Truncated PGS composition object"));
+        }
+        objects.push((i..i + len, flags));
+        i += len;
+    }
+
+    Ok(objects)
+}
@@ -0,0 +1,87 @@
+use crate::{Duration, MuxError, Result};
+use std::{fmt, str::FromStr};
+
+/// A subtitle timings stretch.
+///
+/// Parses `auto`, a ratio or a pair of frame rates `from:to`.
+/// ```
+/// use mux_media::SubStretch;
+///
+/// assert_eq!(SubStretch::Auto, "auto".parse().unwrap());
+/// assert_eq!(SubStretch::Ratio(1.5), "1.5".parse().unwrap());
+/// assert_eq!(SubStretch::Ratio(25.0 / 24.0), "25:24".parse().unwrap());
+/// ```
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum SubStretch {
+    /// Detect a frame rate ratio by speech of the base audio on sub sync.
+    ///
+    /// Without sync, only suggests a ratio by the video duration.
+    Auto,
+
+    /// Multiply all timings by the ratio.
+    Ratio(f64),
+}
+
+impl SubStretch {
+    /// Frame rates to detect ratios between.
+    const FPS: [f64; 5] = [24000.0 / 1001.0, 24.0, 25.0, 30000.0 / 1001.0, 30.0];
+
+    /// Max difference of a detected ratio from a known one.
+    const AUTO_TOLERANCE: f64 = 0.01;
+
+    /// Returns the nearest frame rate ratio for subtitles ending at `last_end`
+    /// against a video of `video` duration.
+    ///
+    /// Returns [`None`] if no known ratio is close enough.
+    pub fn detect_ratio(last_end: Duration, video: Duration) -> Option<f64> {
+        let last_end = last_end.as_secs_f64();
+        if last_end <= 0.0 {
+            return None;
+        }
+        let raw = video.as_secs_f64() / last_end;
+
+        Self::fps_ratios()
+            .map(|r| (r, (r - raw).abs()))
+            .filter(|(_, diff)| *diff < Self::AUTO_TOLERANCE)
+            .min_by(|a, b| a.1.total_cmp(&b.1))
+            .map(|(r, _)| r)
+    }
+
+    /// Returns ratios between known frame rates, including `1.0`.
+    pub fn fps_ratios() -> impl Iterator<Item = f64> {
+        Self::FPS
+            .iter()
+            .flat_map(|from| Self::FPS.iter().map(move |to| from / to))
+    }
+}
+
+impl FromStr for SubStretch {
+    type Err = MuxError;
+
+    fn from_str(s: &str) -> Result<SubStretch> {
+        let s = s.trim();
+        if s.eq_ignore_ascii_case("auto") {
+            return Ok(Self::Auto);
+        }
+
+        let ratio = match s.split_once(':') {
+            Some((from, to)) => from.trim().parse::<f64>()? / to.trim().parse::<f64>()?,
+            None => s.parse::<f64>()?,
+        };
+
+        if !ratio.is_finite() || ratio <= 0.0 {
+            return Err(err!("Stretch ratio must be a positive number"));
+        }
+
+        Ok(Self::Ratio(ratio))
+    }
+}
+
+impl fmt::Display for SubStretch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Auto => write!(f, "auto"),
+            Self::Ratio(r) => write!(f, "{}", r),
+        }
+    }
+}
@@ -0,0 +1,144 @@
+use crate::{
+    Delay, Duration, Result,
+    types::audio_sync::{AudioSync, cross_correlate, try_decode_mono},
+};
+use std::{path::Path, time::Duration as StdDuration};
+
+/// A detected alignment of subtitle cues with speech of the base audio.
+///
+/// Timings are multiplied by `ratio` first, then shifted by `offset`.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct SubSync {
+    pub offset: Delay,
+    pub ratio: f64,
+    /// How much the best offset outweighs the next best one, from 0 to 1.
+    pub confidence: f64,
+}
+
+impl SubSync {
+    /// A length of analyzed audio frames.
+    pub const FRAME: StdDuration = StdDuration::from_millis(20);
+
+    /// A max detected offset in either direction.
+    pub const MAX_OFFSET: StdDuration = StdDuration::from_secs(120);
+
+    /// A confidence below which a detected alignment isn't applied.
+    ///
+    /// Speech and cues are binary, so shifted lags of cues unrelated to the speech
+    /// still score close to the best one.
+    pub const MIN_CONFIDENCE: f64 = 0.3;
+
+    /// Offsets closer than this to the best one are the same peak.
+    const PEAK_WIDTH: StdDuration = StdDuration::from_millis(500);
+
+    /// Speech pauses shorter than this are bridged.
+    const MAX_PAUSE: StdDuration = StdDuration::from_millis(200);
+
+    /// A min range of frame energies in dB to detect any speech.
+    const MIN_RANGE_DB: f64 = 6.0;
+
+    /// Detects speech in mono samples at `rate` by frame energy.
+    ///
+    /// A frame is speech if its energy is above the middle between quiet and loud frames.
+    /// Returns a flag per [`SubSync::FRAME`].
+    pub fn detect_speech(samples: &[f32], rate: u32) -> Vec<bool> {
+        let len = ((Self::FRAME.as_secs_f64() * rate as f64) as usize).max(1);
+
+        let dbs: Vec<f64> = samples
+            .chunks(len)
+            .map(|xs| {
+                let power = xs.iter().map(|x| (*x as f64).powi(2)).sum::<f64>() / xs.len() as f64;
+                10.0 * (power + 1e-12).log10()
+            })
+            .collect();
+
+        if dbs.is_empty() {
+            return Vec::new();
+        }
+
+        let mut sorted = dbs.clone();
+        sorted.sort_by(f64::total_cmp);
+        let quiet = sorted[sorted.len() / 10];
+        let loud = sorted[sorted.len() * 9 / 10];
+
+        if loud - quiet < Self::MIN_RANGE_DB {
+            return vec![false; dbs.len()];
+        }
+
+        let threshold = (quiet + loud) / 2.0;
+        let mut speech: Vec<bool> = dbs.iter().map(|db| *db > threshold).collect();
+        bridge_pauses(&mut speech, Self::frames(Self::MAX_PAUSE));
+
+        speech
+    }
+
+    /// Finds the offset and one of `ratios` which best align `cues` with `speech` frames.
+    ///
+    /// Returns [`None`] if there's no speech or no cues.
+    pub fn new(speech: &[bool], cues: &[(Duration, Duration)], ratios: &[f64]) -> Option<SubSync> {
+        let speech: Vec<f32> = speech.iter().map(|s| *s as u8 as f32).collect();
+        let max_lag = Self::frames(Self::MAX_OFFSET);
+        let peak_width = Self::frames(Self::PEAK_WIDTH);
+
+        ratios
+            .iter()
+            .filter_map(|ratio| {
+                let xs = cue_frames(cues, *ratio);
+                let len = xs.iter().filter(|x| **x > 0.0).count();
+                let peak = cross_correlate(&speech, &xs, max_lag, peak_width)?;
+
+                let sync = SubSync {
+                    offset: Delay(peak.lag as i64 * Self::FRAME.as_millis() as i64),
+                    ratio: *ratio,
+                    confidence: peak.confidence,
+                };
+                // Longer cues of bigger ratios mustn't outweigh by length.
+                Some((sync, peak.value / len as f64))
+            })
+            .max_by(|a, b| a.1.total_cmp(&b.1))
+            .map(|(sync, _)| sync)
+    }
+
+    /// Tries detect speech frames of the audio stream up to `duration`.
+    pub(crate) fn try_speech(src: &Path, i_stream: usize, duration: Duration) -> Result<Vec<bool>> {
+        let samples = try_decode_mono(src, i_stream, *duration)?;
+        Ok(Self::detect_speech(&samples, AudioSync::SAMPLE_RATE))
+    }
+
+    fn frames(d: StdDuration) -> usize {
+        (d.as_secs_f64() / Self::FRAME.as_secs_f64()) as usize
+    }
+}
+
+fn bridge_pauses(speech: &mut [bool], max_pause: usize) {
+    let mut last_speech: Option<usize> = None;
+
+    for i in 0..speech.len() {
+        if !speech[i] {
+            continue;
+        }
+        if let Some(last) = last_speech {
+            if i - last - 1 <= max_pause {
+                speech[last + 1..i].iter_mut().for_each(|s| *s = true);
+            }
+        }
+        last_speech = Some(i);
+    }
+}
+
+fn cue_frames(cues: &[(Duration, Duration)], ratio: f64) -> Vec<f32> {
+    let frame = SubSync::FRAME.as_secs_f64();
+    let to_frame = |d: &Duration| (d.as_secs_f64() * ratio / frame).round() as usize;
+
+    let len = cues.iter().map(|(_, end)| to_frame(end)).max().unwrap_or(0);
+    let mut xs = vec![0f32; len];
+
+    for (start, end) in cues {
+        let (start, end) = (to_frame(start), to_frame(end));
+        if start < end {
+            xs[start..end].iter_mut().for_each(|x| *x = 1.0);
+        }
+    }
+
+    xs
+}
@@ -0,0 +1,90 @@
+use crate::{CodecId, Extension, MediaInfo, MuxError, Result, markers::MIStreams};
+use std::{fmt, path::Path, str::FromStr};
+
+/// A text subtitle format.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum SubType {
+    Srt,
+    Ssa,
+    Vtt,
+}
+
+impl SubType {
+    pub fn new_from_path(file: &Path) -> Option<SubType> {
+        let ext = Extension::new_from_path(file)?;
+        Self::new_from_extension(ext)
+    }
+
+    pub fn new_from_extension(ext: Extension) -> Option<SubType> {
+        match ext {
+            Extension::Ass | Extension::Ssa => Some(Self::Ssa),
+            Extension::Srt => Some(Self::Srt),
+            Extension::Vtt => Some(Self::Vtt),
+            _ => None,
+        }
+    }
+
+    pub(crate) fn from_codec_id(mi: &MediaInfo, src: &Path, i_stream: usize) -> SubType {
+        let c = *mi
+            .immut(MIStreams, src)
+            .map(|xs| &xs[i_stream].codec)
+            .unwrap_or(&CodecId::default());
+
+        Self::new_from_codec(c).unwrap_or_else(|| {
+            log::warn!(
+                "Unsupported codec {:?} of sub stream {}. Try retime as .srt",
+                c,
+                i_stream
+            );
+            SubType::Srt
+        })
+    }
+
+    pub(crate) fn new_from_codec(codec: CodecId) -> Option<SubType> {
+        use crate::ffmpeg::codec::id::Id;
+
+        match codec.0 {
+            Id::ASS | Id::SSA => Some(Self::Ssa),
+            Id::SRT | Id::SUBRIP => Some(Self::Srt),
+            Id::WEBVTT => Some(Self::Vtt),
+            _ => None,
+        }
+    }
+
+    pub(crate) fn codec_id(self) -> CodecId {
+        use crate::ffmpeg::codec::id::Id;
+
+        match self {
+            Self::Srt => CodecId(Id::SUBRIP),
+            Self::Ssa => CodecId(Id::ASS),
+            Self::Vtt => CodecId(Id::WEBVTT),
+        }
+    }
+
+    pub const fn as_ext(self) -> &'static str {
+        match self {
+            Self::Srt => "srt",
+            Self::Ssa => "ass",
+            Self::Vtt => "vtt",
+        }
+    }
+}
+
+impl FromStr for SubType {
+    type Err = MuxError;
+
+    fn from_str(s: &str) -> Result<SubType> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "srt" => Ok(Self::Srt),
+            "ass" | "ssa" => Ok(Self::Ssa),
+            "vtt" | "webvtt" => Ok(Self::Vtt),
+            _ => Err(err!("Unsupported subtitle format '{}'", s)),
+        }
+    }
+}
+
+impl fmt::Display for SubType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_ext())
+    }
+}
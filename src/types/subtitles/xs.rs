@@ -0,0 +1,133 @@
+use super::SubType;
+use crate::{Duration, Result};
+use rsubs_lib::SSA;
+use std::{fs, io::Write, path::Path};
+use time::Time;
+
+/// Parsed text subtitles.
+#[derive(Debug)]
+pub enum Subs {
+    Srt(rsubs_lib::SRT),
+    Ssa(rsubs_lib::SSA),
+    Vtt(rsubs_lib::VTT),
+}
+
+impl Subs {
+    pub fn new(src: &Path, ty: SubType) -> Result<Subs> {
+        let s = fs::read_to_string(src)?;
+        let subs = match ty {
+            SubType::Srt => Subs::Srt(rsubs_lib::SRT::parse(s)?),
+            SubType::Ssa => Subs::Ssa(rsubs_lib::SSA::parse(s)?),
+            SubType::Vtt => Subs::Vtt(rsubs_lib::VTT::parse(s)?),
+        };
+        Ok(subs)
+    }
+}
+
+macro_rules! box_iter {
+    ($sub:ident, $field:ident) => {
+        Box::new(
+            $sub.$field
+                .iter()
+                .enumerate()
+                .map(|(i, f)| (i, f.start.into(), f.end.into())),
+        )
+    };
+}
+
+impl Subs {
+    pub fn iter_i_start_end(&self) -> Box<dyn Iterator<Item = (usize, Time, Time)> + '_> {
+        match self {
+            Self::Srt(sub) => box_iter!(sub, lines),
+            Self::Ssa(sub) => box_iter!(sub, events),
+            Self::Vtt(sub) => box_iter!(sub, lines),
+        }
+    }
+
+    pub fn try_write(&self, dest: &Path) -> Result<()> {
+        let mut file = fs::File::create(dest)?;
+        match self {
+            Self::Srt(s) => write!(file, "{}", s),
+            Self::Ssa(s) => write!(file, "{}", s),
+            Self::Vtt(s) => write!(file, "{}", s),
+        }?;
+        Ok(())
+    }
+}
+
+impl Subs {
+    pub fn ty(&self) -> SubType {
+        match self {
+            Self::Srt(_) => SubType::Srt,
+            Self::Ssa(_) => SubType::Ssa,
+            Self::Vtt(_) => SubType::Vtt,
+        }
+    }
+
+    /// Converts subtitles to the type.
+    ///
+    /// If converts to ASS (including from ASS), takes styles from the `style` template.
+    pub fn convert(self, ty: SubType, style: Option<&SSA>) -> Subs {
+        let mut new = match (self, ty) {
+            (Self::Srt(s), SubType::Ssa) => Self::Ssa(s.to_ssa()),
+            (Self::Srt(s), SubType::Vtt) => Self::Vtt(s.to_vtt()),
+            (Self::Ssa(s), SubType::Srt) => Self::Srt(s.to_srt()),
+            (Self::Ssa(s), SubType::Vtt) => Self::Vtt(s.to_vtt()),
+            (Self::Vtt(s), SubType::Srt) => Self::Srt(s.to_srt()),
+            (Self::Vtt(s), SubType::Ssa) => Self::Ssa(s.to_ssa()),
+            (subs, _) => subs,
+        };
+
+        if let (Self::Ssa(ssa), Some(style)) = (&mut new, style) {
+            apply_style(ssa, style);
+        }
+
+        new
+    }
+}
+
+impl Subs {
+    /// Returns the end of the last cue.
+    pub fn last_end(&self) -> Option<Duration> {
+        self.iter_i_start_end()
+            .map(|(_, _, end)| Duration::from(end))
+            .max()
+    }
+
+    /// Multiplies all timings by the ratio.
+    pub fn stretch(&mut self, ratio: f64) {
+        let f = |t: &mut Time| {
+            let secs = Duration::from(*t).as_secs_f64() * ratio;
+            *t = Duration::from_secs_f64(secs).into();
+        };
+
+        match self {
+            Self::Srt(s) => s.lines.iter_mut().for_each(|l| {
+                f(&mut l.start);
+                f(&mut l.end);
+            }),
+            Self::Ssa(s) => s.events.iter_mut().for_each(|e| {
+                f(&mut e.start);
+                f(&mut e.end);
+            }),
+            Self::Vtt(s) => s.lines.iter_mut().for_each(|l| {
+                f(&mut l.start);
+                f(&mut l.end);
+            }),
+        }
+    }
+}
+
+fn apply_style(ssa: &mut SSA, style: &SSA) {
+    let name = match style.styles.iter().find(|s| s.name == "Default") {
+        Some(_) => None,
+        None => style.styles.first().map(|s| s.name.clone()),
+    };
+
+    ssa.info = style.info.clone();
+    ssa.styles = style.styles.clone();
+
+    if let Some(name) = name {
+        ssa.events.iter_mut().for_each(|e| e.style = name.clone());
+    }
+}
@@ -47,6 +47,16 @@ pub fn temp(add: impl AsRef<OsStr>) -> PathBuf {
     data(p)
 }
 
+/// Writes the content to the file in the temp area, creating its dirs.
+pub fn temp_file(add: impl AsRef<OsStr>, content: impl AsRef<[u8]>) -> PathBuf {
+    let path = temp(add);
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir).unwrap();
+    }
+    std::fs::write(&path, content).unwrap();
+    path
+}
+
 pub fn cfg<I, OS>(args: I) -> Config
 where
     I: IntoIterator<Item = OS>,
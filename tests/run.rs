@@ -30,3 +30,509 @@ macro_rules! test_mux_any {
 }
 
 test_mux_any!(test_mux_matroska, "x1_set/", "mux/matroska/,.mkv");
+
+// Returns (first pts, last pts) in seconds of every video stream of the file.
+fn video_times(path: &std::path::Path) -> Vec<(f64, f64)> {
+    use ffmpeg_next::{format, media::Type};
+
+    let mut ictx = format::input(path).unwrap();
+    let mut times: Vec<Option<(f64, f64)>> = vec![None; ictx.nb_streams() as usize];
+
+    for (ist, packet) in ictx.packets() {
+        if ist.parameters().medium() != Type::Video {
+            continue;
+        }
+        let Some(pts) = packet.pts() else {
+            continue;
+        };
+        let tb = ist.time_base();
+        let secs = pts as f64 * tb.0 as f64 / tb.1 as f64;
+        let t = times[ist.index()].get_or_insert((secs, secs));
+        *t = (t.0.min(secs), t.1.max(secs));
+    }
+
+    times.into_iter().flatten().collect()
+}
+
+#[test]
+fn test_retime_extra_videos() {
+    let input = temp("mux/retime_x8/in/");
+    temp_file(
+        "mux/retime_x8/in/video_x8.mkv",
+        fs::read(data("video_x8.mkv")).unwrap(),
+    );
+    let out_arg = temp("mux/retime_x8/out/,.mkv");
+
+    let mut c = cfg([
+        p("-i"),
+        &input,
+        p("-o"),
+        &out_arg,
+        p("-e"),
+        p("--cut"),
+        p("0.2-0.8"),
+    ]);
+    c.try_finalize_init().unwrap();
+
+    let out = c.output.build_out("video_x8");
+    let _ = fs::remove_file(&out);
+    c.mux().unwrap();
+
+    let times = video_times(&out);
+    assert_eq!(8, times.len());
+
+    // Every extra track is placed at the same boundaries as the base one.
+    let (start, end) = times[0];
+    assert!(end > start);
+    for (s, e) in times.into_iter().skip(1) {
+        assert!((s - start).abs() < 0.05, "{} != {}", s, start);
+        assert!((e - end).abs() < 0.05, "{} != {}", e, end);
+    }
+}
+
+// Returns sorted pts in seconds of the first video stream.
+fn video_pts(path: &std::path::Path) -> Vec<f64> {
+    use ffmpeg_next::{format, media::Type};
+
+    let mut ictx = format::input(path).unwrap();
+    let ist = ictx.streams().best(Type::Video).unwrap();
+    let (i_stream, tb) = (ist.index(), ist.time_base());
+
+    let mut pts: Vec<f64> = ictx
+        .packets()
+        .filter(|(ist, _)| ist.index() == i_stream)
+        .filter_map(|(_, packet)| packet.pts())
+        .map(|pts| pts as f64 * tb.0 as f64 / tb.1 as f64)
+        .collect();
+    pts.sort_by(|a, b| a.partial_cmp(b).unwrap());
+    pts
+}
+
+#[test]
+fn test_smart_cut() {
+    // Frames of video_x1.mkv are 0.04 s long, keyframes are at 0, 0.48 and 0.96.
+    [
+        ("head_tail", "0.2-0.8", 15),
+        ("keyframes", "0.48-0.96", 12),
+        ("within_gop", "0.6-0.72", 3),
+    ]
+    .into_iter()
+    .for_each(|(dir, cut, frames)| {
+        let input = temp(format!("mux/smart_cut/{}/in/", dir));
+        temp_file(
+            format!("mux/smart_cut/{}/in/video_x1.mkv", dir),
+            fs::read(data("video_x1.mkv")).unwrap(),
+        );
+        let out_arg = temp(format!("mux/smart_cut/{}/out/,.mkv", dir));
+
+        let mut c = cfg([
+            p("-i"),
+            &input,
+            p("-o"),
+            &out_arg,
+            p("-e"),
+            p("--smart-cut"),
+            p("--cut"),
+            p(cut),
+        ]);
+        c.try_finalize_init().unwrap();
+
+        let out = c.output.build_out("video_x1");
+        let _ = fs::remove_file(&out);
+        c.mux().unwrap();
+
+        // Every frame of the range is kept and the first one is at zero.
+        let pts = video_pts(&out);
+        assert_eq!(frames, pts.len(), "cut {}", cut);
+        assert!(pts[0].abs() < 0.002, "cut {}: {}", cut, pts[0]);
+        let last = 0.04 * (frames - 1) as f64;
+        assert!((pts[frames - 1] - last).abs() < 0.002, "cut {}", cut);
+    });
+}
+
+// Writes the video of video_x1.mkv with a second of AAC noise at 48 kHz.
+fn write_video_aac(path: &std::path::Path) {
+    use ffmpeg_next::{
+        ChannelLayout, Packet, Rational, codec, encoder,
+        format::{self, Sample, sample::Type},
+        frame,
+    };
+    const RATE: i32 = 48000;
+
+    let mut ictx = format::input(&data("video_x1.mkv")).unwrap();
+    let mut octx = format::output(&path).unwrap();
+
+    let (ist_index, ist_tb) = {
+        let ist = ictx.streams().next().unwrap();
+        octx.add_stream(codec::Id::None)
+            .unwrap()
+            .set_parameters(ist.parameters());
+        (ist.index(), ist.time_base())
+    };
+
+    let aac = encoder::find(codec::Id::AAC).unwrap();
+    let mut enc = codec::Context::new_with_codec(aac)
+        .encoder()
+        .audio()
+        .unwrap();
+    enc.set_rate(RATE);
+    enc.set_channel_layout(ChannelLayout::MONO);
+    enc.set_format(Sample::F32(Type::Planar));
+    enc.set_time_base((1, RATE));
+    enc.set_flags(codec::Flags::GLOBAL_HEADER);
+    let mut enc = enc.open_as(aac).unwrap();
+    octx.add_stream(aac).unwrap().set_parameters(&enc);
+    octx.write_header().unwrap();
+
+    let vtb = octx.stream(0).unwrap().time_base();
+    let atb = octx.stream(1).unwrap().time_base();
+
+    for (ist, mut packet) in ictx.packets() {
+        if ist.index() != ist_index {
+            continue;
+        }
+        packet.rescale_ts(ist_tb, vtb);
+        packet.set_position(-1);
+        packet.set_stream(0);
+        packet.write_interleaved(&mut octx).unwrap();
+    }
+
+    // Priming packets before zero are dropped.
+    let receive = |enc: &mut encoder::audio::Encoder, octx: &mut format::context::Output| {
+        let mut packet = Packet::empty();
+        while enc.receive_packet(&mut packet).is_ok() {
+            if packet.pts().is_none_or(|pts| pts < 0) {
+                continue;
+            }
+            packet.rescale_ts(Rational(1, RATE), atb);
+            packet.set_stream(1);
+            packet.write_interleaved(octx).unwrap();
+        }
+    };
+
+    let frame_size = enc.frame_size() as usize;
+    let mut seed = 1u32;
+    for i in 0..RATE as usize / frame_size + 1 {
+        let mut frame =
+            frame::Audio::new(Sample::F32(Type::Planar), frame_size, ChannelLayout::MONO);
+        for x in frame.plane_mut::<f32>(0) {
+            seed = seed.wrapping_mul(1103515245).wrapping_add(12345);
+            *x = (seed >> 16) as f32 / 65536.0 - 0.5;
+        }
+        frame.set_rate(RATE as u32);
+        frame.set_pts(Some((i * frame_size) as i64));
+        enc.send_frame(&frame).unwrap();
+        receive(&mut enc, &mut octx);
+    }
+    enc.send_eof().unwrap();
+    receive(&mut enc, &mut octx);
+
+    octx.write_trailer().unwrap();
+}
+
+// Returns (first pts, duration) in seconds of the decoded first audio stream.
+fn audio_times(path: &std::path::Path) -> (f64, f64) {
+    use ffmpeg_next::{codec, format, frame, media::Type};
+
+    let mut ictx = format::input(path).unwrap();
+    let ist = ictx.streams().best(Type::Audio).unwrap();
+    let (i_stream, tb) = (ist.index(), ist.time_base());
+    let mut dec = codec::Context::from_parameters(ist.parameters())
+        .unwrap()
+        .decoder()
+        .audio()
+        .unwrap();
+    dec.set_packet_time_base(tb);
+
+    let mut first = None;
+    let mut samples = 0;
+    let mut frame = frame::Audio::empty();
+    let mut receive = |dec: &mut ffmpeg_next::decoder::Audio| {
+        while dec.receive_frame(&mut frame).is_ok() {
+            if let Some(ts) = frame.timestamp() {
+                first.get_or_insert(ts as f64 * tb.0 as f64 / tb.1 as f64);
+            }
+            samples += frame.samples();
+        }
+    };
+
+    for (ist, packet) in ictx.packets() {
+        if ist.index() == i_stream {
+            dec.send_packet(&packet).unwrap();
+            receive(&mut dec);
+        }
+    }
+    dec.send_eof().unwrap();
+    receive(&mut dec);
+
+    (first.unwrap(), samples as f64 / dec.rate() as f64)
+}
+
+#[test]
+fn test_exact_audio() {
+    // AAC frames of 1024 samples at 48 kHz straddle every cut start.
+    [
+        ("single", "0.2-0.8", 0.6),
+        ("multi", "0.1-0.3,0.5-0.8", 0.5),
+    ]
+    .into_iter()
+    .for_each(|(dir, cut, len)| {
+        let input = temp(format!("mux/exact_audio/{}/in/", dir));
+        let src = temp_file(format!("mux/exact_audio/{}/in/video_aac.mkv", dir), "");
+        write_video_aac(&src);
+        let out_arg = temp(format!("mux/exact_audio/{}/out/,.mkv", dir));
+
+        let mut c = cfg([
+            p("-i"),
+            &input,
+            p("-o"),
+            &out_arg,
+            p("-e"),
+            p("--smart-cut"),
+            p("--exact-audio"),
+            p("--cut"),
+            p(cut),
+        ]);
+        c.try_finalize_init().unwrap();
+
+        let out = c.output.build_out("video_aac");
+        let _ = fs::remove_file(&out);
+        c.mux().unwrap();
+
+        // Audio starts at zero with no gap and lasts as the kept ranges.
+        let (first, duration) = audio_times(&out);
+        assert!(first.abs() < 0.002, "cut {}: {}", cut, first);
+        assert!((duration - len).abs() < 0.002, "cut {}: {}", cut, duration);
+    });
+}
+
+#[test]
+fn test_retime_edition() {
+    // Editions of the file: "Director's Cut" ordered of 0.2-0.8, ordered of 0-0.4
+    // titled "Theatrical" by a tag, and "Markers" not ordered.
+    [
+        ("num", "1", 10),
+        ("name", "director's cut", 15),
+        ("tag", "THEATRICAL", 10),
+        ("not_ordered", "Markers", 25),
+    ]
+    .into_iter()
+    .for_each(|(dir, edition, frames)| {
+        let input = temp(format!("mux/edition/{}/in/", dir));
+        temp_file(
+            format!("mux/edition/{}/in/vid_x1_editions_x3.mkv", dir),
+            fs::read(data("vid_x1_editions_x3.mkv")).unwrap(),
+        );
+        let out_arg = temp(format!("mux/edition/{}/out/,.mkv", dir));
+
+        let mut c = cfg([
+            p("-i"),
+            &input,
+            p("-o"),
+            &out_arg,
+            p("-e"),
+            p("--smart-cut"),
+            p("--edition"),
+            p(edition),
+        ]);
+        c.try_finalize_init().unwrap();
+
+        let out = c.output.build_out("vid_x1_editions_x3");
+        let _ = fs::remove_file(&out);
+        c.mux().unwrap();
+
+        assert_eq!(frames, video_pts(&out).len(), "edition {}", edition);
+    });
+}
+
+#[test]
+fn test_retime_linked_segment() {
+    // An ordered edition of the file: its own 0-0.4 and 0-0.48 of the segment of
+    // video_x1.mkv, which is found in --segments-dir only.
+    let input = temp("mux/linked/in/");
+    temp_file(
+        "mux/linked/in/vid_x1_linked_x1.mkv",
+        fs::read(data("vid_x1_linked_x1.mkv")).unwrap(),
+    );
+    let segments = temp("mux/linked/segments/");
+    let external = temp_file(
+        "mux/linked/segments/video_x1.mkv",
+        fs::read(data("video_x1.mkv")).unwrap(),
+    );
+    let gone = temp("mux/linked/gone.mkv");
+    let index = temp_file(
+        "mux/linked/index.json",
+        format!(
+            r#"{{"{}": {{"mtime": 1, "size": 1, "uid": "00"}}}}"#,
+            gone.display().to_string().replace('\\', "\\\\")
+        ),
+    );
+    let out_arg = temp("mux/linked/out/,.mkv");
+
+    let mut c = cfg([
+        p("-i"),
+        &input,
+        p("-o"),
+        &out_arg,
+        p("-e"),
+        p("--segments-dir"),
+        &segments,
+        p("--segments-cache"),
+        &index,
+    ]);
+    c.try_finalize_init().unwrap();
+
+    let out = c.output.build_out("vid_x1_linked_x1");
+    let _ = fs::remove_file(&out);
+    c.mux().unwrap();
+
+    // Parts within 10 s of file bounds are kept whole.
+    assert_eq!(50, video_pts(&out).len());
+
+    // The index keeps the found segment and drops files that no longer exist.
+    let json: serde_json::Value = serde_json::from_slice(&fs::read(&index).unwrap()).unwrap();
+    let files = json.as_object().unwrap();
+    assert!(!files.keys().any(|k| k.ends_with("gone.mkv")));
+    let (_, f) = files
+        .iter()
+        .find(|(k, _)| k.ends_with("video_x1.mkv"))
+        .unwrap();
+    assert_eq!("659240e266fc1122675ee164c9fd81d0", f["uid"]);
+    assert_eq!(fs::metadata(&external).unwrap().len(), f["size"]);
+}
+
+#[test]
+fn test_mux_codecs() {
+    use ffmpeg_next::{codec::Id, format, media::Type};
+
+    [
+        ("aac", Some(Id::AAC)),
+        ("flac", Some(Id::FLAC)),
+        ("not-a-codec", None),
+    ]
+    .into_iter()
+    .for_each(|(codec, expected)| {
+        let input = temp(format!("mux/codecs/{}/in/", codec));
+        temp_file(
+            format!("mux/codecs/{}/in/audio_x1.mka", codec),
+            fs::read(data("audio_x1.mka")).unwrap(),
+        );
+        let out_arg = temp(format!("mux/codecs/{}/out/,.mkv", codec));
+
+        let mut c = cfg([
+            p("-i"),
+            &input,
+            p("-o"),
+            &out_arg,
+            p("-e"),
+            p("--target"),
+            p("audio"),
+            p("--codecs"),
+            p(codec),
+        ]);
+        c.try_finalize_init().unwrap();
+
+        let out = c.output.build_out("audio_x1");
+        let _ = fs::remove_file(&out);
+
+        // An unknown codec is an error, not a copy of the source one.
+        let Some(expected) = expected else {
+            c.mux().unwrap_err();
+            assert!(!out.exists());
+            return;
+        };
+        c.mux().unwrap();
+
+        let ictx = format::input(&out).unwrap();
+        let ost = ictx.streams().best(Type::Audio).unwrap();
+        assert_eq!(expected, ost.parameters().id(), "codec {}", codec);
+    });
+}
+
+#[test]
+fn test_dry_run() {
+    let input = temp("mux/dry_run/in/");
+    let src = temp_file(
+        "mux/dry_run/in/audio_x1.mka",
+        fs::read(data("audio_x1.mka")).unwrap(),
+    );
+    let out_arg = temp("mux/dry_run/out/,.mkv");
+
+    let mut c = cfg([
+        p("-i"),
+        &input,
+        p("-o"),
+        &out_arg,
+        p("-e"),
+        p("--dry-run"),
+        p("--target"),
+        p("audio"),
+        p("--codecs"),
+        p("aac"),
+        p("--delays"),
+        p("120"),
+    ]);
+    c.try_finalize_init().unwrap();
+
+    // An existing output is listed too and is left as is.
+    let out = c.output.build_out("audio_x1");
+    temp_file("mux/dry_run/out/audio_x1.mkv", "");
+    assert!(out.exists());
+    assert_eq!(1, c.mux().unwrap());
+    assert_eq!(0, fs::metadata(&out).unwrap().len());
+
+    let mut mi = MediaInfo::new(&c, 0);
+    mi.set_cmn(markers::MICmnStem, "audio_x1".into());
+    mi.try_insert(&src).unwrap();
+    mi.try_finalize_init_streams().unwrap();
+
+    let layout = mi.dry_run_layout(&out).unwrap();
+    assert!(layout.contains("vorbis -> aac"), "{}", layout);
+    assert!(layout.contains("120ms"), "{}", layout);
+}
+
+#[test]
+fn test_mux_delays() {
+    use ffmpeg_next::media::Type;
+
+    // Cues of 1-3 s and 4-6 s; the first one is shifted to start before zero.
+    let input = temp("mux/delays/in/");
+    temp_file(
+        "mux/delays/in/cues.srt",
+        "1\n00:00:01,000 --> 00:00:03,000\nFirst\n\n2\n00:00:04,000 --> 00:00:06,000\nSecond\n",
+    );
+    let out_arg = temp("mux/delays/out/,.mkv");
+
+    let mut c = cfg([
+        p("-i"),
+        &input,
+        p("-o"),
+        &out_arg,
+        p("-e"),
+        p("--delays"),
+        p("0:-2000"),
+    ]);
+    c.try_finalize_init().unwrap();
+
+    let out = c.output.build_out("cues");
+    let _ = fs::remove_file(&out);
+    c.mux().unwrap();
+
+    // The cue is clamped to zero, keeping its end, instead of being dropped.
+    let mut ictx = ffmpeg_next::format::input(&out).unwrap();
+    let times: Vec<(f64, f64)> = ictx
+        .packets()
+        .filter(|(ist, _)| ist.parameters().medium() == Type::Subtitle)
+        .map(|(ist, packet)| {
+            let tb = ist.time_base();
+            let secs = |ts: i64| ts as f64 * tb.0 as f64 / tb.1 as f64;
+            let pts = packet.pts().unwrap();
+            (secs(pts), secs(pts + packet.duration()))
+        })
+        .collect();
+
+    assert_eq!(2, times.len());
+    assert!(times[0].0.abs() < 0.002, "{:?}", times);
+    assert!((times[0].1 - 1.0).abs() < 0.002, "{:?}", times);
+    assert!((times[1].0 - 2.0).abs() < 0.002, "{:?}", times);
+}
@@ -1,8 +1,12 @@
 mod common;
 mod macros;
 
+#[path = "types/audio_sync.rs"]
+mod audio_sync;
 #[path = "types/auto_flags.rs"]
 mod auto_flags;
+#[path = "types/bitrate.rs"]
+mod bitrate;
 #[path = "types/chapters.rs"]
 mod chapters;
 #[path = "types/char_encoding.rs"]
@@ -13,12 +17,16 @@ mod cli_arg;
 mod config;
 #[path = "types/container.rs"]
 mod container;
+#[path = "types/delay.rs"]
+mod delay;
 #[path = "types/dispositions.rs"]
 mod dispositions;
 #[path = "types/extension.rs"]
 mod extension;
 #[path = "types/input.rs"]
 mod input;
+#[path = "types/keyword.rs"]
+mod keyword;
 #[path = "types/lang_code.rs"]
 mod lang_code;
 #[path = "types/log_level.rs"]
@@ -31,13 +39,25 @@ mod media_number;
 mod metadata;
 #[path = "types/mux_error.rs"]
 mod mux_error;
+#[path = "types/mux_plan.rs"]
+mod mux_plan;
 #[path = "types/output.rs"]
 mod output;
+#[path = "types/pgs.rs"]
+mod pgs;
 #[path = "types/range.rs"]
 mod range;
 #[path = "types/streams.rs"]
 mod streams;
 #[path = "types/streams_order.rs"]
 mod streams_order;
+#[path = "types/sub_density.rs"]
+mod sub_density;
+#[path = "types/sub_stretch.rs"]
+mod sub_stretch;
+#[path = "types/sub_sync.rs"]
+mod sub_sync;
+#[path = "types/sub_type.rs"]
+mod sub_type;
 #[path = "types/targets.rs"]
 mod targets;
@@ -0,0 +1,68 @@
+use mux_media::*;
+
+const RATE: u32 = AudioSync::SAMPLE_RATE;
+
+fn noise(len: usize) -> Vec<f32> {
+    let mut x = 1u32;
+    (0..len)
+        .map(|_| {
+            x = x.wrapping_mul(1103515245).wrapping_add(12345);
+            (x >> 16) as f32 / 65536.0 - 0.5
+        })
+        .collect()
+}
+
+fn shifted(xs: &[f32], lag: isize) -> Vec<f32> {
+    if lag >= 0 {
+        xs[lag as usize..].to_vec()
+    } else {
+        let mut ys = vec![0.0; (-lag) as usize];
+        ys.extend_from_slice(xs);
+        ys
+    }
+}
+
+#[test]
+fn test_detect_delay() {
+    let base = noise(RATE as usize * 30);
+
+    [(0, 0), (400, 100), (-1200, -300), (RATE as isize * 5, 5000)]
+        .iter()
+        .for_each(|(lag, ms)| {
+            let other = shifted(&base, *lag);
+            let sync = AudioSync::new(&base, &other, RATE).unwrap();
+            assert_eq!(Delay(*ms), sync.delay, "lag {}", lag);
+            assert!(sync.confidence > 0.9, "lag {}", lag);
+        });
+}
+
+#[test]
+fn test_detect_delay_of_noisy() {
+    let base = noise(RATE as usize * 30);
+    let hiss = noise(base.len() + 7);
+    let other: Vec<f32> = shifted(&base, 800)
+        .iter()
+        .zip(&hiss[7..])
+        .map(|(x, h)| x + 0.5 * h)
+        .collect();
+
+    let sync = AudioSync::new(&base, &other, RATE).unwrap();
+    assert_eq!(Delay(200), sync.delay);
+    assert!(sync.confidence >= AudioSync::MIN_CONFIDENCE);
+}
+
+#[test]
+fn test_low_confidence_of_periodic() {
+    let base: Vec<f32> = (0..RATE * 10).map(|i| (i as f32 * 0.1).sin()).collect();
+    let sync = AudioSync::new(&base, &base, RATE).unwrap();
+    assert!(sync.confidence < AudioSync::MIN_CONFIDENCE);
+}
+
+#[test]
+fn test_none_of_empty_or_silent() {
+    let base = noise(RATE as usize);
+    assert_eq!(None, AudioSync::new(&base, &[], RATE));
+    assert_eq!(None, AudioSync::new(&[], &base, RATE));
+    assert_eq!(None, AudioSync::new(&base, &vec![0.0; base.len()], RATE));
+    assert_eq!(None, AudioSync::new(&base, &base, 0));
+}
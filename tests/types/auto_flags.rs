@@ -11,9 +11,11 @@ fn test_empty() {
     assert_eq!(false, f.pro);
     assert_eq!(Value::Auto(true), f.defaults);
     assert_eq!(Value::Auto(true), f.forceds);
+    assert_eq!(Value::Auto(true), f.dispositions);
     assert_eq!(Value::Auto(true), f.names);
     assert_eq!(Value::Auto(true), f.langs);
     assert_eq!(Value::Auto(true), f.encs);
+    assert_eq!(Value::Auto(true), f.delays);
 }
 
 #[test]
@@ -22,9 +24,11 @@ fn test_pro() {
     assert_eq!(true, f.pro);
     assert_eq!(Value::Auto(false), f.defaults);
     assert_eq!(Value::Auto(false), f.forceds);
+    assert_eq!(Value::Auto(false), f.dispositions);
     assert_eq!(Value::Auto(false), f.names);
     assert_eq!(Value::Auto(false), f.langs);
     assert_eq!(Value::Auto(false), f.encs);
+    assert_eq!(Value::Auto(false), f.delays);
 }
 
 #[test]
@@ -32,9 +36,11 @@ fn test_manual_on() {
     let v = Value::User(true);
     assert_eq!(v, new(&["--auto-defaults"]).defaults);
     assert_eq!(v, new(&["--auto-forceds"]).forceds);
+    assert_eq!(v, new(&["--auto-dispositions"]).dispositions);
     assert_eq!(v, new(&["--auto-names"]).names);
     assert_eq!(v, new(&["--auto-langs"]).langs);
     assert_eq!(v, new(&["--auto-encs"]).encs);
+    assert_eq!(v, new(&["--auto-delays"]).delays);
 }
 
 #[test]
@@ -42,9 +48,11 @@ fn test_manual_off() {
     let v = Value::User(false);
     assert_eq!(v, new(&["--no-auto-defaults"]).defaults);
     assert_eq!(v, new(&["--no-auto-forceds"]).forceds);
+    assert_eq!(v, new(&["--no-auto-dispositions"]).dispositions);
     assert_eq!(v, new(&["--no-auto-names"]).names);
     assert_eq!(v, new(&["--no-auto-langs"]).langs);
     assert_eq!(v, new(&["--no-auto-encs"]).encs);
+    assert_eq!(v, new(&["--no-auto-delays"]).delays);
 }
 
 #[test]
@@ -52,9 +60,37 @@ fn test_manual_on_with_pro() {
     let v = Value::User(true);
     assert_eq!(v, new(&["--pro", "--auto-defaults"]).defaults);
     assert_eq!(v, new(&["--pro", "--auto-forceds"]).forceds);
+    assert_eq!(v, new(&["--pro", "--auto-dispositions"]).dispositions);
     assert_eq!(v, new(&["--pro", "--auto-names"]).names);
     assert_eq!(v, new(&["--pro", "--auto-langs"]).langs);
     assert_eq!(v, new(&["--pro", "--auto-encs"]).encs);
+    assert_eq!(v, new(&["--pro", "--auto-delays"]).delays);
+}
+
+#[test]
+fn test_off_on_plan_in() {
+    let mut cfg = cfg([
+        p("-i"),
+        &data(""),
+        p("--plan-in"),
+        p("plan.json"),
+        p("--auto-defaults"),
+        p("--sync-audio"),
+        p("--sync-subs"),
+    ]);
+    cfg.try_finalize_init().unwrap();
+
+    let f = cfg.auto_flags;
+    assert_eq!(true, f.pro);
+    assert_eq!(Value::User(false), f.defaults);
+    assert_eq!(Value::User(false), f.forceds);
+    assert_eq!(Value::User(false), f.dispositions);
+    assert_eq!(Value::User(false), f.names);
+    assert_eq!(Value::User(false), f.langs);
+    assert_eq!(Value::Auto(true), f.encs);
+    assert_eq!(Value::User(false), f.delays);
+    assert!(!cfg.sync_audio);
+    assert!(!cfg.sync_subs);
 }
 
 crate::build_test_to_json_args!(
@@ -62,12 +98,16 @@ crate::build_test_to_json_args!(
     vec![],
     vec!["--no-auto-defaults"],
     vec!["--no-auto-forceds"],
+    vec!["--no-auto-dispositions"],
     vec!["--no-auto-names"],
     vec!["--no-auto-langs"],
     vec!["--no-auto-encs"],
+    vec!["--no-auto-delays"],
     vec!["--pro", "--auto-defaults"],
     vec!["--pro", "--auto-forceds"],
+    vec!["--pro", "--auto-dispositions"],
     vec!["--pro", "--auto-names"],
     vec!["--pro", "--auto-langs"],
     vec!["--pro", "--auto-encs"],
+    vec!["--pro", "--auto-delays"],
 );
@@ -0,0 +1,30 @@
+use mux_media::*;
+
+#[test]
+fn test_from_str() {
+    [
+        (640, "640"),
+        (192_000, "192k"),
+        (192_000, "192K"),
+        (1_500_000, "1.5M"),
+        (2_000_000, "2m"),
+    ]
+    .iter()
+    .for_each(|(num, s)| {
+        assert_eq!(Bitrate(*num), s.parse::<Bitrate>().unwrap(), "from '{}'", s);
+    });
+}
+
+#[test]
+fn test_from_str_err() {
+    ["", "0", "k", "x", "-192k", "1.5G"]
+        .iter()
+        .for_each(|s| assert!(s.parse::<Bitrate>().is_err(), "from '{}'", s));
+}
+
+#[test]
+fn test_display() {
+    [(640, "640"), (192_000, "192k"), (2_000_000, "2M")]
+        .iter()
+        .for_each(|(num, s)| assert_eq!(*s, Bitrate(*num).to_string()));
+}
@@ -1,7 +1,7 @@
 use crate::common::*;
 use clap::{error::ErrorKind, *};
 use mux_media::{markers::*, *};
-use std::{fs, sync::LazyLock};
+use std::{fs, path::PathBuf, sync::LazyLock};
 
 static EMPTY_ARGS: LazyLock<Config> = LazyLock::new(|| cfg::<_, &str>([]));
 
@@ -36,13 +36,26 @@ fn parse_empty_args() {
     assert!(!e.exit_on_err);
     assert!(!e.save_config);
     assert!(!e.reencode);
+    assert!(!e.reencode_unsupported);
+    assert!(!e.dry_run);
+    assert!(!e.sync_audio);
+    assert!(!e.sync_subs);
+    assert_eq!(None, e.plan_in);
+    assert_eq!(None, e.plan_out);
     assert_eq!(1, e.jobs);
     assert_eq!(&e.auto_flags, &Default::default());
     assert_eq!(&e.streams, &Default::default());
     assert_eq!(&e.defaults, &Default::default());
     assert_eq!(&e.forceds, &Default::default());
+    assert_eq!(&e.hearing_impaireds, &Default::default());
+    assert_eq!(&e.comments, &Default::default());
+    assert_eq!(&e.originals, &Default::default());
+    assert_eq!(&e.visual_impaireds, &Default::default());
+    assert_eq!(&e.dubs, &Default::default());
     assert_eq!(&e.names, &Default::default());
     assert_eq!(&e.langs, &Default::default());
+    assert_eq!(&e.codecs, &Default::default());
+    assert_eq!(&e.bitrates, &Default::default());
     assert_eq!(&e.retiming_options, &Default::default());
     assert_eq!(&e.targets, &Default::default());
     assert_eq!(&e.container, &Default::default());
@@ -133,6 +146,20 @@ fn parse_global() {
     test_parse!(["-e"], exit_on_err, true);
     test_parse!(["--save-config"], save_config, true);
     test_parse!(["--reencode"], reencode, true);
+    test_parse!(["--reencode-unsupported"], reencode_unsupported, true);
+    test_parse!(["--dry-run"], dry_run, true);
+    test_parse!(["--sync-audio"], sync_audio, true);
+    test_parse!(["--sync-subs"], sync_subs, true);
+    test_parse!(
+        ["--plan-in", "plan.json"],
+        plan_in,
+        Some("plan.json".into())
+    );
+    test_parse!(
+        ["--plan-out", "plan.json"],
+        plan_out,
+        Some("plan.json".into())
+    );
     test_parse!(["--jobs", "8"], jobs, 8);
 }
 
@@ -206,7 +233,26 @@ fn parse_dispositions() {
         defaults,
         DefaultDispositions(xs.clone())
     );
-    test_parse!(["--forceds", "true"], forceds, ForcedDispositions(xs));
+    test_parse!(
+        ["--forceds", "true"],
+        forceds,
+        ForcedDispositions(xs.clone())
+    );
+    test_parse!(
+        ["--hearing-impaireds", "true"],
+        hearing_impaireds,
+        HearingImpairedDispositions(xs.clone())
+    );
+    test_parse!(
+        ["--originals", "true"],
+        originals,
+        OriginalDispositions(xs.clone())
+    );
+    test_parse!(
+        ["--visual-impaireds", "true"],
+        visual_impaireds,
+        VisualImpairedDispositions(xs)
+    );
 
     let xs = Dispositions {
         max_in_auto: Some(1),
@@ -217,7 +263,17 @@ fn parse_dispositions() {
         defaults,
         DefaultDispositions(xs.clone())
     );
-    test_parse!(["--max-forceds", "1"], forceds, ForcedDispositions(xs));
+    test_parse!(
+        ["--max-forceds", "1"],
+        forceds,
+        ForcedDispositions(xs.clone())
+    );
+    test_parse!(
+        ["--max-comments", "1"],
+        comments,
+        CommentDispositions(xs.clone())
+    );
+    test_parse!(["--max-dubs", "1"], dubs, DubDispositions(xs));
 }
 
 #[test]
@@ -238,6 +294,55 @@ fn parse_langs() {
     test_parse!(["--langs", "eng"], langs, LangMetadata(xs));
 }
 
+#[test]
+fn parse_codecs() {
+    let xs = Metadata {
+        single_val: Some(String::from("aac")),
+        ..Default::default()
+    };
+    test_parse!(["--codecs", "aac"], codecs, CodecMetadata(xs));
+}
+
+#[test]
+fn parse_bitrates() {
+    let xs = Metadata {
+        single_val: Some(Bitrate(192_000)),
+        ..Default::default()
+    };
+    test_parse!(["--bitrates", "192k"], bitrates, BitrateMetadata(xs));
+}
+
+#[test]
+fn parse_delays() {
+    let xs = Metadata {
+        single_val: Some(Delay(-42)),
+        ..Default::default()
+    };
+    test_parse!(["--delays", "-42ms"], delays, DelayMetadata(xs));
+}
+
+#[test]
+fn parse_sub_format() {
+    let mut fmt = SubFormat::default();
+    fmt.ty = Some(SubType::Ssa);
+    test_parse!(["--sub-format", "ass"], sub_format, fmt.clone());
+
+    fmt.style = Some(PathBuf::from("style.ass"));
+    test_parse!(
+        ["--sub-format", "ass", "--sub-style", "style.ass"],
+        sub_format,
+        fmt.clone()
+    );
+
+    let mut fmt = SubFormat::default();
+    fmt.stretch = Some(SubStretch::Ratio(25.0 / 24.0));
+    test_parse!(["--sub-stretch", "25:24"], sub_format, fmt);
+
+    let mut fmt = SubFormat::default();
+    fmt.split_forced_pgs = Some("0,eng".parse::<Streams>().unwrap());
+    test_parse!(["--split-forced-pgs", "0,eng"], sub_format, fmt);
+}
+
 #[test]
 fn parse_retiming_options() {
     let mut parts = RetimingOptionsParts::default();
@@ -248,6 +353,70 @@ fn parse_retiming_options() {
     test_parse!(["--parts", "!x"], retiming_options.parts, parts);
 
     test_parse!(["--no-linked"], retiming_options.no_linked, true);
+    test_parse!(["--smart-cut"], retiming_options.smart_cut, true);
+    test_parse!(["--exact-audio"], retiming_options.exact_audio, true);
+
+    let dir = fs::canonicalize(".").unwrap();
+    let dir_str = dir.to_str().unwrap();
+    let mut segments = RetimingOptionsSegments::default();
+    segments.dirs = vec![dir.clone(), dir.clone()];
+    test_parse!(
+        ["--segments-dir", dir_str, "--segments-dir", dir_str],
+        retiming_options.segments,
+        segments
+    );
+
+    let mut segments = RetimingOptionsSegments::default();
+    segments.cache = Some("x.json".into());
+    test_parse!(
+        ["--segments-cache", "x.json"],
+        retiming_options.segments,
+        segments
+    );
+
+    let mut cut = RetimingOptionsCut::default();
+    cut.ranges = vec![
+        (Duration::new(5, 0), Some(Duration::new(90, 500_000_000))),
+        (Duration::new(3600, 0), None),
+    ];
+    test_parse!(
+        ["--cut", "5-1:30.5,1:00:00-"],
+        retiming_options.cut,
+        cut.clone()
+    );
+
+    cut.inverse = true;
+    cut.ranges = vec![(Duration::default(), Some(Duration::new(60, 0)))];
+    test_parse!(["--cut", "!-60"], retiming_options.cut, cut);
+
+    let ed = Some(RetimingEdition::Num(1));
+    test_parse!(["--edition", "1"], retiming_options.edition, ed);
+    let ed = Some(RetimingEdition::Name("Director's Cut".into()));
+    test_parse!(
+        ["--edition", "Director's Cut"],
+        retiming_options.edition,
+        ed
+    );
+
+    ["", "x", "10", "10-5", "5-5", "00:x-10"]
+        .iter()
+        .for_each(|s| assert!(s.parse::<RetimingOptionsCut>().is_err()));
+}
+
+#[test]
+fn test_cut_save_ranges() {
+    let d = |secs| Duration::new(secs, 0);
+    let mut cut: RetimingOptionsCut = "50-70,10-20,15-30,90-".parse().unwrap();
+    assert_eq!(
+        vec![(d(10), d(30)), (d(50), d(70)), (d(90), d(100))],
+        cut.save_ranges(d(100))
+    );
+
+    cut.inverse = true;
+    assert_eq!(
+        vec![(d(0), d(10)), (d(30), d(50)), (d(70), d(90))],
+        cut.save_ranges(d(100))
+    );
 }
 
 #[test]
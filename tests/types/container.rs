@@ -5,9 +5,10 @@ use mux_media::*;
 fn test_from_output() {
     [
         (Container::Matroska, "avi"),
-        (Container::Matroska, "mp4"),
+        (Container::Mp4, "mp4"),
         (Container::Matroska, "mkv"),
-        (Container::Matroska, "webm"),
+        (Container::Webm, "webm"),
+        (Container::Webm, "weba"),
         (Container::Matroska, "x"),
         (Container::Matroska, "abc"),
         (Container::Matroska, "rand"),
@@ -19,3 +20,185 @@ fn test_from_output() {
         assert_eq!(expected, Container::new(&out));
     })
 }
+
+#[test]
+fn test_is_supported_copy() {
+    use ffmpeg_next::codec::Id;
+
+    [
+        Id::AAC,
+        Id::AC3,
+        Id::EAC3,
+        Id::FLAC,
+        Id::MP3,
+        Id::OPUS,
+        Id::H264,
+        Id::HEVC,
+        Id::AV1,
+        Id::MPEG4,
+        Id::MOV_TEXT,
+        Id::DVD_SUBTITLE,
+        Id::VORBIS,
+        Id::DTS,
+        Id::SUBRIP,
+        Id::HDMV_PGS_SUBTITLE,
+    ]
+    .into_iter()
+    .for_each(|id| {
+        assert!(
+            Container::Matroska.is_supported_copy(CodecId(id)),
+            "{:?}",
+            id
+        )
+    });
+
+    [
+        (true, Id::AAC),
+        (true, Id::AAC_LATM),
+        (true, Id::AC3),
+        (true, Id::EAC3),
+        (true, Id::ALAC),
+        (true, Id::FLAC),
+        (true, Id::MP2),
+        (true, Id::MP3),
+        (true, Id::OPUS),
+        (true, Id::AV1),
+        (true, Id::H264),
+        (true, Id::HEVC),
+        (true, Id::MPEG2VIDEO),
+        (true, Id::MPEG4),
+        (true, Id::VP9),
+        (true, Id::DVD_SUBTITLE),
+        (true, Id::MOV_TEXT),
+        (false, Id::VORBIS),
+        (false, Id::DTS),
+        (false, Id::TRUEHD),
+        (false, Id::PCM_S16LE),
+        (false, Id::VP8),
+        (false, Id::ASS),
+        (false, Id::SUBRIP),
+        (false, Id::WEBVTT),
+        (false, Id::HDMV_PGS_SUBTITLE),
+        (false, Id::TTF),
+    ]
+    .into_iter()
+    .for_each(|(expected, id)| {
+        assert_eq!(
+            expected,
+            Container::Mp4.is_supported_copy(CodecId(id)),
+            "{:?}",
+            id
+        );
+    });
+
+    [
+        (true, Id::OPUS),
+        (true, Id::VORBIS),
+        (true, Id::AV1),
+        (true, Id::VP8),
+        (true, Id::VP9),
+        (true, Id::WEBVTT),
+        (false, Id::AAC),
+        (false, Id::FLAC),
+        (false, Id::H264),
+        (false, Id::HEVC),
+        (false, Id::SUBRIP),
+        (false, Id::ASS),
+        (false, Id::HDMV_PGS_SUBTITLE),
+    ]
+    .into_iter()
+    .for_each(|(expected, id)| {
+        assert_eq!(
+            expected,
+            Container::Webm.is_supported_copy(CodecId(id)),
+            "{:?}",
+            id
+        );
+    });
+}
+
+#[test]
+fn test_fallback_codec() {
+    use ffmpeg_next::{codec::Id, encoder};
+
+    let first_encoder = |ids: &[Id]| ids.iter().find(|id| encoder::find(**id).is_some()).copied();
+    let fallback = |cont: Container, ty, id| cont.fallback_codec(ty, CodecId(id)).map(|c| c.0);
+
+    [
+        (
+            Container::Mp4,
+            StreamType::Audio,
+            Id::VORBIS,
+            &[Id::AAC, Id::AC3, Id::MP3][..],
+        ),
+        (
+            Container::Mp4,
+            StreamType::Video,
+            Id::VP8,
+            &[Id::H264, Id::HEVC, Id::MPEG4],
+        ),
+        (Container::Mp4, StreamType::Sub, Id::SUBRIP, &[Id::MOV_TEXT]),
+        (
+            Container::Webm,
+            StreamType::Audio,
+            Id::AAC,
+            &[Id::OPUS, Id::VORBIS],
+        ),
+        (
+            Container::Webm,
+            StreamType::Video,
+            Id::H264,
+            &[Id::VP9, Id::VP8, Id::AV1],
+        ),
+        (Container::Webm, StreamType::Sub, Id::ASS, &[Id::WEBVTT]),
+    ]
+    .into_iter()
+    .for_each(|(cont, ty, id, candidates)| {
+        assert_eq!(
+            first_encoder(candidates),
+            fallback(cont, ty, id),
+            "{:?}",
+            id
+        );
+    });
+
+    [
+        (Container::Matroska, StreamType::Audio, Id::VORBIS),
+        (Container::Matroska, StreamType::Video, Id::VP8),
+        (Container::Mp4, StreamType::Sub, Id::HDMV_PGS_SUBTITLE),
+        (Container::Webm, StreamType::Sub, Id::DVD_SUBTITLE),
+        (Container::Mp4, StreamType::Attach, Id::TTF),
+    ]
+    .into_iter()
+    .for_each(|(cont, ty, id)| {
+        assert_eq!(None, fallback(cont, ty, id), "{:?}", id);
+    });
+}
+
+fn mp4_order(args: &[&str]) -> StreamsOrder {
+    let mut cfg = cfg(args);
+    cfg.container = Container::Mp4;
+    let mut mi = MediaInfo::new(&cfg, 0);
+    mi.try_insert(data("audio_x1.mka")).unwrap();
+    StreamsOrder::new(&mut mi).unwrap()
+}
+
+#[test]
+fn test_drop_unsupported_codec() {
+    assert!(mp4_order(&[]).is_empty());
+}
+
+#[test]
+fn test_reencode_unsupported_codec() {
+    let order = mp4_order(&["--reencode-unsupported"]);
+    let fallback =
+        Container::Mp4.fallback_codec(StreamType::Audio, CodecId(ffmpeg_next::codec::Id::VORBIS));
+
+    match fallback {
+        Some(to) => {
+            assert_eq!(1, order.len());
+            assert_eq!(Some(to.0), order[0].reencode.map(|c| c.0));
+        }
+        None => assert!(order.is_empty()),
+    }
+}
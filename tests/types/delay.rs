@@ -0,0 +1,31 @@
+use mux_media::*;
+
+#[test]
+fn test_from_str() {
+    [
+        (0, "0"),
+        (120, "120"),
+        (120, "+120"),
+        (120, "120ms"),
+        (-42, "-42ms"),
+        (-42, "-42 MS"),
+    ]
+    .iter()
+    .for_each(|(num, s)| {
+        assert_eq!(Delay(*num), s.parse::<Delay>().unwrap(), "from '{}'", s);
+    });
+}
+
+#[test]
+fn test_from_str_err() {
+    ["", "ms", "x", "1.5", "120s"]
+        .iter()
+        .for_each(|s| assert!(s.parse::<Delay>().is_err(), "from '{}'", s));
+}
+
+#[test]
+fn test_display() {
+    [(0, "0"), (120, "120"), (-42, "-42")]
+        .iter()
+        .for_each(|(num, s)| assert_eq!(*s, Delay(*num).to_string()));
+}
@@ -9,6 +9,36 @@ fn parse_default() {
     assert_eq!(xs, cfg.forceds.0);
 }
 
+#[test]
+fn parse_other_types() {
+    let single = Dispositions {
+        single_val: Some(true),
+        ..Default::default()
+    };
+    let max = Dispositions {
+        max_in_auto: Some(2),
+        ..Default::default()
+    };
+
+    for (arg, max_arg, get) in [
+        (
+            "--hearing-impaireds",
+            "--max-hearing-impaireds",
+            (|c: Config| c.hearing_impaireds.0) as fn(Config) -> Dispositions,
+        ),
+        ("--comments", "--max-comments", |c| c.comments.0),
+        ("--originals", "--max-originals", |c| c.originals.0),
+        ("--visual-impaireds", "--max-visual-impaireds", |c| {
+            c.visual_impaireds.0
+        }),
+        ("--dubs", "--max-dubs", |c| c.dubs.0),
+    ] {
+        assert_eq!(Dispositions::default(), get(cfg::<_, &str>([])));
+        assert_eq!(single, get(cfg([arg, "true"])));
+        assert_eq!(max, get(cfg([max_arg, "2"])));
+    }
+}
+
 #[test]
 fn parse_max() {
     for (&i, _) in iter_i_lang() {
@@ -184,6 +214,8 @@ fn max_default() {
     let xs = Dispositions::default();
     assert_eq!(1, xs.max(DispositionType::Default));
     assert_eq!(0, xs.max(DispositionType::Forced));
+    assert_eq!(usize::MAX, xs.max(DispositionType::Comment));
+    assert_eq!(usize::MAX, xs.max(DispositionType::HearingImpaired));
 }
 
 #[test]
@@ -191,7 +223,11 @@ fn max_user() {
     let mut xs = Dispositions::default();
     for (&i, _) in iter_i_lang() {
         xs.max_in_auto = Some(i);
-        for ty in [DispositionType::Default, DispositionType::Forced] {
+        for ty in [
+            DispositionType::Default,
+            DispositionType::Forced,
+            DispositionType::Comment,
+        ] {
             assert_eq!(i, xs.max(ty))
         }
     }
@@ -214,3 +250,43 @@ build_test_to_json_args!(
     vec!["--forceds", "1:true,2:false,8:true"],
     vec!["--forceds", "false", "--max-forceds", "1"],
 );
+
+build_test_to_json_args!(
+    to_json_args_hearing_impaireds, hearing_impaireds, "hearing_impaireds";
+    vec![],
+    vec!["--max-hearing-impaireds", "5"],
+    vec!["--hearing-impaireds", "true"],
+    vec!["--hearing-impaireds", "1:true,2:false,8:true"],
+);
+
+build_test_to_json_args!(
+    to_json_args_comments, comments, "comments";
+    vec![],
+    vec!["--max-comments", "5"],
+    vec!["--comments", "true"],
+    vec!["--comments", "1:true,2:false,8:true"],
+);
+
+build_test_to_json_args!(
+    to_json_args_originals, originals, "originals";
+    vec![],
+    vec!["--max-originals", "5"],
+    vec!["--originals", "true"],
+    vec!["--originals", "1:true,2:false,8:true"],
+);
+
+build_test_to_json_args!(
+    to_json_args_visual_impaireds, visual_impaireds, "visual_impaireds";
+    vec![],
+    vec!["--max-visual-impaireds", "5"],
+    vec!["--visual-impaireds", "true"],
+    vec!["--visual-impaireds", "1:true,2:false,8:true"],
+);
+
+build_test_to_json_args!(
+    to_json_args_dubs, dubs, "dubs";
+    vec![],
+    vec!["--max-dubs", "5"],
+    vec!["--dubs", "true"],
+    vec!["--dubs", "1:true,2:false,8:true"],
+);
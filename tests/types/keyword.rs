@@ -0,0 +1,109 @@
+use crate::common::*;
+use mux_media::*;
+
+#[test]
+fn test_detect() {
+    [
+        (Keyword::Signs, "Signs"),
+        (Keyword::Signs, "[Group] Надписи"),
+        (Keyword::Forced, "eng.forced"),
+        (Keyword::Full, "Full Subs"),
+        (Keyword::Sdh, "English SDH"),
+        (Keyword::Sdh, "eng_cc"),
+        (Keyword::Sdh, "Hearing Impaired"),
+        (Keyword::Sdh, "Для слабослышащих"),
+        (Keyword::Commentary, "Director's Commentary"),
+        (Keyword::Commentary, "Комментарии"),
+    ]
+    .into_iter()
+    .for_each(|(kw, s)| {
+        let kws = Keywords::default().detect(s);
+        assert!(kws[kw], "from '{}'", s);
+        assert_eq!(1, kws.values().filter(|v| **v).count(), "from '{}'", s);
+    });
+}
+
+#[test]
+fn test_detect_none() {
+    [
+        "",
+        "English",
+        "design",
+        "fullhd",
+        "accent",
+        "hearing",
+        "impaired hearing",
+    ]
+    .into_iter()
+    .for_each(|s| {
+        let kws = Keywords::default().detect(s);
+        assert!(!kws.values().any(|v| *v), "from '{}'", s);
+    });
+}
+
+#[test]
+fn test_is_for() {
+    assert!(Keyword::Signs.is_for(StreamType::Sub));
+    assert!(Keyword::Sdh.is_for(StreamType::Sub));
+    assert!(Keyword::Commentary.is_for(StreamType::Audio));
+    assert!(!Keyword::Sdh.is_for(StreamType::Audio));
+    assert!(!Keyword::Commentary.is_for(StreamType::Video));
+}
+
+#[test]
+fn test_user_dictionaries() {
+    let kws: Keywords = r#"{
+        "signs": {"spa": ["Carteles"], "jpn": ["看板"]},
+        "sdh": {"eng": ["sdh"]},
+        "commentary": {"spa": ["comentarios del director"]}
+    }"#
+    .parse()
+    .unwrap();
+
+    [
+        (Keyword::Signs, "Español Carteles"),
+        (Keyword::Signs, "[字幕组]看板"),
+        (Keyword::Signs, "signs"),
+        (Keyword::Sdh, "English SDH"),
+        (Keyword::Commentary, "Comentarios del director"),
+    ]
+    .into_iter()
+    .for_each(|(kw, s)| assert!(kws.detect(s)[kw], "from '{}'", s));
+
+    // User words of eng replace built-in ones.
+    assert!(!kws.detect("eng cc")[Keyword::Sdh]);
+    assert!(!kws.detect("comentarios")[Keyword::Commentary]);
+}
+
+#[test]
+fn test_parse_err() {
+    [
+        "{",
+        "[]",
+        r#"{"unknown": {"eng": ["x"]}}"#,
+        r#"{"signs": ["x"]}"#,
+        r#"{"signs": {"eng": "x"}}"#,
+        r#"{"signs": {"eng": [1]}}"#,
+        "missing_keywords.json",
+    ]
+    .into_iter()
+    .for_each(|s| assert!(s.parse::<Keywords>().is_err(), "from '{}'", s));
+}
+
+#[test]
+fn parse_keywords() {
+    let json = r#"{"signs": {"spa": ["carteles"]}}"#;
+    let exp: Keywords = json.parse().unwrap();
+
+    assert_eq!(Keywords::default(), cfg::<_, &str>([]).keywords);
+    assert_eq!(exp, cfg(["--keywords", json]).keywords);
+
+    let file = temp_file("keywords/keywords.json", json);
+    assert_eq!(exp, cfg([p("--keywords"), file.as_path()]).keywords);
+}
+
+crate::build_test_to_json_args!(
+    to_json_args_keywords, keywords, "keywords";
+    vec![],
+    vec!["--keywords", r#"{"signs":{"spa":["carteles"]}}"#],
+);
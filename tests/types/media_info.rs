@@ -1,5 +1,7 @@
 #[path = "media_info/durations.rs"]
 mod durations;
+#[path = "media_info/sparse_subs.rs"]
+mod sparse_subs;
 
 use crate::{common::*, *};
 use mux_media::{markers::*, *};
@@ -238,6 +240,64 @@ fn test_path_tail() {
         })
 }
 
+#[test]
+fn test_delay() {
+    let mut mi = new();
+    let f = data("srt.srt");
+
+    mi.set_cmn(MICmnStem, "srt".into());
+    assert_eq!(&Delay(0), mi.get(MIDelay, &f).unwrap());
+
+    [
+        (-42, ".eng DELAY -42ms"),
+        (120, " delay 120ms"),
+        (0, ".eng"),
+        (0, ".DELAY 120"),
+    ]
+    .iter()
+    .for_each(|(exp, tail)| {
+        mi.clear();
+        mi.set_cmn(MICmnStem, "srt".into());
+        let _ = mi.get(MIPathTail, &f);
+        mi.set(MIPathTail, &f, tail.to_string());
+        assert_eq!(
+            &Delay(*exp),
+            mi.get(MIDelay, &f).unwrap(),
+            "from '{}'",
+            tail
+        );
+    });
+}
+
+#[test]
+fn test_sync_audio_delay_hints() {
+    let video = temp_file(
+        "sync_audio_hints/clip.mkv",
+        std::fs::read(data("vid_1s_and_aud_1.013s.mkv")).unwrap(),
+    );
+    let audio = temp_file(
+        "sync_audio_hints/clip delay 120ms.mka",
+        std::fs::read(data("audio_x1.mka")).unwrap(),
+    );
+
+    // The hint takes precedence with auto delays, and is ignored without them.
+    [(vec![], true), (vec!["--no-auto-delays"], false)]
+        .into_iter()
+        .for_each(|(extra, is_hint)| {
+            let input = temp("sync_audio_hints/");
+            let args = [p("-i"), &input, p("--sync-audio")];
+            let mut cfg = cfg(args.into_iter().chain(extra.iter().map(p)));
+            cfg.try_finalize_init().unwrap();
+            let mut mi = MediaInfo::new(&cfg, 0);
+            mi.try_insert(&video).unwrap();
+            mi.try_insert(&audio).unwrap();
+            mi.try_finalize_init().unwrap();
+
+            let delay = mi.immut(MIDelay, &audio).unwrap();
+            assert_eq!(is_hint, &Delay(120) == delay, "{:?}", delay);
+        });
+}
+
 #[test]
 fn test_relative_upmost() {
     // Upmost dir = data("")
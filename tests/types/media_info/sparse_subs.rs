@@ -0,0 +1,137 @@
+use crate::common::*;
+use mux_media::{markers::*, *};
+use std::{fmt::Write, path::PathBuf};
+
+fn write_srt(dir: &str, name: &str, cues: usize) -> PathBuf {
+    let mut s = String::new();
+    for i in 0..cues {
+        let start = i * 3;
+        let _ = write!(
+            s,
+            "{}\n00:{:02}:{:02},000 --> 00:{:02}:{:02},000\nCue {}\n\n",
+            i + 1,
+            start / 60,
+            start % 60,
+            (start + 2) / 60,
+            (start + 2) % 60,
+            i
+        );
+    }
+
+    temp_file(format!("{}/{}", dir, name), s)
+}
+
+// Writes the video of video_x1.mkv with a SubRip track of cues as of write_srt.
+fn write_video_srt(dir: &str, cues: usize) -> PathBuf {
+    use ffmpeg_next::{Packet, codec, decoder, format};
+
+    let dest = temp_file(format!("{}/video.mkv", dir), "");
+    let mut ictx = format::input(&data("video_x1.mkv")).unwrap();
+    let mut octx = format::output(&dest).unwrap();
+
+    let ist_time_base = {
+        let ist = ictx.stream(0).unwrap();
+        let mut ost = octx.add_stream(codec::Id::None).unwrap();
+        ost.set_parameters(ist.parameters());
+        ist.time_base()
+    };
+    let srt = codec::Context::new_with_codec(decoder::find(codec::Id::SUBRIP).unwrap());
+    octx.add_stream_with(&srt).unwrap();
+    octx.write_header().unwrap();
+
+    let sub_tb = octx.stream(1).unwrap().time_base();
+    let ts = |secs: usize| secs as i64 * sub_tb.1 as i64 / sub_tb.0 as i64;
+    for i in 0..cues {
+        let mut packet = Packet::copy(format!("Cue {}", i).as_bytes());
+        packet.set_pts(Some(ts(i * 3)));
+        packet.set_dts(Some(ts(i * 3)));
+        packet.set_duration(ts(2));
+        packet.set_stream(1);
+        packet.write_interleaved(&mut octx).unwrap();
+    }
+
+    let video_tb = octx.stream(0).unwrap().time_base();
+    for (ist, mut packet) in ictx.packets() {
+        if ist.index() != 0 {
+            continue;
+        }
+        packet.set_position(-1);
+        packet.rescale_ts(ist_time_base, video_tb);
+        packet.set_stream(0);
+        packet.write_interleaved(&mut octx).unwrap();
+    }
+
+    octx.write_trailer().unwrap();
+    dest
+}
+
+fn is_sparse(mi: &MediaInfo, src: &PathBuf) -> bool {
+    mi.immut(MIStreams, src).unwrap()[0].is_sparse
+}
+
+fn new_mi<'a>(cfg: &'a Config, files: &[&PathBuf]) -> MediaInfo<'a> {
+    let mut mi = MediaInfo::new(cfg, 0);
+    files.iter().for_each(|f| mi.try_insert(*f).unwrap());
+    mi.try_finalize_init_streams().unwrap();
+    mi
+}
+
+#[test]
+fn test_mark_sparse_subs() {
+    let dense = write_srt("sparse_subs", "a.srt", 100);
+    let sparse = write_srt("sparse_subs", "b.srt", 5);
+
+    let cfg = cfg::<_, &str>([]);
+    let mi = new_mi(&cfg, &[&dense, &sparse]);
+    assert!(!is_sparse(&mi, &dense));
+    assert!(is_sparse(&mi, &sparse));
+}
+
+#[test]
+fn test_mark_sparse_subs_against_embedded() {
+    let sparse = write_srt("sparse_subs_embedded", "b.srt", 5);
+    let video = write_video_srt("sparse_subs_embedded", 100);
+
+    let cfg = cfg::<_, &str>([]);
+    let mi = new_mi(&cfg, &[&video, &sparse]);
+    assert!(is_sparse(&mi, &sparse));
+
+    // Cues are shown longer than a part of the video without subs.
+    let mi = new_mi(&cfg, &[&data("video_x1.mkv"), &sparse]);
+    assert!(!is_sparse(&mi, &sparse));
+}
+
+#[test]
+fn test_not_mark_alone_or_equal_subs() {
+    let a = write_srt("sparse_subs_equal", "a.srt", 100);
+    let b = write_srt("sparse_subs_equal", "b.srt", 90);
+
+    let cfg = cfg::<_, &str>([]);
+    let mi = new_mi(&cfg, &[&a]);
+    assert!(!is_sparse(&mi, &a));
+
+    let mi = new_mi(&cfg, &[&a, &b]);
+    assert!(!is_sparse(&mi, &a));
+    assert!(!is_sparse(&mi, &b));
+}
+
+#[test]
+fn test_not_mark_sparse_subs_with_keyword() {
+    let dense = write_srt("sparse_subs_keyword", "a.srt", 100);
+    let signs = write_srt("sparse_subs_keyword", "b.signs.srt", 5);
+
+    let cfg = cfg::<_, &str>([]);
+    let mi = new_mi(&cfg, &[&dense, &signs]);
+    assert!(!is_sparse(&mi, &signs));
+    assert!(mi.immut(MIStreams, &signs).unwrap()[0].is_forced_hint());
+}
+
+#[test]
+fn test_not_mark_sparse_subs_without_auto_forceds() {
+    let dense = write_srt("sparse_subs_no_auto", "a.srt", 100);
+    let sparse = write_srt("sparse_subs_no_auto", "b.srt", 5);
+
+    let cfg = cfg(["--no-auto-forceds"]);
+    let mi = new_mi(&cfg, &[&dense, &sparse]);
+    assert!(!is_sparse(&mi, &sparse));
+}
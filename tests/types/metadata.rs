@@ -196,3 +196,28 @@ build_test_to_json_args!(
     vec!["--langs", "eng:und,rus:eng,und:rus"],
     vec!["--langs", "1:eng,2-8:rus,eng:und"],
 );
+
+build_test_to_json_args!(
+    to_json_args_codecs, codecs, "codecs";
+    vec![],
+    vec!["--codecs", "aac"],
+    vec!["--codecs", "0:aac,1:opus"],
+    vec!["--codecs", "eng:flac,und:aac"],
+);
+
+build_test_to_json_args!(
+    to_json_args_bitrates, bitrates, "bitrates";
+    vec![],
+    vec!["--bitrates", "192k"],
+    vec!["--bitrates", "0:1500k,1:192k"],
+    vec!["--bitrates", "0-1:640"],
+);
+
+build_test_to_json_args!(
+    to_json_args_delays, delays, "delays";
+    vec![],
+    vec!["--delays", "120"],
+    vec!["--delays", "-42"],
+    vec!["--delays", "0:-42,1:300"],
+    vec!["--delays", "eng:120,und:-80"],
+);
@@ -0,0 +1,104 @@
+use crate::common::*;
+use enum_map::{EnumMap, enum_map};
+use mux_media::*;
+
+fn new_plan() -> MuxPlan {
+    let track = |src: &str, i_stream, lang: &str| MuxPlanTrack {
+        src: data(src),
+        i_stream,
+        reencode: None,
+        name: None,
+        lang: lang.parse().unwrap(),
+        dispositions: EnumMap::default(),
+        delay: Delay::default(),
+        codec: None,
+        bitrate: None,
+    };
+
+    MuxPlan(vec![MuxPlanGroup {
+        output: temp("mux_plan/out.mkv"),
+        tracks: vec![
+            track("srt.mkv", 0, "und"),
+            MuxPlanTrack {
+                reencode: Some(CodecId(ffmpeg_next::codec::Id::AAC)),
+                name: Some("Commentary".into()),
+                delay: Delay(-120),
+                codec: Some("libopus".into()),
+                bitrate: Some(Bitrate(128_000)),
+                dispositions: enum_map! {
+                    DispositionType::Default | DispositionType::Comment => true,
+                    _ => false,
+                },
+                ..track("audio_x1.mka", 0, "eng")
+            },
+            MuxPlanTrack {
+                dispositions: enum_map! {
+                    DispositionType::Forced => true,
+                    _ => false,
+                },
+                reencode: Some(CodecId(ffmpeg_next::codec::Id::WEBVTT)),
+                delay: Delay(500),
+                ..track("sub_x1.srt", 0, "rus")
+            },
+        ],
+    }])
+}
+
+#[test]
+fn test_write_read() {
+    let json = temp_file("mux_plan/plan.json", "");
+    let plan = new_plan();
+
+    plan.try_write(&json).unwrap();
+    assert_eq!(plan, MuxPlan::try_read(&json).unwrap());
+}
+
+#[test]
+fn test_read_err() {
+    [
+        r#"{}"#,
+        r#"[{"tracks": []}]"#,
+        r#"[{"output": "out.mkv"}]"#,
+        r#"[{"output": "out.mkv", "tracks": [{"stream": 0}]}]"#,
+        r#"[{"output": "out.mkv", "tracks": [{"source": "a.mkv"}]}]"#,
+        r#"[{"output": "out.mkv", "tracks": [{"source": "a.mkv", "stream": 0, "reencode": "x"}]}]"#,
+        r#"[{"output": "out.mkv", "tracks": [{"source": "a.mkv", "stream": 0, "reencode": "libopus"}]}]"#,
+        r#"[{"output": "out.mkv", "tracks": [{"source": "a.mkv", "stream": 0, "delay": "x"}]}]"#,
+        r#"[{"output": "out.mkv", "tracks": [{"source": "a.mkv", "stream": 0, "bitrate": "x"}]}]"#,
+    ]
+    .iter()
+    .enumerate()
+    .for_each(|(i, s)| {
+        let json = temp_file(format!("mux_plan_err/{}.json", i), s);
+        assert!(MuxPlan::try_read(&json).is_err(), "from '{}'", s);
+    });
+}
+
+#[test]
+fn test_read_codec_names() {
+    use ffmpeg_next::codec::Id;
+
+    [
+        ("aac", Id::AAC),
+        ("opus", Id::OPUS),
+        ("h264", Id::H264),
+        ("mov_text", Id::MOV_TEXT),
+        ("webvtt", Id::WEBVTT),
+        ("subrip", Id::SUBRIP),
+        ("hdmv_pgs_subtitle", Id::HDMV_PGS_SUBTITLE),
+    ]
+    .into_iter()
+    .enumerate()
+    .for_each(|(i, (name, id))| {
+        assert_eq!(name, id.name());
+
+        let s = format!(
+            r#"[{{"output": "out.mkv", "tracks": [{{"source": "a.mkv", "stream": 0, "reencode": "{}"}}]}}]"#,
+            name
+        );
+        let json = temp_file(format!("mux_plan_codecs/{}.json", i), s);
+
+        let plan = MuxPlan::try_read(&json).unwrap();
+        assert_eq!(Some(CodecId(id)), plan[0].tracks[0].reencode);
+    });
+}
@@ -0,0 +1,346 @@
+use crate::common::*;
+use mux_media::*;
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+const PCS: u8 = 0x16;
+const END: u8 = 0x80;
+
+fn segment(pts_secs: u32, ty: u8, data: &[u8]) -> Vec<u8> {
+    let mut xs = b"PG".to_vec();
+    xs.extend_from_slice(&(pts_secs * 90_000).to_be_bytes());
+    xs.extend_from_slice(&(pts_secs * 90_000).to_be_bytes());
+    xs.push(ty);
+    xs.extend_from_slice(&(data.len() as u16).to_be_bytes());
+    xs.extend_from_slice(data);
+    xs
+}
+
+// A display set with composition objects of the flags; each object id is its flags.
+fn display_set(pts_secs: u32, flags: &[u8]) -> Vec<u8> {
+    let mut pcs = vec![
+        0x07,
+        0x80,
+        0x04,
+        0x38,
+        0x10,
+        0,
+        1,
+        0x80,
+        0,
+        0,
+        flags.len() as u8,
+    ];
+    for f in flags {
+        pcs.extend_from_slice(&[0, *f, 0, *f, 0, 10, 0, 20]);
+        if f & 0x80 != 0 {
+            pcs.extend_from_slice(&[0; 8]);
+        }
+    }
+    let mut xs = segment(pts_secs, PCS, &pcs);
+    xs.extend(segment(pts_secs, END, &[]));
+    xs
+}
+
+#[test]
+fn test_sup_round_trip() {
+    let sup = [display_set(1, &[0x40, 0]), display_set(2, &[])].concat();
+    let pgs = Pgs::try_from_sup(&sup).unwrap();
+    assert_eq!(sup, pgs.to_sup());
+
+    let objects = pgs.objects();
+    assert_eq!(2, objects.len());
+    assert_eq!((Duration::new(1, 0), true), objects[0]);
+    assert_eq!((Duration::new(1, 0), false), objects[1]);
+}
+
+#[test]
+fn test_forced_only() {
+    let sup = [
+        display_set(1, &[0, 0x40 | 0x80]),
+        display_set(2, &[]),
+        display_set(3, &[0]),
+        display_set(4, &[0x40]),
+    ]
+    .concat();
+
+    let forced = Pgs::try_from_sup(&sup)
+        .unwrap()
+        .forced_only()
+        .unwrap()
+        .unwrap();
+
+    let objects = forced.objects();
+    assert_eq!(2, objects.len());
+    assert!(objects.iter().all(|(_, is_forced)| *is_forced));
+    assert_eq!(Duration::new(1, 0), objects[0].0);
+    assert_eq!(Duration::new(4, 0), objects[1].0);
+
+    // Display sets and their timings are kept.
+    let expected = [
+        display_set(1, &[0x40 | 0x80]),
+        display_set(2, &[]),
+        display_set(3, &[]),
+        display_set(4, &[0x40]),
+    ]
+    .concat();
+    assert_eq!(expected, forced.to_sup());
+}
+
+#[test]
+fn test_forced_only_none() {
+    let sup = [display_set(1, &[0]), display_set(2, &[])].concat();
+    let pgs = Pgs::try_from_sup(&sup).unwrap();
+    assert_eq!(None, pgs.forced_only().unwrap());
+}
+
+#[test]
+fn test_sup_err() {
+    let sup = display_set(1, &[0x40]);
+    [&b"XG"[..], &sup[..12], &sup[..sup.len() - 1]]
+        .iter()
+        .for_each(|bytes| assert!(Pgs::try_from_sup(bytes).is_err()));
+}
+
+fn write_sup(dir: &str, sup: &[u8]) -> PathBuf {
+    temp_file(format!("{}/pgs.sup", dir), sup)
+}
+
+#[test]
+fn test_from_stream() {
+    let sup = [
+        display_set(1, &[0x40, 0]),
+        display_set(2, &[]),
+        display_set(3, &[0]),
+        display_set(4, &[]),
+    ]
+    .concat();
+    let src = write_sup("pgs_from_stream", &sup);
+
+    let pgs = Pgs::try_from_stream(&src, 0).unwrap();
+    assert_eq!(Pgs::try_from_sup(&sup).unwrap(), pgs);
+    assert_eq!(sup, pgs.to_sup());
+
+    assert!(Pgs::try_from_stream(&src, 1).unwrap().to_sup().is_empty());
+}
+
+#[test]
+fn test_split_forced_pgs() {
+    let sup = [
+        display_set(1, &[0x40, 0]),
+        display_set(2, &[]),
+        display_set(3, &[0]),
+        display_set(4, &[]),
+    ]
+    .concat();
+    let dir = "pgs_split_forced";
+    let src = write_sup(dir, &sup);
+
+    let mut cfg = cfg(["--split-forced-pgs", "0"]);
+    cfg.output.temp_dir = temp(dir);
+    let mut mi = MediaInfo::new(&cfg, 0);
+    mi.try_insert(&src).unwrap();
+    let order = StreamsOrder::new(&mut mi).unwrap();
+
+    assert_eq!(2, order.len());
+    let (forced, full) = (&order[0], &order[1]);
+
+    assert_eq!(src.as_path(), full.src());
+    assert_eq!(None, full.dispositions);
+
+    assert_ne!(src.as_path(), forced.src());
+    assert_eq!(0, forced.i_stream);
+    assert!(forced.dispositions.unwrap()[DispositionType::Forced]);
+
+    let expected = Pgs::try_from_sup(&sup)
+        .unwrap()
+        .forced_only()
+        .unwrap()
+        .unwrap();
+    let bytes = std::fs::read(forced.src()).unwrap();
+    assert_eq!(expected, Pgs::try_from_sup(&bytes).unwrap());
+    assert_eq!(expected, Pgs::try_from_stream(forced.src(), 0).unwrap());
+}
+
+#[test]
+fn test_not_split_without_forced() {
+    let sup = [display_set(1, &[0]), display_set(2, &[])].concat();
+    let dir = "pgs_split_none";
+    let src = write_sup(dir, &sup);
+
+    let mut cfg = cfg(["--split-forced-pgs", "0"]);
+    cfg.output.temp_dir = temp(dir);
+    let mut mi = MediaInfo::new(&cfg, 0);
+    mi.try_insert(&src).unwrap();
+    let order = StreamsOrder::new(&mut mi).unwrap();
+
+    assert_eq!(1, order.len());
+    assert_eq!(src.as_path(), order[0].src());
+}
+
+fn retimed(sup: &[u8], start: u64, end: u64) -> Vec<u8> {
+    Pgs::try_from_sup(sup)
+        .unwrap()
+        .retimed(Duration::from_secs(start), Duration::from_secs(end))
+        .to_sup()
+}
+
+#[test]
+fn test_retimed() {
+    let sup = [
+        display_set(1, &[0]),
+        display_set(3, &[]),
+        display_set(4, &[0x40]),
+        display_set(6, &[]),
+    ]
+    .concat();
+
+    // Keeps the caption showing at the start and clears the one showing at the end.
+    let expected = [
+        display_set(0, &[0]),
+        display_set(1, &[]),
+        display_set(2, &[0x40]),
+        display_set(3, &[]),
+    ]
+    .concat();
+    assert_eq!(expected, retimed(&sup, 2, 5));
+
+    assert_eq!(sup, retimed(&sup, 0, 7));
+    assert!(retimed(&sup, 0, 1).is_empty());
+    assert!(retimed(&sup, 7, 8).is_empty());
+}
+
+#[test]
+fn test_retimed_clears_at_end() {
+    let sup = [
+        display_set(1, &[0]),
+        display_set(2, &[0x40]),
+        display_set(3, &[]),
+    ]
+    .concat();
+
+    let clear = [0x07, 0x80, 0x04, 0x38, 0x10, 0, 2, 0, 0, 0, 0];
+    let expected = [
+        display_set(1, &[0]),
+        segment(2, PCS, &clear),
+        segment(2, END, &[]),
+    ]
+    .concat();
+    assert_eq!(expected, retimed(&sup, 0, 2));
+}
+
+// A DVD subpicture showing a 2x2 bitmap at once.
+const SPU: [u8; 30] = [
+    0, 30, 0, 6, 0x90, 0x90, 0, 0, 0, 6, 0x01, 0x03, 0x01, 0x23, 0x04, 0xFF, 0xF0, 0x05, 0, 0, 1,
+    0, 0, 1, 0x06, 0, 4, 0, 5, 0xFF,
+];
+
+// Writes the video of video_x1.mkv looped `loops` times with VobSub cues of
+// `(start, end)` seconds.
+fn write_vobsub(dir: &str, loops: i64, cues: &[(f64, f64)]) -> PathBuf {
+    use ffmpeg_next::{Packet, codec, decoder, format};
+
+    let dest = temp_file(format!("{}/in/vobsub.mkv", dir), "");
+    let mut ictx = format::input(&data("video_x1.mkv")).unwrap();
+    let mut octx = format::output(&dest).unwrap();
+
+    let ist_time_base = {
+        let ist = ictx.stream(0).unwrap();
+        let mut ost = octx.add_stream(codec::Id::None).unwrap();
+        ost.set_parameters(ist.parameters());
+        ist.time_base()
+    };
+    let dvdsub = codec::Context::new_with_codec(decoder::find(codec::Id::DVD_SUBTITLE).unwrap());
+    octx.add_stream_with(&dvdsub).unwrap();
+    octx.write_header().unwrap();
+
+    let sub_tb = octx.stream(1).unwrap().time_base();
+    let ts = |secs: f64| (secs * sub_tb.1 as f64 / sub_tb.0 as f64).round() as i64;
+    for (start, end) in cues {
+        let mut packet = Packet::copy(&SPU);
+        packet.set_pts(Some(ts(*start)));
+        packet.set_dts(Some(ts(*start)));
+        packet.set_duration(ts(end - start));
+        packet.set_stream(1);
+        packet.write_interleaved(&mut octx).unwrap();
+    }
+
+    let video_tb = octx.stream(0).unwrap().time_base();
+    let packets: Vec<Packet> = ictx
+        .packets()
+        .filter(|(ist, _)| ist.index() == 0)
+        .map(|(_, packet)| packet)
+        .collect();
+    let frame = packets[1].pts().unwrap() - packets[0].pts().unwrap();
+    let step = packets.iter().filter_map(|p| p.pts()).max().unwrap() + frame;
+
+    for i in 0..loops {
+        for packet in packets.iter() {
+            let mut packet = packet.clone();
+            packet.set_pts(packet.pts().map(|ts| ts + i * step));
+            packet.set_dts(packet.dts().map(|ts| ts + i * step));
+            packet.set_position(-1);
+            packet.rescale_ts(ist_time_base, video_tb);
+            packet.set_stream(0);
+            packet.write_interleaved(&mut octx).unwrap();
+        }
+    }
+
+    octx.write_trailer().unwrap();
+    dest
+}
+
+// Returns (pts, pts + duration) in seconds of packets of the stream type.
+fn packet_times(path: &Path, ty: ffmpeg_next::media::Type) -> Vec<(f64, f64)> {
+    let mut ictx = ffmpeg_next::format::input(path).unwrap();
+    ictx.packets()
+        .filter(|(ist, _)| ist.parameters().medium() == ty)
+        .filter_map(|(ist, packet)| {
+            let tb = ist.time_base();
+            let secs = |ts: i64| ts as f64 * tb.0 as f64 / tb.1 as f64;
+            let pts = packet.pts()?;
+            Some((secs(pts), secs(pts + packet.duration())))
+        })
+        .collect()
+}
+
+#[test]
+fn test_retime_vobsub() {
+    use ffmpeg_next::media::Type;
+
+    let dir = "vobsub_retime";
+    let cues = [(10.0, 14.0), (16.0, 17.0), (22.0, 26.0), (30.0, 31.0)];
+    let src = write_vobsub(dir, 40, &cues);
+    let out_arg = temp(format!("{}/out/,.mkv", dir));
+
+    let mut c = cfg([
+        p("-i"),
+        src.parent().unwrap(),
+        p("-o"),
+        &out_arg,
+        p("-e"),
+        p("--cut"),
+        p("12-24"),
+    ]);
+    c.try_finalize_init().unwrap();
+    let out = c.output.build_out("vobsub");
+    let _ = fs::remove_file(&out);
+    c.mux().unwrap();
+
+    let video_end = packet_times(&out, Type::Video)
+        .into_iter()
+        .fold(0.0, |end, (pts, _)| f64::max(end, pts));
+    let subs = packet_times(&out, Type::Subtitle);
+    let near = |a: f64, b: f64| assert!((a - b).abs() < 0.05, "{} != {}", a, b);
+
+    // The cue showing at the start is kept from zero, the one showing at the end
+    // is cut by it, and the gaps between cues stay as is.
+    assert_eq!(3, subs.len());
+    near(0.0, subs[0].0);
+    near(2.0, subs[1].0 - subs[0].1);
+    near(1.0, subs[1].1 - subs[1].0);
+    near(6.0, subs[2].0 - subs[1].0);
+    near(video_end, subs[2].1);
+}
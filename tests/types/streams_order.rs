@@ -66,6 +66,23 @@ fn test_it_signs_order() {
     body_test_order(&["--names", "0:НаДпИси", "--locale", "eng"], [0, 1, 2]);
 }
 
+#[test]
+fn test_keywords_order() {
+    body_test_order(&["--names", "0:forced", "--locale", "eng"], [0, 1, 2]);
+    body_test_order(&["--names", "1:Full", "--locale", "eng"], [1, 2, 0]);
+    body_test_order(&["--names", "1:SDH", "--locale", "eng"], [2, 0, 1]);
+    body_test_order(&["--names", "1:Commentary", "--locale", "eng"], [2, 0, 1]);
+    body_test_order(
+        &[
+            "--names",
+            "1:Commentary,2:Hearing Impaired",
+            "--locale",
+            "eng",
+        ],
+        [0, 2, 1],
+    );
+}
+
 #[test]
 fn test_track_type_order() {
     let file = data("streams_order/reverse_stream_types.mkv");
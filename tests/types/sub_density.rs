@@ -0,0 +1,24 @@
+use mux_media::*;
+
+#[test]
+fn test_is_sparse() {
+    let d = |cues, secs| SubDensity {
+        cues,
+        shown: Duration::new(secs, 0),
+    };
+    let video = Some(Duration::new(1440, 0));
+
+    [
+        (true, d(30, 60), vec![d(400, 900)], None),
+        (true, d(30, 60), vec![], video),
+        (false, d(400, 900), vec![d(30, 60)], video),
+        (false, d(300, 700), vec![d(400, 900)], video),
+        (false, d(30, 300), vec![d(400, 900)], video),
+        (false, d(0, 0), vec![d(400, 900)], video),
+        (false, d(30, 60), vec![], None),
+    ]
+    .into_iter()
+    .for_each(|(exp, sub, others, video)| {
+        assert_eq!(exp, sub.is_sparse(others, video), "{:?}", sub);
+    });
+}
@@ -0,0 +1,103 @@
+use crate::common::*;
+use mux_media::*;
+
+#[test]
+fn test_from_str() {
+    [
+        (SubStretch::Auto, "auto"),
+        (SubStretch::Auto, "AUTO"),
+        (SubStretch::Ratio(1.5), "1.5"),
+        (SubStretch::Ratio(25.0 / 24.0), "25:24"),
+        (SubStretch::Ratio(25.0 / 23.976), " 25 : 23.976 "),
+    ]
+    .iter()
+    .for_each(|(stretch, s)| {
+        assert_eq!(*stretch, s.parse::<SubStretch>().unwrap(), "from '{}'", s);
+    });
+}
+
+#[test]
+fn test_from_str_err() {
+    ["", "x", "0", "-1", "25:0", "25:", ":24"]
+        .iter()
+        .for_each(|s| assert!(s.parse::<SubStretch>().is_err(), "from '{}'", s));
+}
+
+fn assert_near(expected: f64, x: f64) {
+    assert!((expected - x).abs() < 1e-6, "{} != {}", expected, x);
+}
+
+#[test]
+fn test_detect_ratio() {
+    let secs = |s: f64| Duration::from(std::time::Duration::from_secs_f64(s));
+    let ntsc_film = 24000.0 / 1001.0;
+
+    [
+        (1.0, 1000.0, 1000.0),
+        (25.0 / ntsc_film, 1000.0, 1000.0 * 25.0 / ntsc_film),
+        (ntsc_film / 25.0, 1000.0, 1000.0 * ntsc_film / 25.0),
+        (25.0 / 24.0, 1000.0, 1000.0 * 25.0 / 24.0 - 2.0),
+    ]
+    .into_iter()
+    .for_each(|(expected, last_end, video)| {
+        let r = SubStretch::detect_ratio(secs(last_end), secs(video)).unwrap();
+        assert_near(expected, r);
+    });
+
+    // Credits without cues, or no cues at all.
+    [(900.0, 1000.0), (1000.0, 1100.0), (0.0, 1000.0)]
+        .into_iter()
+        .for_each(|(last_end, video)| {
+            assert_eq!(
+                None,
+                SubStretch::detect_ratio(secs(last_end), secs(video)),
+                "{} of {}",
+                last_end,
+                video
+            );
+        });
+}
+
+#[test]
+fn test_fps_ratios() {
+    let xs: Vec<f64> = SubStretch::fps_ratios().collect();
+    assert!(xs.contains(&1.0));
+    assert!(xs.contains(&(25.0 / 24.0)));
+    assert!(xs.contains(&(24.0 / 25.0)));
+    assert!(xs.iter().all(|r| *r > 0.75 && *r < 1.3));
+}
+
+#[test]
+fn test_subs_stretch() {
+    let src = temp_file(
+        "sub_stretch/in.srt",
+        "1\n00:00:01,000 --> 00:00:02,500\nOne\n\n2\n00:01:00,000 --> 00:01:04,000\nTwo\n",
+    );
+
+    let timings = |subs: &Subs| -> Vec<(f64, f64)> {
+        subs.iter_i_start_end()
+            .map(|(_, start, end)| {
+                (
+                    Duration::from(start).as_secs_f64(),
+                    Duration::from(end).as_secs_f64(),
+                )
+            })
+            .collect()
+    };
+
+    [1.0, 1.5, 25.0 / 24.0, 24.0 / 25.0]
+        .into_iter()
+        .for_each(|ratio| {
+            let mut subs = Subs::new(&src, SubType::Srt).unwrap();
+            let before = timings(&subs);
+            subs.stretch(ratio);
+            let after = timings(&subs);
+
+            assert_eq!(before.len(), after.len());
+            before.iter().zip(&after).for_each(|(b, a)| {
+                assert_near(b.0 * ratio, a.0);
+                assert_near(b.1 * ratio, a.1);
+            });
+            assert_near(64.0 * ratio, subs.last_end().unwrap().as_secs_f64());
+        });
+}
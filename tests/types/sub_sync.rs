@@ -0,0 +1,139 @@
+use mux_media::*;
+
+const FRAME_MS: u64 = SubSync::FRAME.as_millis() as u64;
+
+/// Returns speech spans in ms of pseudo-random length, with pauses between them.
+fn speech_spans(total_ms: u64, seed: u32) -> Vec<(u64, u64)> {
+    let mut x = seed;
+    let mut rand = |min: u64, max: u64| {
+        x = x.wrapping_mul(1103515245).wrapping_add(12345);
+        min + (x >> 16) as u64 % (max - min)
+    };
+
+    let mut spans = Vec::new();
+    let mut t = 0;
+    loop {
+        t += rand(300, 2500);
+        let end = t + rand(700, 4000);
+        if end > total_ms {
+            return spans;
+        }
+        spans.push((t, end));
+        t = end;
+    }
+}
+
+fn speech_frames(spans: &[(u64, u64)], total_ms: u64) -> Vec<bool> {
+    (0..total_ms / FRAME_MS)
+        .map(|i| {
+            let t = i * FRAME_MS;
+            spans.iter().any(|(s, e)| t >= *s && t < *e)
+        })
+        .collect()
+}
+
+fn cues(spans: &[(u64, u64)], shift_ms: i64, ratio: f64) -> Vec<(Duration, Duration)> {
+    let f = |t: u64| {
+        let ms = ((t as f64 / ratio) as i64 + shift_ms).max(0) as u64;
+        Duration(std::time::Duration::from_millis(ms))
+    };
+    spans.iter().map(|(s, e)| (f(*s), f(*e))).collect()
+}
+
+#[test]
+fn test_detect_offset() {
+    let total = 10 * 60 * 1000;
+    let spans = speech_spans(total, 7);
+    let speech = speech_frames(&spans, total);
+
+    [(0, 0), (-1500, 1500), (2000, -2000), (-60000, 60000)]
+        .iter()
+        .for_each(|(shift, offset)| {
+            let cues = cues(&spans, *shift, 1.0);
+            let sync = SubSync::new(&speech, &cues, &[1.0]).unwrap();
+            assert_eq!(Delay(*offset), sync.offset, "shift {}", shift);
+            assert_eq!(1.0, sync.ratio);
+            assert!(
+                sync.confidence >= SubSync::MIN_CONFIDENCE,
+                "shift {}",
+                shift
+            );
+        });
+}
+
+#[test]
+fn test_detect_stretch() {
+    let total = 10 * 60 * 1000;
+    let spans = speech_spans(total, 7);
+    let speech = speech_frames(&spans, total);
+    let ratios = [24.0 / 25.0, 1.0, 25.0 / 24.0];
+
+    ratios.iter().for_each(|ratio| {
+        let cues = cues(&spans, -1000, *ratio);
+        let sync = SubSync::new(&speech, &cues, &ratios).unwrap();
+        assert_eq!(*ratio, sync.ratio);
+        let offset = (1000.0 * ratio) as i64;
+        assert!(
+            (sync.offset.0 - offset).abs() <= FRAME_MS as i64,
+            "{:?}",
+            sync
+        );
+    });
+}
+
+#[test]
+fn test_reject_unrelated_cues() {
+    let total = 10 * 60 * 1000;
+    let speech = speech_frames(&speech_spans(total, 7), total);
+
+    [11, 13, 42, 99].iter().for_each(|seed| {
+        let cues = cues(&speech_spans(total, *seed), 0, 1.0);
+        let sync = SubSync::new(&speech, &cues, &[1.0]);
+        assert!(
+            sync.is_none_or(|s| s.confidence < SubSync::MIN_CONFIDENCE),
+            "seed {}: {:?}",
+            seed,
+            sync
+        );
+    });
+}
+
+#[test]
+fn test_none_without_speech_or_cues() {
+    let cues = [(Duration::new(1, 0), Duration::new(2, 0))];
+    assert_eq!(None, SubSync::new(&vec![false; 500], &cues, &[1.0]));
+    assert_eq!(None, SubSync::new(&vec![true; 500], &cues, &[1.0]));
+
+    let speech: Vec<bool> = (0..500).map(|i| i % 100 < 50).collect();
+    assert_eq!(None, SubSync::new(&speech, &[], &[1.0]));
+}
+
+#[test]
+fn test_detect_speech() {
+    let rate = 4000;
+    let frame = rate as usize * FRAME_MS as usize / 1000;
+    // Loud bursts with a short pause inside, over a quiet hiss.
+    let loud = |i: usize| (i / frame) % 100 >= 40 && (i / frame) % 100 != 70;
+    let samples: Vec<f32> = (0..rate as usize * 20)
+        .map(|i| {
+            let hiss = ((i * 7919) % 13) as f32 / 13.0 - 0.5;
+            if loud(i) {
+                (i as f32 * 0.7).sin() * 0.8 + hiss * 0.01
+            } else {
+                hiss * 0.01
+            }
+        })
+        .collect();
+
+    let speech = SubSync::detect_speech(&samples, rate);
+    assert_eq!(1000, speech.len());
+    speech.iter().enumerate().for_each(|(i, s)| {
+        assert_eq!(i % 100 >= 40, *s, "frame {}", i);
+    });
+
+    assert!(
+        SubSync::detect_speech(&vec![0.0; 8000], rate)
+            .iter()
+            .all(|s| !s)
+    );
+}
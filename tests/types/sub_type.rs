@@ -0,0 +1,34 @@
+use mux_media::*;
+
+#[test]
+fn test_from_str() {
+    [
+        (SubType::Srt, "srt"),
+        (SubType::Ssa, "ass"),
+        (SubType::Ssa, "SSA"),
+        (SubType::Vtt, "vtt"),
+        (SubType::Vtt, "webvtt"),
+    ]
+    .iter()
+    .for_each(|(ty, s)| {
+        assert_eq!(*ty, s.parse::<SubType>().unwrap(), "from '{}'", s);
+    });
+}
+
+#[test]
+fn test_from_str_err() {
+    ["", "pgs", "sub", "x"]
+        .iter()
+        .for_each(|s| assert!(s.parse::<SubType>().is_err(), "from '{}'", s));
+}
+
+#[test]
+fn test_display() {
+    [
+        (SubType::Srt, "srt"),
+        (SubType::Ssa, "ass"),
+        (SubType::Vtt, "vtt"),
+    ]
+    .iter()
+    .for_each(|(ty, s)| assert_eq!(*s, ty.to_string()));
+}